@@ -5,97 +5,37 @@
 //! eframe::App trait for the main GUI update loop.
 
 use eframe::egui;
-use image::DynamicImage;
 use serde::{Serialize, Deserialize};
+use std::borrow::Cow;
 use std::path::PathBuf;
 
-use crate::types::*;
-use crate::qr;
+use qrtistry::types::*;
+use qrtistry::qr::{self, QrSpec};
+use qrtistry::qr::occlusion;
+use qrtistry::qr::scannability::ScannabilityReport;
 use crate::ui;
 use crate::io;
 
 /// Main application state structure
 ///
-/// Contains all configuration options, runtime state, and UI data.
-/// Most fields are serializable for preset save/load functionality.
+/// Holds the rendering specification plus runtime and UI data.
+/// The specification and the selected tab are serialized for preset
+/// save/load functionality.
 #[derive(Serialize, Deserialize)]
 pub struct QrCodeApp {
-    // === Content Settings ===
-    /// Text content to encode in the QR code
-    pub qr_text: String,
+    /// Everything needed to render the QR code (flattened into presets)
+    #[serde(flatten)]
+    pub spec: QrSpec,
     
-    /// Output size of the QR code image in pixels (128-2048)
-    pub size: u32,
-    
-    /// Border width in modules (quiet zone around QR code)
-    pub border: u32,
-    
-    /// Error correction level (affects reliability and capacity)
-    pub ec_level: ErrorCorrectionLevel,
-    
-    // === Color Settings ===
-    /// Foreground color for dark modules (RGB 0-255)
-    pub fg_color: [u8; 3],
-    
-    /// Background color for light areas (RGB 0-255)
-    pub bg_color: [u8; 3],
-    
-    /// Enable gradient color blending
-    pub use_gradient: bool,
-    
-    /// Type of gradient to apply (horizontal, vertical, etc.)
-    pub gradient_type: GradientType,
-    
-    /// Second color for gradient blending (RGB 0-255)
-    pub gradient_color: [u8; 3],
-    
-    // === Module Styling ===
-    /// Visual style for data modules (square, circle, etc.)
-    pub module_style: ModuleStyle,
-    
-    /// Enable extra rounding on rounded modules
-    pub use_rounded_corners: bool,
-    
-    /// Corner radius for rounded modules (0.0-1.0)
-    pub corner_radius: f32,
-    
-    // === Eye (Finder Pattern) Styling ===
-    /// Visual style for the three corner finder patterns
-    pub eye_style: EyeStyle,
-    
-    /// Use a custom color for eye patterns
-    pub use_custom_eye_color: bool,
-    
-    /// Custom color for eye patterns if enabled (RGB 0-255)
-    pub eye_color: [u8; 3],
-    
-    // === Image Integration ===
+    // === Image Sources ===
     /// Path to logo image file (not serialized)
     #[serde(skip)]
     pub logo_path: Option<PathBuf>,
     
-    /// Loaded logo image data (not serialized)
-    #[serde(skip)]
-    pub logo_image: Option<DynamicImage>,
-    
-    /// Logo size as fraction of QR code (0.05-0.35)
-    pub logo_size: f32,
-    
     /// Path to background image file (not serialized)
     #[serde(skip)]
     pub bg_image_path: Option<PathBuf>,
     
-    /// Loaded background image data (not serialized)
-    #[serde(skip)]
-    pub bg_image: Option<DynamicImage>,
-    
-    /// Background image opacity (0.0-1.0)
-    pub bg_image_opacity: f32,
-    
-    // === Advanced Settings ===
-    /// Overall QR code opacity (0.0-1.0) for watermark effects
-    pub qr_opacity: f32,
-    
     // === UI State ===
     /// Currently selected tab in the UI
     pub selected_tab: TabSelection,
@@ -104,6 +44,14 @@ pub struct QrCodeApp {
     #[serde(skip)]
     pub preview_texture: Option<egui::TextureHandle>,
     
+    /// Decode check and robustness margins of the current preview (not serialized)
+    #[serde(skip)]
+    pub scannability: Option<ScannabilityReport>,
+    
+    /// Background measurement of the degradations and its cache (not serialized)
+    #[serde(skip)]
+    pub scannability_job: ui::preview::ScannabilityJob,
+    
     /// Status message displayed to user (not serialized)
     #[serde(skip)]
     pub status_message: String,
@@ -111,53 +59,36 @@ pub struct QrCodeApp {
     /// Flag to trigger auto-preview on first frame (not serialized)
     #[serde(skip)]
     pub first_frame: bool,
+    
+    /// Batch generation dialog state (not serialized)
+    #[serde(skip)]
+    pub batch_dialog: ui::batch::BatchDialog,
+
+    /// Export format dialog state (not serialized)
+    #[serde(skip)]
+    pub export_dialog: ui::export::ExportDialog,
 }
 
 impl Default for QrCodeApp {
     /// Create a new application with sensible default settings
     fn default() -> Self {
         Self {
-            // Default content
-            qr_text: String::from("https://oliverbonhamcarter.com"),
-            
-            // Default dimensions
-            size: 512,
-            border: 2,
-            ec_level: ErrorCorrectionLevel::Medium,
-            
-            // Default colors (classic black on white)
-            fg_color: [0, 0, 0],
-            bg_color: [255, 255, 255],
-            use_gradient: false,
-            gradient_type: GradientType::Horizontal,
-            gradient_color: [100, 100, 255],
-            
-            // Default module style (classic square)
-            module_style: ModuleStyle::Square,
-            use_rounded_corners: false,
-            corner_radius: 0.3,
-            
-            // Default eye style (standard)
-            eye_style: EyeStyle::Standard,
-            use_custom_eye_color: false,
-            eye_color: [255, 0, 0],
+            // Default rendering settings
+            spec: QrSpec::default(),
             
             // No images by default
             logo_path: None,
-            logo_image: None,
-            logo_size: 0.2,
             bg_image_path: None,
-            bg_image: None,
-            bg_image_opacity: 0.3,
-            
-            // Default opacity (fully opaque)
-            qr_opacity: 1.0,
             
             // UI state
             selected_tab: TabSelection::Basic,
             preview_texture: None,
+            scannability: None,
+            scannability_job: ui::preview::ScannabilityJob::default(),
             status_message: String::from("Ready to generate QR code"),
             first_frame: true,
+            batch_dialog: ui::batch::BatchDialog::default(),
+            export_dialog: ui::export::ExportDialog::default(),
         }
     }
 }
@@ -177,6 +108,11 @@ impl eframe::App for QrCodeApp {
             self.generate_preview(ctx);
         }
         
+        // Pick up a finished background scannability measurement
+        if let Some(report) = self.scannability_job.poll() {
+            self.scannability = Some(report);
+        }
+        
         // === Top Panel: Title and Action Buttons ===
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -198,8 +134,12 @@ impl eframe::App for QrCodeApp {
                         io::save_preset(self);
                     }
                     
-                    if ui.button("💾 Save PNG").clicked() {
-                        io::save_qr_code(self);
+                    if ui.button("📦 Batch").clicked() {
+                        self.batch_dialog.open = true;
+                    }
+                    
+                    if ui.button("💾 Save").clicked() {
+                        self.export_dialog.open = true;
                     }
                     
                     if ui.button("🔄 Generate Preview").clicked() {
@@ -233,6 +173,10 @@ impl eframe::App for QrCodeApp {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui::render_preview(self, ui, ctx);
         });
+        
+        // === Floating Windows ===
+        ui::render_batch_dialog(self, ctx);
+        ui::render_export_dialog(self, ctx);
     }
 }
 
@@ -240,22 +184,37 @@ impl QrCodeApp {
     /// Generate QR code and update preview texture
     ///
     /// This is the main entry point for creating the QR code visual.
-    /// It generates the image using the QR module and converts it to an egui texture.
+    /// It generates the image using the QR module, checks that it decodes,
+    /// starts measuring the simulated degradations in the background (see
+    /// `ScannabilityJob`), and converts it to an egui texture.
     ///
     /// # Arguments
     /// * `ctx` - egui context for texture loading
     pub fn generate_preview(&mut self, ctx: &egui::Context) {
         // Validate input
-        if self.qr_text.is_empty() {
+        if self.spec.qr_text.is_empty() {
             self.status_message = "⚠️ Please enter text for the QR code".to_string();
             return;
         }
 
         self.status_message = "🔄 Generating QR code...".to_string();
+
+        // Keep the logo within the error correction budget (if auto-fit is on)
+        // on a copy, so repeated previews do not compound the adjustment
+        let (spec, adjustment) = self.fitted_spec();
+        let spec = spec.into_owned();
         
-        // Generate QR code image
-        match qr::generate_qr_image(self) {
+        // Generate QR code image (all codes on one sheet for a Structured Append sequence)
+        let image = match spec.structured_append {
+            Some(_) => qr::structured::render_sheet(&spec),
+            None => qr::generate_qr_image(&spec),
+        };
+
+        match image {
             Ok(img) => {
+                // Decode check now; the simulated degradations follow in the background
+                self.scannability = self.scannability_job.start(&img, &spec, ctx);
+                
                 let width = img.width() as usize;
                 let height = img.height() as usize;
                 
@@ -277,10 +236,34 @@ impl QrCodeApp {
                 ));
                 
                 self.status_message = format!("✅ QR code generated successfully! ({}x{})", width, height);
+                if let Some(adjustment) = adjustment {
+                    self.status_message.push_str(&format!(" 💡 {} (preview and saved file only)", adjustment));
+                }
             }
             Err(e) => {
+                self.scannability = None;
+                self.scannability_job.cancel();
                 self.status_message = format!("❌ Error: {}", e);
             }
         }
     }
+
+    /// Specification to render, with the logo fit applied to a copy
+    ///
+    /// The user's settings stay as they are, so the adjustment is
+    /// recomputed from them every time instead of building on itself.
+    ///
+    /// # Returns
+    /// The specification (borrowed when nothing changes) and a description
+    /// of the adjustment, if any. Errors resurface when rendering.
+    pub fn fitted_spec(&self) -> (Cow<'_, QrSpec>, Option<String>) {
+        if self.spec.logo_fit == LogoFit::Off || self.spec.logo_image.is_none() {
+            return (Cow::Borrowed(&self.spec), None);
+        }
+        let mut spec = self.spec.clone();
+        match occlusion::fit_logo(&mut spec) {
+            Ok(Some(adjustment)) => (Cow::Owned(spec), Some(adjustment)),
+            _ => (Cow::Borrowed(&self.spec), None),
+        }
+    }
 }
@@ -0,0 +1,537 @@
+//! Batch QR code generation
+//!
+//! Generates one QR code per row of a CSV or JSONL file, applying the same
+//! style settings to every row:
+//! - Row fields are substituted into the content template (`{field}`), or
+//!   into the fields of a structured payload such as a URL with UTM
+//!   parameters, which is then rebuilt and validated per row
+//! - Row fields are substituted into the output filename template
+//! - Each rendered code can be decoded again to check that it scans
+//! - Per-row failures are collected and written to a summary file instead
+//!   of aborting the whole run
+//! - Progress is reported after every row, and the run can be cancelled
+//!
+//! Used by both the `batch` CLI subcommand and the GUI batch dialog.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::qr::{eci, occlusion, verify, QrSpec};
+use crate::export;
+use crate::payload::Payload;
+
+/// A single input row: field name → value
+pub type Row = BTreeMap<String, String>;
+
+/// Default output filename template (uses the 1-based row number)
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "qrcode_{row}.png";
+
+/// Default summary filename written into the output directory
+pub const SUMMARY_FILENAME: &str = "batch_summary.csv";
+
+/// Settings for a batch run
+pub struct BatchJob {
+    /// CSV (with header row) or JSONL input file
+    pub input: PathBuf,
+
+    /// Directory where generated images and the summary are written
+    pub output_dir: PathBuf,
+
+    /// Content template; `None` uses the preset's payload fields, or its
+    /// `qr_text`, as the template
+    pub text_template: Option<String>,
+
+    /// Output filename template, e.g. `"label_{sku}.png"`
+    pub filename_template: String,
+
+    /// Decode every generated code and fail rows that do not round-trip
+    pub verify: bool,
+}
+
+/// Outcome of a single row
+pub struct RowResult {
+    /// 1-based row number (excluding the CSV header)
+    pub row: usize,
+
+    /// Output file path if one was determined
+    pub file: Option<PathBuf>,
+
+    /// Error message if the row failed
+    pub error: Option<String>,
+
+    /// Problem that did not stop the row, e.g. characters outside the
+    /// character set
+    pub warning: Option<String>,
+}
+
+/// Outcome of a whole batch run
+pub struct BatchReport {
+    /// Per-row results in input order
+    pub rows: Vec<RowResult>,
+
+    /// Location of the written summary file
+    pub summary_path: PathBuf,
+
+    /// Whether the run was cancelled before the last row
+    pub cancelled: bool,
+}
+
+impl BatchReport {
+    /// Number of rows that produced an image
+    pub fn succeeded(&self) -> usize {
+        self.rows.iter().filter(|r| r.error.is_none()).count()
+    }
+
+    /// Number of rows that failed
+    pub fn failed(&self) -> usize {
+        self.rows.len() - self.succeeded()
+    }
+}
+
+/// Run a batch job with the given base settings
+///
+/// The settings in `spec` (typically a loaded preset) are applied to every
+/// row. Only the content (`qr_text` and `payload`) changes between rows; it
+/// is restored afterwards. Without a content template, a preset payload
+/// has the row values substituted into its text fields, so every row gets
+/// its own escaped and validated content (e.g. a tracked link per flyer).
+///
+/// # Arguments
+/// * `spec` - Style settings to apply to every row
+/// * `job` - Input, output and template settings
+///
+/// # Returns
+/// * `Ok(BatchReport)` - Run completed (individual rows may still have failed)
+/// * `Err(String)` - The input could not be read or the output directory
+///   or summary file could not be written
+pub fn run_batch(spec: &mut QrSpec, job: &BatchJob) -> Result<BatchReport, String> {
+    run_batch_with_progress(spec, job, |_, _| true)
+}
+
+/// Run a batch job, reporting progress after every row
+///
+/// Same as `run_batch`, for callers that show progress (e.g. from a worker
+/// thread). Returning `false` from `progress` cancels the run after the
+/// current row; the summary then lists the rows processed so far.
+///
+/// # Arguments
+/// * `spec` - Style settings to apply to every row
+/// * `job` - Input, output and template settings
+/// * `progress` - Called with the number of rows done and the total
+///
+/// # Returns
+/// * `Ok(BatchReport)` - Run completed or cancelled (individual rows may still have failed)
+/// * `Err(String)` - The input could not be read or the output directory
+///   or summary file could not be written
+pub fn run_batch_with_progress(
+    spec: &mut QrSpec,
+    job: &BatchJob,
+    mut progress: impl FnMut(usize, usize) -> bool,
+) -> Result<BatchReport, String> {
+    let rows = read_rows(&job.input)?;
+    let total = rows.len();
+
+    std::fs::create_dir_all(&job.output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let text_template = job.text_template.clone().unwrap_or_else(|| spec.qr_text.clone());
+    let original_text = std::mem::take(&mut spec.qr_text);
+    let original_payload = spec.payload.take();
+    // An explicit content template replaces the payload
+    let payload_template = original_payload.as_ref().filter(|_| job.text_template.is_none());
+    let mut used_names = HashSet::new();
+    let mut results = Vec::with_capacity(rows.len());
+    let mut cancelled = false;
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+        let prepared = row.and_then(|fields| {
+            prepare_row(&fields, row_number, &text_template, payload_template, job, &mut used_names)
+        });
+
+        let result = match prepared {
+            Ok((file, text, payload)) => {
+                spec.qr_text = text;
+                spec.payload = payload;
+                let error = render_row(spec, &file, job.verify).err();
+                let warning = eci::warning(&spec.qr_text, spec.character_set);
+                RowResult { row: row_number, file: Some(file), error, warning }
+            }
+            Err(e) => RowResult { row: row_number, file: None, error: Some(e), warning: None },
+        };
+
+        results.push(result);
+
+        if !progress(row_number, total) && row_number < total {
+            cancelled = true;
+            break;
+        }
+    }
+
+    spec.qr_text = original_text;
+    spec.payload = original_payload;
+
+    let summary_path = job.output_dir.join(SUMMARY_FILENAME);
+    write_summary(&summary_path, &results)?;
+
+    Ok(BatchReport { rows: results, summary_path, cancelled })
+}
+
+/// Resolve the output path, content and payload for one row
+fn prepare_row(
+    fields: &Row,
+    row_number: usize,
+    text_template: &str,
+    payload_template: Option<&Payload>,
+    job: &BatchJob,
+    used_names: &mut HashSet<String>,
+) -> Result<(PathBuf, String, Option<Payload>), String> {
+    let (text, payload) = match payload_template {
+        Some(template) => {
+            let mut payload = template.clone();
+            for field in payload.text_fields_mut() {
+                *field = expand_template(field, fields, row_number, false)?;
+            }
+            (payload.to_text()?, Some(payload))
+        }
+        None => (expand_template(text_template, fields, row_number, false)?, None),
+    };
+    if text.is_empty() {
+        return Err("Content is empty".to_string());
+    }
+
+    let filename = expand_template(&job.filename_template, fields, row_number, true)?;
+    if filename.is_empty() {
+        return Err("Output filename is empty".to_string());
+    }
+    if !used_names.insert(filename.clone()) {
+        return Err(format!("Duplicate output filename '{}'", filename));
+    }
+
+    Ok((job.output_dir.join(filename), text, payload))
+}
+
+/// Generate and save the QR code for one row, optionally verifying it
+///
+/// The logo is fitted to the row's content first (see `QrSpec::logo_fit`).
+/// A code that fails verification is still written so it can be inspected.
+fn render_row(spec: &QrSpec, path: &Path, verify: bool) -> Result<(), String> {
+    // Each row has its own content, so fit the logo per row
+    let mut spec = spec.clone();
+    occlusion::fit_logo(&mut spec)?;
+    if spec.structured_append.is_some() {
+        export::export_sequence(&spec, path)?;
+    } else {
+        export::export_to_path(&spec, path)?;
+    }
+
+    if verify {
+        // Symbols the decoder cannot read (Micro QR, rMQR) are not failures
+        let verification = verify::verify_spec(&spec)?;
+        if !verification.passed() && !verification.skipped() {
+            return Err(verification.summary());
+        }
+    }
+    Ok(())
+}
+
+/// Read all rows from a CSV or JSONL file
+///
+/// The format is chosen from the file extension (`.jsonl`/`.ndjson` for
+/// JSON Lines, anything else is treated as CSV with a header row).
+/// Malformed rows are returned as `Err` so they can be reported individually.
+///
+/// # Returns
+/// * `Ok(Vec<Result<Row, String>>)` - One entry per data row
+/// * `Err(String)` - The file could not be opened at all
+pub fn read_rows(path: &Path) -> Result<Vec<Result<Row, String>>, String> {
+    let is_jsonl = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("jsonl") || e.eq_ignore_ascii_case("ndjson"))
+        .unwrap_or(false);
+
+    if is_jsonl {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(parse_jsonl(&content))
+    } else {
+        let reader = csv::Reader::from_path(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        parse_csv(reader)
+    }
+}
+
+/// Parse CSV records (first line is the header) into rows
+fn parse_csv<R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<Vec<Result<Row, String>>, String> {
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .clone();
+
+    Ok(reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Malformed CSV row: {}", e))?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+                .collect())
+        })
+        .collect())
+}
+
+/// Parse JSON Lines content into rows
+///
+/// Each non-blank line must be a JSON object. String values are used as-is;
+/// numbers and booleans use their JSON text; nulls become empty strings.
+fn parse_jsonl(content: &str) -> Vec<Result<Row, String>> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line)
+                .map_err(|e| format!("Malformed JSON row: {}", e))?;
+            let object = value
+                .as_object()
+                .ok_or_else(|| "JSON row is not an object".to_string())?;
+            Ok(object
+                .iter()
+                .map(|(k, v)| {
+                    let text = match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        serde_json::Value::Null => String::new(),
+                        other => other.to_string(),
+                    };
+                    (k.clone(), text)
+                })
+                .collect())
+        })
+        .collect()
+}
+
+/// Substitute `{field}` placeholders in a template
+///
+/// `{row}` is always available and expands to the 1-based row number unless
+/// the row has its own `row` field. `{{` and `}}` produce literal braces.
+///
+/// # Arguments
+/// * `template` - Template text
+/// * `fields` - Row values
+/// * `row_number` - 1-based row number
+/// * `for_filename` - Replace path separators and other unsafe characters
+///   in substituted values so a row cannot write outside the output directory
+///
+/// # Returns
+/// * `Ok(String)` - Expanded text
+/// * `Err(String)` - Unknown field or unbalanced brace
+pub fn expand_template(
+    template: &str,
+    fields: &Row,
+    row_number: usize,
+    for_filename: bool,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => name.push(ch),
+                        None => return Err(format!("Unclosed '{{' in template '{}'", template)),
+                    }
+                }
+                let name = name.trim();
+                let value = match fields.get(name) {
+                    Some(value) => value.clone(),
+                    None if name == "row" => row_number.to_string(),
+                    None => return Err(format!("Unknown field '{}' in template", name)),
+                };
+                if for_filename {
+                    output.push_str(&sanitize_filename_part(&value));
+                } else {
+                    output.push_str(&value);
+                }
+            }
+            '}' => return Err(format!("Unmatched '}}' in template '{}'", template)),
+            _ => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Replace characters that are unsafe in filenames with underscores
+fn sanitize_filename_part(value: &str) -> String {
+    let cleaned: String = value
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    // Prevent "." and ".." from escaping the output directory
+    if cleaned.chars().all(|c| c == '.') {
+        cleaned.replace('.', "_")
+    } else {
+        cleaned
+    }
+}
+
+/// Write the per-row summary CSV (row, status, file, error)
+fn write_summary(path: &Path, results: &[RowResult]) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path)
+        .map_err(|e| format!("Failed to write summary: {}", e))?;
+
+    writer
+        .write_record(["row", "status", "file", "error", "warning"])
+        .map_err(|e| format!("Failed to write summary: {}", e))?;
+
+    for result in results {
+        let row = result.row.to_string();
+        let status = if result.error.is_none() { "ok" } else { "failed" };
+        let file = result
+            .file
+            .as_ref()
+            .map(|f| f.display().to_string())
+            .unwrap_or_default();
+        let error = result.error.clone().unwrap_or_default();
+        let warning = result.warning.clone().unwrap_or_default();
+        writer
+            .write_record([row.as_str(), status, file.as_str(), error.as_str(), warning.as_str()])
+            .map_err(|e| format!("Failed to write summary: {}", e))?;
+    }
+
+    writer.flush().map_err(|e| format!("Failed to write summary: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> Row {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let fields = row(&[("sku", "A-100"), ("name", "Widget")]);
+        assert_eq!(
+            expand_template("https://example.com/{sku}?n={name}", &fields, 3, false).unwrap(),
+            "https://example.com/A-100?n=Widget"
+        );
+        assert_eq!(expand_template("qr_{row}.png", &fields, 3, true).unwrap(), "qr_3.png");
+        assert_eq!(expand_template("{{literal}}", &fields, 1, false).unwrap(), "{literal}");
+        assert!(expand_template("{missing}", &fields, 1, false).is_err());
+        assert!(expand_template("{sku", &fields, 1, false).is_err());
+    }
+
+    #[test]
+    fn test_payload_fields_are_filled_per_row() {
+        let job = BatchJob {
+            input: PathBuf::from("rows.csv"),
+            output_dir: PathBuf::from("out"),
+            text_template: None,
+            filename_template: "flyer_{store}.png".to_string(),
+            verify: false,
+        };
+        let template = Payload::Url {
+            url: "example.com/offer".to_string(),
+            source: "flyer".to_string(),
+            medium: "print".to_string(),
+            campaign: "spring_sale".to_string(),
+            term: String::new(),
+            content: "{store}".to_string(),
+            params: vec![crate::payload::QueryParameter { name: "ref".to_string(), value: "{row}".to_string() }],
+        };
+        let fields = row(&[("store", "Main St & 5th")]);
+        let (file, text, payload) =
+            prepare_row(&fields, 4, "", Some(&template), &job, &mut HashSet::new()).unwrap();
+        assert_eq!(file, PathBuf::from("out/flyer_Main St & 5th.png"));
+        assert_eq!(
+            text,
+            "https://example.com/offer?utm_source=flyer&utm_medium=print&utm_campaign=spring_sale\
+             &utm_content=Main%20St%20%26%205th&ref=4"
+        );
+        assert!(matches!(payload, Some(Payload::Url { ref content, .. }) if content == "Main St & 5th"));
+
+        // Rows are validated like the form
+        let fields = row(&[("store", "")]);
+        let mut bad = template.clone();
+        *bad.text_fields_mut()[0] = "{store}".to_string();
+        assert!(prepare_row(&fields, 5, "", Some(&bad), &job, &mut HashSet::new()).unwrap_err().contains("URL is required"));
+    }
+
+    #[test]
+    fn test_filename_values_are_sanitized() {
+        let fields = row(&[("path", "../etc/passwd"), ("dots", "..")]);
+        assert_eq!(expand_template("{path}.png", &fields, 1, true).unwrap(), ".._etc_passwd.png");
+        assert_eq!(expand_template("{dots}", &fields, 1, true).unwrap(), "__");
+    }
+
+    #[test]
+    fn test_parse_jsonl() {
+        let rows = parse_jsonl("{\"sku\": \"A1\", \"qty\": 5}\n\nnot json\n[1, 2]\n");
+        assert_eq!(rows.len(), 3);
+        let first = rows[0].as_ref().unwrap();
+        assert_eq!(first["sku"], "A1");
+        assert_eq!(first["qty"], "5");
+        assert!(rows[1].is_err());
+        assert!(rows[2].is_err());
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let data = "sku,name\nA1,Widget\nB2,\"Gadget, large\"\n";
+        let rows = parse_csv(csv::Reader::from_reader(data.as_bytes())).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].as_ref().unwrap()["name"], "Gadget, large");
+    }
+
+    #[test]
+    fn test_progress_and_cancel() {
+        let dir = std::env::temp_dir().join(format!("qrtistry_batch_cancel_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("rows.csv");
+        std::fs::write(&input, "sku\nA1\nA2\nA3\nA4\n").unwrap();
+        let job = BatchJob {
+            input,
+            output_dir: dir.join("out"),
+            text_template: Some("item {sku}".to_string()),
+            filename_template: "{sku}.png".to_string(),
+            verify: false,
+        };
+
+        let mut seen = Vec::new();
+        let report = run_batch_with_progress(&mut QrSpec::default(), &job, |done, total| {
+            seen.push((done, total));
+            done < 2
+        })
+        .unwrap();
+        assert_eq!(seen, [(1, 4), (2, 4)]);
+        assert!(report.cancelled);
+        assert_eq!(report.succeeded(), 2);
+        assert!(!dir.join("out/A3.png").exists());
+
+        let report = run_batch(&mut QrSpec::default(), &job).unwrap();
+        assert!(!report.cancelled && report.succeeded() == 4);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
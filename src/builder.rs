@@ -0,0 +1,444 @@
+//! Builder-style API over the generation pipeline
+//!
+//! `QrBuilder` is the recommended entry point for library users: it starts
+//! from the default design, applies each setting through a chained method
+//! and renders to an image, encoded bytes or a file.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use image::{DynamicImage, ImageFormat, RgbaImage};
+
+use crate::export::{self, ExportOptions, OutputFormat};
+use crate::payload::Payload;
+use crate::qr::{self, QrSpec};
+use crate::qr::{occlusion, structured};
+use crate::qr::pdf::PdfOptions;
+use crate::types::*;
+
+/// Chainable builder for styled QR codes
+///
+/// # Example
+/// ```
+/// use qrtistry::{QrBuilder, ErrorCorrectionLevel, ModuleStyle, EyeStyle};
+///
+/// let png = QrBuilder::new("https://example.com")
+///     .ec_level(ErrorCorrectionLevel::High)
+///     .size(512)
+///     .module_style(ModuleStyle::RoundedSquare)
+///     .eye_style(EyeStyle::CIRCLE)
+///     .foreground([27, 94, 32])
+///     .to_png()
+///     .unwrap();
+/// assert!(png.starts_with(b"\x89PNG"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QrBuilder {
+    spec: QrSpec,
+}
+
+impl QrBuilder {
+    /// Start a new QR code with the default design
+    ///
+    /// # Arguments
+    /// * `content` - Text, URL or data to encode
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            spec: QrSpec {
+                qr_text: content.into(),
+                ..QrSpec::default()
+            },
+        }
+    }
+
+    /// Start a new QR code from a structured payload (WiFi, contact, event, payment, ...)
+    ///
+    /// Formats that mandate an error correction level (Swiss QR-bills) start with it.
+    ///
+    /// # Returns
+    /// * `Ok(QrBuilder)` - Builder with the payload's escaped text as content
+    /// * `Err(String)` - Error message if a payload field is missing or invalid
+    pub fn from_payload(payload: Payload) -> Result<Self, String> {
+        let mut spec = QrSpec::default();
+        spec.set_payload(payload)?;
+        Ok(Self { spec })
+    }
+
+    /// Start from an existing specification (e.g. a loaded preset)
+    pub fn from_spec(spec: QrSpec) -> Self {
+        Self { spec }
+    }
+
+    /// Text, URL or data to encode
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.spec.qr_text = content.into();
+        self
+    }
+
+    /// Requested output size in pixels (128-2048, checked when rendering)
+    pub fn size(mut self, size: u32) -> Self {
+        self.spec.size = size;
+        self
+    }
+
+    /// How the requested size is met (whole modules, padding or exact)
+    pub fn sizing_mode(mut self, mode: SizingMode) -> Self {
+        self.spec.sizing_mode = mode;
+        self
+    }
+
+    /// Border (quiet zone) width in modules (0-10, checked when rendering)
+    pub fn border(mut self, modules: u32) -> Self {
+        self.spec.border = modules;
+        self
+    }
+
+    /// Error correction level
+    pub fn ec_level(mut self, level: ErrorCorrectionLevel) -> Self {
+        self.spec.ec_level = level;
+        self
+    }
+
+    /// Symbol type: standard QR, Micro QR or rectangular Micro QR (rMQR)
+    pub fn symbol_type(mut self, symbol_type: SymbolType) -> Self {
+        self.spec.symbol_type = symbol_type;
+        self
+    }
+
+    /// QR version selection (automatic, minimum or exact)
+    pub fn version(mut self, version: QrVersion) -> Self {
+        self.spec.version = version;
+        self
+    }
+
+    /// Force a mask pattern (0-7, Micro QR 0-3) instead of choosing the best one
+    pub fn mask_pattern(mut self, mask: u8) -> Self {
+        self.spec.mask_pattern = Some(mask);
+        self
+    }
+
+    /// Segment mode for the content
+    pub fn encoding_mode(mut self, mode: EncodingMode) -> Self {
+        self.spec.encoding_mode = mode;
+        self
+    }
+
+    /// Character set the content is stored in
+    ///
+    /// Anything other than `CharacterSet::Auto` transcodes the text and
+    /// adds an ECI designator; characters outside the set become `?` (see
+    /// `qr::eci::warning`).
+    pub fn character_set(mut self, charset: CharacterSet) -> Self {
+        self.spec.character_set = charset;
+        self
+    }
+
+    /// How the content is interpreted
+    ///
+    /// `ContentMode::Gs1` reads it as a GS1 element string such as
+    /// "(01)09501101530003(10)LOT42", encoded with FNC1, or as a GS1 Digital
+    /// Link URI. Invalid GS1 data fails when rendering.
+    pub fn content_mode(mut self, mode: ContentMode) -> Self {
+        self.spec.content_mode = mode;
+        self
+    }
+
+    /// Split the content across 2-16 linked codes (Structured Append)
+    ///
+    /// Used by `render_sheet` and `save_sequence`; the single-code methods
+    /// still encode the whole content as one code.
+    pub fn structured_append(mut self, count: u8) -> Self {
+        self.spec.structured_append = Some(count);
+        self
+    }
+
+    /// Color of dark modules (RGB 0-255)
+    pub fn foreground(mut self, color: [u8; 3]) -> Self {
+        self.spec.fg_color = color;
+        self
+    }
+
+    /// Color of light areas (RGB 0-255)
+    pub fn background(mut self, color: [u8; 3]) -> Self {
+        self.spec.bg_color = color;
+        self
+    }
+
+    /// Blend dark modules from the foreground color to `end_color`
+    pub fn gradient(mut self, gradient_type: GradientType, end_color: [u8; 3]) -> Self {
+        self.spec.use_gradient = true;
+        self.spec.gradient_type = gradient_type;
+        self.spec.gradient_color = end_color;
+        self
+    }
+
+    /// Shape of data modules
+    pub fn module_style(mut self, style: ModuleStyle) -> Self {
+        self.spec.module_style = style;
+        self
+    }
+
+    /// Corner radius for rounded modules (0.0-1.0, enables extra rounding)
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.spec.use_rounded_corners = true;
+        self.spec.corner_radius = radius.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Style of the alignment patterns (solid squares by default)
+    pub fn alignment_style(mut self, style: PatternStyle) -> Self {
+        self.spec.alignment_style = style;
+        self
+    }
+
+    /// Style of the timing patterns (solid squares by default)
+    pub fn timing_style(mut self, style: PatternStyle) -> Self {
+        self.spec.timing_style = style;
+        self
+    }
+
+    /// Style of the three finder patterns (frame and pupil)
+    pub fn eye_style(mut self, style: EyeStyle) -> Self {
+        self.spec.eye_style = style;
+        self
+    }
+
+    /// Shape of the outer ring of the finder patterns
+    pub fn eye_frame(mut self, frame: EyeFrameStyle) -> Self {
+        self.spec.eye_style.frame = frame;
+        self
+    }
+
+    /// Shape of the center of the finder patterns
+    pub fn eye_pupil(mut self, pupil: EyePupilStyle) -> Self {
+        self.spec.eye_style.pupil = pupil;
+        self
+    }
+
+    /// Custom color for the finder patterns (RGB 0-255)
+    pub fn eye_color(mut self, color: [u8; 3]) -> Self {
+        self.spec.use_custom_eye_color = true;
+        self.spec.eye_color = color;
+        self
+    }
+
+    /// Style one finder pattern differently from the others
+    ///
+    /// Replaces any earlier override for the same eye.
+    pub fn eye_override(mut self, eye: EyeOverride) -> Self {
+        self.spec.eye_overrides.retain(|o| o.position != eye.position);
+        self.spec.eye_overrides.push(eye);
+        self
+    }
+
+    /// Custom color for the finder pattern pupils (RGB 0-255)
+    ///
+    /// Frames keep the `eye_color`, or the foreground color if none was set.
+    pub fn eye_pupil_color(mut self, color: [u8; 3]) -> Self {
+        if !self.spec.use_custom_eye_color {
+            self.spec.use_custom_eye_color = true;
+            self.spec.eye_color = self.spec.fg_color;
+        }
+        self.spec.eye_pupil_color = Some(color);
+        self
+    }
+
+    /// Logo centered on the code
+    ///
+    /// # Arguments
+    /// * `logo` - Logo image
+    /// * `size_ratio` - Logo size as fraction of the QR code (0.05-0.35)
+    pub fn logo(mut self, logo: DynamicImage, size_ratio: f32) -> Self {
+        self.spec.logo_image = Some(logo);
+        self.spec.logo_size = size_ratio.clamp(0.05, 0.35);
+        self
+    }
+
+    /// Keep the logo within the error correction budget
+    ///
+    /// Applied when rendering: with `LogoFit::RaiseErrorCorrection` or
+    /// `LogoFit::ShrinkLogo` the output may use a stronger level or a
+    /// smaller logo than configured.
+    ///
+    /// # Arguments
+    /// * `fit` - Adjustment strategy
+    /// * `safety_margin` - Share of the budget to keep free (0.0-0.9)
+    pub fn logo_fit(mut self, fit: LogoFit, safety_margin: f32) -> Self {
+        self.spec.logo_fit = fit;
+        self.spec.logo_safety_margin = safety_margin.clamp(0.0, 0.9);
+        self
+    }
+
+    /// Image blended behind the code
+    ///
+    /// # Arguments
+    /// * `image` - Background image (resized to the output size)
+    /// * `opacity` - Image opacity over the background color (0.0-1.0)
+    pub fn background_image(mut self, image: DynamicImage, opacity: f32) -> Self {
+        self.spec.bg_image = Some(image);
+        self.spec.bg_image_opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Overall opacity of the modules (0.0-1.0)
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.spec.qr_opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Edge smoothing of curved shapes in raster output
+    pub fn antialiasing(mut self, quality: Antialiasing) -> Self {
+        self.spec.antialiasing = quality;
+        self
+    }
+
+    /// Physical page layout used by PDF output
+    pub fn pdf_options(mut self, options: PdfOptions) -> Self {
+        self.spec.pdf_options = options;
+        self
+    }
+
+    /// Raster format settings (JPEG quality, WebP, TIFF depth, ICO sizes,
+    /// transparent background) used by `encode` and `save`
+    pub fn export_options(mut self, options: ExportOptions) -> Self {
+        self.spec.export_options = options;
+        self
+    }
+
+    /// Specification built so far
+    pub fn spec(&self) -> &QrSpec {
+        &self.spec
+    }
+
+    /// Finish building and return the specification
+    pub fn build(self) -> QrSpec {
+        self.spec
+    }
+
+    /// Render to an RGBA image
+    ///
+    /// # Returns
+    /// * `Ok(RgbaImage)` - Styled QR code
+    /// * `Err(String)` - Error message (e.g. content too long for the EC level)
+    pub fn render(&self) -> Result<RgbaImage, String> {
+        qr::generate_qr_image(&*self.fitted()?)
+    }
+
+    /// Render and encode as PNG bytes
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        self.encode(OutputFormat::Raster(ImageFormat::Png))
+    }
+
+    /// Render as an SVG document
+    pub fn to_svg(&self) -> Result<String, String> {
+        qr::generate_qr_svg(&*self.fitted()?)
+    }
+
+    /// Render as a PDF document using the configured page layout
+    pub fn to_pdf(&self) -> Result<Vec<u8>, String> {
+        let spec = self.fitted()?;
+        qr::generate_qr_pdf(&spec, &spec.pdf_options)
+    }
+
+    /// Render and encode in any supported format
+    pub fn encode(&self, format: OutputFormat) -> Result<Vec<u8>, String> {
+        export::encode(&*self.fitted()?, format)
+    }
+
+    /// Render and write to a file, choosing the format from its extension
+    ///
+    /// # Returns
+    /// * `Ok((width, height))` - Output dimensions in pixels
+    /// * `Err(String)` - Error message if generation or writing failed
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(u32, u32), String> {
+        export::export_to_path(&*self.fitted()?, path.as_ref())
+    }
+
+    /// Render every code of a Structured Append sequence onto one sheet
+    ///
+    /// # Returns
+    /// * `Ok(RgbaImage)` - Combined sheet
+    /// * `Err(String)` - Error message (e.g. `structured_append` not set)
+    pub fn render_sheet(&self) -> Result<RgbaImage, String> {
+        structured::render_sheet(&*self.fitted()?)
+    }
+
+    /// Write every code of a Structured Append sequence plus a combined sheet
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PathBuf>)` - Files written, the sheet last
+    /// * `Err(String)` - Error message if generation or writing failed
+    pub fn save_sequence(&self, path: impl AsRef<Path>) -> Result<Vec<PathBuf>, String> {
+        export::export_sequence(&*self.fitted()?, path.as_ref())
+    }
+
+    /// Specification with the logo fit applied (borrowed when unchanged)
+    ///
+    /// Fails if the size or border is out of range.
+    fn fitted(&self) -> Result<Cow<'_, QrSpec>, String> {
+        self.spec.check_dimensions()?;
+        if self.spec.logo_fit == LogoFit::Off || self.spec.logo_image.is_none() {
+            return Ok(Cow::Borrowed(&self.spec));
+        }
+        let mut spec = self.spec.clone();
+        occlusion::fit_logo(&mut spec)?;
+        Ok(Cow::Owned(spec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_applies_settings() {
+        let spec = QrBuilder::new("hello")
+            .gradient(GradientType::Radial, [1, 2, 3])
+            .corner_radius(2.0)
+            .logo(DynamicImage::new_rgba8(8, 8), 0.9)
+            .build();
+
+        assert_eq!(spec.qr_text, "hello");
+        assert!(spec.use_gradient);
+        assert_eq!(spec.gradient_color, [1, 2, 3]);
+        assert_eq!(spec.corner_radius, 1.0);
+        assert_eq!(spec.logo_size, 0.35);
+    }
+
+    #[test]
+    fn test_builder_reports_errors() {
+        let too_long = "x".repeat(4000);
+        let result = QrBuilder::new(too_long.clone()).ec_level(ErrorCorrectionLevel::High).render();
+        assert!(result.is_err());
+
+        // The same content fits when split across linked codes
+        let builder = QrBuilder::new(too_long).ec_level(ErrorCorrectionLevel::High);
+        assert!(builder.clone().structured_append(4).render_sheet().is_ok());
+        assert!(builder.render_sheet().is_err());
+
+        // Sizes and borders outside the supported ranges are errors, not panics
+        assert!(QrBuilder::new("hi").size(100).render().unwrap_err().contains("size 100"));
+        assert!(QrBuilder::new("hi").border(50).to_svg().unwrap_err().contains("border of 50"));
+
+        // Payload fields are checked before anything is rendered
+        let wifi = Payload::Wifi {
+            ssid: "Guest".to_string(),
+            password: "1234".to_string(),
+            security: crate::payload::WifiSecurity::Wpa,
+            hidden: false,
+        };
+        assert!(QrBuilder::from_payload(wifi).unwrap_err().contains("8-63"));
+        let phone = QrBuilder::from_payload(Payload::Phone { number: "+1 555 0100".to_string() }).unwrap();
+        assert_eq!(phone.build().qr_text, "tel:+15550100");
+
+        let result = QrBuilder::new("hello").mask_pattern(9).render();
+        assert!(result.is_err());
+
+        let result = QrBuilder::new("hello").symbol_type(SymbolType::Micro).mask_pattern(5).render();
+        assert!(result.is_err());
+
+        // A wrong GTIN check digit is rejected instead of encoded
+        let result = QrBuilder::new("(01)09501101530004").content_mode(ContentMode::Gs1).render();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,887 @@
+//! Command-line interface
+//!
+//! Provides headless subcommands so QR codes can be produced from build
+//! scripts and CI machines that have no display:
+//! - `render`: Generate a single QR code image from flags and/or a preset
+//! - `batch`: Generate one QR code per row of a CSV or JSONL file
+//!
+//! Running `qrtistry` without a subcommand launches the GUI as before.
+
+use clap::{Args, Parser, Subcommand};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use qrtistry::types::*;
+use qrtistry::export;
+use qrtistry::batch;
+use qrtistry::qr::{eci, occlusion, scannability, spec, verify, QrSpec};
+use qrtistry::qr::pdf::{self, PageSize};
+
+/// Top-level command-line arguments
+#[derive(Parser)]
+#[command(name = "qrtistry", version, about = "Create beautifully customized QR codes")]
+pub struct Cli {
+    /// Headless subcommand to run (omit to launch the GUI)
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Available headless subcommands
+#[derive(Subcommand)]
+pub enum Command {
+    /// Render a single QR code to an image file without opening the GUI
+    Render(RenderArgs),
+
+    /// Render one QR code per row of a CSV or JSONL file
+    Batch(BatchArgs),
+}
+
+/// Arguments for the `render` subcommand
+#[derive(Args)]
+pub struct RenderArgs {
+    /// Output file path (.svg/.pdf for vector output, otherwise inferred from the extension)
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// Text content to encode
+    #[arg(short, long)]
+    pub text: Option<String>,
+
+    /// Skip decoding the rendered code to check that it scans
+    #[arg(long)]
+    pub no_verify: bool,
+
+    /// Print a scannability report (score and margin per simulated degradation)
+    #[arg(long, conflicts_with = "no_verify")]
+    pub report: bool,
+
+    #[command(flatten)]
+    pub style: StyleArgs,
+}
+
+/// Arguments for the `batch` subcommand
+#[derive(Args)]
+pub struct BatchArgs {
+    /// CSV (with header row) or JSONL input file
+    #[arg(short, long)]
+    pub input: PathBuf,
+
+    /// Directory for generated images and the summary file
+    #[arg(short, long)]
+    pub output_dir: PathBuf,
+
+    /// Content template with {field} placeholders (defaults to the preset text)
+    #[arg(short, long)]
+    pub text: Option<String>,
+
+    /// Output filename template with {field} placeholders
+    #[arg(short, long, default_value = batch::DEFAULT_FILENAME_TEMPLATE)]
+    pub filename: String,
+
+    /// Skip decoding each rendered code to check that it scans
+    #[arg(long)]
+    pub no_verify: bool,
+
+    #[command(flatten)]
+    pub style: StyleArgs,
+}
+
+/// Styling flags shared by all rendering subcommands
+///
+/// Every flag is optional. Settings are taken from `--preset` (or the
+/// application defaults) first, then overridden by any flags that are given.
+#[derive(Args)]
+pub struct StyleArgs {
+    /// Preset JSON file to use as the starting configuration
+    #[arg(short, long)]
+    pub preset: Option<PathBuf>,
+
+    /// Output size in pixels, 128-2048
+    #[arg(long, value_parser = ranged_u32(spec::SIZE_RANGE))]
+    pub size: Option<u32>,
+
+    /// Border (quiet zone) width in modules, 0-10
+    #[arg(long, value_parser = ranged_u32(spec::BORDER_RANGE))]
+    pub border: Option<u32>,
+
+    /// How --size is met: fit (whole modules, may be smaller), pad (exact size with padding) or exact (fractional modules)
+    #[arg(long)]
+    pub sizing: Option<SizingMode>,
+
+    /// Error correction level: low, medium, quartile or high
+    #[arg(long)]
+    pub ec_level: Option<ErrorCorrectionLevel>,
+
+    /// Symbol type: qr, micro (M1-M4) or rmqr (rectangular; needs an rMQR-capable scanner)
+    #[arg(long)]
+    pub symbol: Option<SymbolType>,
+
+    /// QR version: auto, an exact version (1-40, M1-M4, or an rMQR size like R11x43) or a minimum version (e.g. "5+")
+    #[arg(long)]
+    pub qr_version: Option<QrVersion>,
+
+    /// Mask pattern 0-7, 0-3 for Micro QR (chosen automatically if omitted)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=7))]
+    pub mask: Option<u8>,
+
+    /// Encoding mode: auto, numeric, alphanumeric, byte or kanji
+    #[arg(long)]
+    pub encoding: Option<EncodingMode>,
+
+    /// Character set for the content, written as an ECI designator: auto (UTF-8 without ECI), utf-8, iso-8859-1, -2, -5, -7, -15, windows-1250, -1251, -1252, shift-jis, big5, gb18030 or euc-kr
+    #[arg(long)]
+    pub charset: Option<CharacterSet>,
+
+    /// How the text is read: text, or gs1 for an element string like "(01)09501101530003(10)LOT42" or a GS1 Digital Link URI
+    #[arg(long)]
+    pub content: Option<ContentMode>,
+
+    /// Split the content across 2-16 linked codes (Structured Append); writes NAME-1ofN.EXT ... and NAME-sheet
+    #[arg(long, value_parser = clap::value_parser!(u8).range(2..=16))]
+    pub split: Option<u8>,
+
+    /// Foreground color as hex, e.g. "#000000"
+    #[arg(long, value_parser = parse_hex_color)]
+    pub fg: Option<[u8; 3]>,
+
+    /// Background color as hex, e.g. "#FFFFFF"
+    #[arg(long, value_parser = parse_hex_color)]
+    pub bg: Option<[u8; 3]>,
+
+    /// Gradient type: horizontal, vertical, diagonal or radial (enables gradient)
+    #[arg(long)]
+    pub gradient: Option<GradientType>,
+
+    /// Gradient end color as hex
+    #[arg(long, value_parser = parse_hex_color)]
+    pub gradient_color: Option<[u8; 3]>,
+
+    /// Module style: square, circle, rounded-square, dots, liquid, vertical-lines or horizontal-lines
+    #[arg(long)]
+    pub module_style: Option<ModuleStyle>,
+
+    /// Corner radius for rounded modules, 0.0-1.0 (enables extra rounding)
+    #[arg(long)]
+    pub corner_radius: Option<f32>,
+
+    /// Alignment pattern style: solid, rounded, circle or modules (follow --module-style)
+    #[arg(long)]
+    pub alignment_style: Option<PatternStyle>,
+
+    /// Timing pattern style: solid, rounded, circle or modules (follow --module-style)
+    #[arg(long)]
+    pub timing_style: Option<PatternStyle>,
+
+    /// Eye style (sets frame and pupil): standard, circle, rounded-square, flower or diamond
+    #[arg(long)]
+    pub eye_style: Option<EyeStyle>,
+
+    /// Eye frame shape: square, rounded, circle, leaf, dotted or diamond
+    #[arg(long)]
+    pub eye_frame: Option<EyeFrameStyle>,
+
+    /// Eye pupil shape: square, rounded, circle, diamond or star
+    #[arg(long)]
+    pub eye_pupil: Option<EyePupilStyle>,
+
+    /// Custom eye color as hex (enables custom eye color)
+    #[arg(long, value_parser = parse_hex_color)]
+    pub eye_color: Option<[u8; 3]>,
+
+    /// Custom eye pupil color as hex (the frame keeps the eye color)
+    #[arg(long, value_parser = parse_hex_color)]
+    pub eye_pupil_color: Option<[u8; 3]>,
+
+    /// Style one eye differently, e.g. "top-right:frame=leaf,pupil=star,color=#E53935,rotation=90"
+    /// (keys: frame, pupil, color, pupil-color, rotation; repeatable)
+    #[arg(long = "eye", value_parser = parse_eye_override)]
+    pub eye_overrides: Vec<EyeOverrideArg>,
+
+    /// Logo image to overlay in the center
+    #[arg(long)]
+    pub logo: Option<PathBuf>,
+
+    /// Logo size as fraction of the QR code, 0.05-0.35
+    #[arg(long)]
+    pub logo_size: Option<f32>,
+
+    /// What to do when the logo exceeds the error correction budget: off, raise-ec or shrink-logo
+    #[arg(long)]
+    pub logo_fit: Option<LogoFit>,
+
+    /// Share of the error correction budget to keep free of the logo, 0.0-0.9
+    #[arg(long)]
+    pub logo_margin: Option<f32>,
+
+    /// Background image to blend behind the QR code
+    #[arg(long)]
+    pub background: Option<PathBuf>,
+
+    /// Background image opacity, 0.0-1.0
+    #[arg(long)]
+    pub background_opacity: Option<f32>,
+
+    /// Overall QR code opacity, 0.0-1.0
+    #[arg(long)]
+    pub opacity: Option<f32>,
+
+    /// Anti-aliasing of curved shapes in raster output: off, standard or high
+    #[arg(long)]
+    pub antialiasing: Option<Antialiasing>,
+
+    /// PDF page size: a4, a5, letter, business-card or sticker
+    #[arg(long)]
+    pub page: Option<PageSize>,
+
+    /// PDF page width, e.g. "210mm" or "8.5in" (plain numbers are mm)
+    #[arg(long, value_parser = pdf::parse_length_mm)]
+    pub page_width: Option<f32>,
+
+    /// PDF page height, e.g. "297mm" or "11in"
+    #[arg(long, value_parser = pdf::parse_length_mm)]
+    pub page_height: Option<f32>,
+
+    /// Printed size of the QR code in the PDF, e.g. "50mm" or "2in"
+    #[arg(long, value_parser = pdf::parse_length_mm)]
+    pub print_size: Option<f32>,
+
+    /// PDF bleed beyond the trim edge, e.g. "3mm"
+    #[arg(long, value_parser = pdf::parse_length_mm)]
+    pub bleed: Option<f32>,
+
+    /// Draw crop marks at the PDF trim corners
+    #[arg(long)]
+    pub crop_marks: bool,
+
+    /// JPEG quality, 1-100
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub jpeg_quality: Option<u8>,
+
+    /// Encode WebP lossy at this quality, 1-100 (lossless otherwise)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub webp_quality: Option<u8>,
+
+    /// Write TIFF with 16 bits per channel
+    #[arg(long)]
+    pub tiff_16bit: bool,
+
+    /// Icon sizes stored in ICO output, e.g. "16,32,48" (1-256 each)
+    #[arg(long, value_delimiter = ',')]
+    pub ico_sizes: Option<Vec<u32>>,
+
+    /// Transparent background in PNG, WebP, TIFF and ICO output
+    #[arg(long)]
+    pub transparent: bool,
+}
+
+impl StyleArgs {
+    /// Build the rendering configuration from the preset and flags
+    ///
+    /// # Returns
+    /// * `Ok(QrSpec)` - Preset (or defaults) with all flags applied
+    /// * `Err(String)` - The preset or an image could not be loaded
+    fn build_spec(&self) -> Result<QrSpec, String> {
+        let mut spec = match &self.preset {
+            Some(path) => export::read_preset(path)?,
+            None => QrSpec::default(),
+        };
+        self.apply_to(&mut spec)?;
+        // Presets are not limited by the flag ranges
+        spec.check_dimensions()?;
+        Ok(spec)
+    }
+
+    /// Apply all provided flags on top of an existing configuration
+    ///
+    /// # Arguments
+    /// * `spec` - Configuration to modify (defaults or a loaded preset)
+    ///
+    /// # Returns
+    /// * `Ok(())` - All overrides applied
+    /// * `Err(String)` - A logo or background image could not be loaded
+    fn apply_to(&self, spec: &mut QrSpec) -> Result<(), String> {
+        // === Dimensions ===
+        if let Some(size) = self.size {
+            spec.size = size;
+        }
+        if let Some(border) = self.border {
+            spec.border = border;
+        }
+        if let Some(sizing) = self.sizing {
+            spec.sizing_mode = sizing;
+        }
+        if let Some(ec_level) = self.ec_level {
+            spec.ec_level = ec_level;
+        }
+        if let Some(symbol) = self.symbol {
+            spec.symbol_type = symbol;
+        }
+        if let Some(version) = self.qr_version {
+            spec.version = version;
+        }
+        if let Some(mask) = self.mask {
+            spec.mask_pattern = Some(mask);
+        }
+        if let Some(encoding) = self.encoding {
+            spec.encoding_mode = encoding;
+        }
+        if let Some(charset) = self.charset {
+            spec.character_set = charset;
+        }
+        if let Some(content) = self.content {
+            spec.content_mode = content;
+        }
+        if let Some(count) = self.split {
+            spec.structured_append = Some(count);
+        }
+
+        // === Colors ===
+        if let Some(fg) = self.fg {
+            spec.fg_color = fg;
+        }
+        if let Some(bg) = self.bg {
+            spec.bg_color = bg;
+        }
+        if let Some(gradient) = self.gradient {
+            spec.use_gradient = true;
+            spec.gradient_type = gradient;
+        }
+        if let Some(gradient_color) = self.gradient_color {
+            spec.gradient_color = gradient_color;
+        }
+
+        // === Module and Eye Styling ===
+        if let Some(module_style) = self.module_style {
+            spec.module_style = module_style;
+        }
+        if let Some(corner_radius) = self.corner_radius {
+            spec.use_rounded_corners = true;
+            spec.corner_radius = corner_radius.clamp(0.0, 1.0);
+        }
+        if let Some(alignment_style) = self.alignment_style {
+            spec.alignment_style = alignment_style;
+        }
+        if let Some(timing_style) = self.timing_style {
+            spec.timing_style = timing_style;
+        }
+        if let Some(eye_style) = self.eye_style {
+            spec.eye_style = eye_style;
+        }
+        if let Some(frame) = self.eye_frame {
+            spec.eye_style.frame = frame;
+        }
+        if let Some(pupil) = self.eye_pupil {
+            spec.eye_style.pupil = pupil;
+        }
+        if let Some(eye_color) = self.eye_color {
+            spec.use_custom_eye_color = true;
+            spec.eye_color = eye_color;
+        }
+        if let Some(pupil_color) = self.eye_pupil_color {
+            if !spec.use_custom_eye_color {
+                // Only the pupil was given: keep the frame in the module color
+                spec.use_custom_eye_color = true;
+                spec.eye_color = spec.fg_color;
+            }
+            spec.eye_pupil_color = Some(pupil_color);
+        }
+        for eye in &self.eye_overrides {
+            eye.apply_to(spec);
+        }
+
+        // === Images ===
+        if let Some(path) = &self.logo {
+            spec.logo_image = Some(load_image(path, "logo")?);
+        }
+        if let Some(logo_size) = self.logo_size {
+            spec.logo_size = logo_size.clamp(0.05, 0.35);
+        }
+        if let Some(logo_fit) = self.logo_fit {
+            spec.logo_fit = logo_fit;
+        }
+        if let Some(margin) = self.logo_margin {
+            spec.logo_safety_margin = margin.clamp(0.0, 0.9);
+        }
+        if let Some(path) = &self.background {
+            spec.bg_image = Some(load_image(path, "background")?);
+        }
+        if let Some(opacity) = self.background_opacity {
+            spec.bg_image_opacity = opacity.clamp(0.0, 1.0);
+        }
+
+        // === Advanced ===
+        if let Some(opacity) = self.opacity {
+            spec.qr_opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(antialiasing) = self.antialiasing {
+            spec.antialiasing = antialiasing;
+        }
+
+        // === PDF Page Layout ===
+        let layout = &mut spec.pdf_options;
+        if let Some(page) = self.page {
+            layout.set_page_size(page);
+        }
+        if let Some(width) = self.page_width {
+            layout.page_width_mm = width;
+        }
+        if let Some(height) = self.page_height {
+            layout.page_height_mm = height;
+        }
+        if let Some(size) = self.print_size {
+            layout.qr_size_mm = size;
+        }
+        if let Some(bleed) = self.bleed {
+            layout.bleed_mm = bleed;
+        }
+        if self.crop_marks {
+            layout.crop_marks = true;
+        }
+
+        // === Raster Export ===
+        let options = &mut spec.export_options;
+        if let Some(quality) = self.jpeg_quality {
+            options.jpeg_quality = quality;
+        }
+        if let Some(quality) = self.webp_quality {
+            options.webp_lossless = false;
+            options.webp_quality = quality;
+        }
+        if self.tiff_16bit {
+            options.tiff_16_bit = true;
+        }
+        if let Some(sizes) = &self.ico_sizes {
+            options.ico_sizes = sizes.clone();
+        }
+        if self.transparent {
+            options.transparent_background = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run a headless subcommand
+///
+/// # Arguments
+/// * `command` - Parsed subcommand to execute
+///
+/// # Returns
+/// Process exit code (0 on success, 1 on failure)
+pub fn run(command: Command) -> i32 {
+    let result = match command {
+        Command::Render(args) => render(&args),
+        Command::Batch(args) => run_batch(&args),
+    };
+
+    match result {
+        Ok(message) => {
+            println!("✅ {}", message);
+            0
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            1
+        }
+    }
+}
+
+/// Execute the `render` subcommand
+///
+/// The rendered code is decoded again unless `--no-verify` is given; a
+/// design that does not round-trip is still saved but fails the command.
+/// With `--report` the scannability margins are printed as well.
+///
+/// # Returns
+/// * `Ok(String)` - Success message naming the written file
+/// * `Err(String)` - Error message describing what went wrong
+fn render(args: &RenderArgs) -> Result<String, String> {
+    let mut spec = args.style.build_spec()?;
+    if let Some(text) = &args.text {
+        // Explicit text replaces any structured payload from the preset
+        spec.qr_text = text.clone();
+        spec.payload = None;
+    }
+
+    if spec.qr_text.is_empty() {
+        return Err("No text to encode (use --text or a preset with content)".to_string());
+    }
+
+    if let Some(warning) = eci::warning(&spec.qr_text, spec.character_set) {
+        eprintln!("⚠️ {}", warning);
+    }
+
+    // Keep the logo within the error correction budget (see --logo-fit)
+    if let Some(adjustment) = occlusion::fit_logo(&mut spec)? {
+        println!("💡 {}", adjustment);
+    }
+    if let Some(report) = occlusion::analyze(&spec)? {
+        if !report.fits(spec.logo_safety_margin) {
+            eprintln!("⚠️ {}", report.summary());
+        }
+    }
+
+    let saved = if spec.structured_append.is_some() {
+        let written = export::export_sequence(&spec, &args.output)?;
+        let names: Vec<String> = written.iter().map(|p| p.display().to_string()).collect();
+        format!("Saved {} linked codes and a sheet: {}", written.len() - 1, names.join(", "))
+    } else {
+        let (width, height) = export::export_to_path(&spec, &args.output)?;
+        format!("Saved to: {} ({}x{})", args.output.display(), width, height)
+    };
+
+    if args.no_verify {
+        return Ok(saved);
+    }
+
+    // The built-in decoder only reads standard QR codes
+    if let Some(skipped) = verify::unsupported_symbol(&spec) {
+        eprintln!("⚠️ {}", skipped.summary());
+        return Ok(saved);
+    }
+
+    // Decode the design again so unscannable artwork fails the command
+    let verification = if args.report {
+        let report = scannability::analyze(&spec)?;
+        println!("📊 {}", report.summary());
+        for result in &report.results {
+            println!(
+                "   {:<18} {}/{}  {}",
+                result.degradation.name(),
+                result.levels_passed,
+                result.levels_total,
+                result.summary()
+            );
+        }
+        report.baseline
+    } else {
+        verify::verify_spec(&spec)?
+    };
+    if verification.passed() {
+        Ok(format!("{}, {}", saved, verification.summary().to_lowercase()))
+    } else {
+        Err(format!("{}, but {}", saved, verification.summary().to_lowercase()))
+    }
+}
+
+/// Execute the `batch` subcommand
+///
+/// Individual row failures are listed on stderr and in the summary file;
+/// the command fails if any row failed.
+///
+/// # Returns
+/// * `Ok(String)` - Summary message when every row succeeded
+/// * `Err(String)` - Error message if the run failed or any row failed
+fn run_batch(args: &BatchArgs) -> Result<String, String> {
+    let mut spec = args.style.build_spec()?;
+    let job = batch::BatchJob {
+        input: args.input.clone(),
+        output_dir: args.output_dir.clone(),
+        text_template: args.text.clone(),
+        filename_template: args.filename.clone(),
+        verify: !args.no_verify,
+    };
+
+    let report = batch::run_batch(&mut spec, &job)?;
+
+    for row in report.rows.iter() {
+        if let Some(error) = &row.error {
+            eprintln!("   Row {}: {}", row.row, error);
+        } else if let Some(warning) = &row.warning {
+            eprintln!("   Row {}: ⚠️ {}", row.row, warning);
+        }
+    }
+
+    let summary = format!(
+        "{} of {} rows generated (summary: {})",
+        report.succeeded(),
+        report.rows.len(),
+        report.summary_path.display()
+    );
+    if report.failed() > 0 {
+        Err(summary)
+    } else {
+        Ok(summary)
+    }
+}
+
+/// Settings for one eye given with `--eye`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EyeOverrideArg {
+    /// Which eye to style
+    pub position: EyePosition,
+    /// Frame shape
+    pub frame: Option<EyeFrameStyle>,
+    /// Pupil shape
+    pub pupil: Option<EyePupilStyle>,
+    /// Frame (and pupil) color
+    pub color: Option<[u8; 3]>,
+    /// Pupil color
+    pub pupil_color: Option<[u8; 3]>,
+    /// Clockwise rotation in degrees
+    pub rotation: Option<u16>,
+}
+
+impl EyeOverrideArg {
+    /// Merge into the spec's override for this eye
+    ///
+    /// Shapes that are not given keep the eye's current style.
+    fn apply_to(&self, spec: &mut QrSpec) {
+        let shared_style = spec.eye_style;
+        let eye = spec.eye_override_mut(self.position);
+
+        if self.frame.is_some() || self.pupil.is_some() {
+            let mut style = eye.style.unwrap_or(shared_style);
+            style.frame = self.frame.unwrap_or(style.frame);
+            style.pupil = self.pupil.unwrap_or(style.pupil);
+            eye.style = Some(style);
+        }
+        if self.color.is_some() {
+            eye.frame_color = self.color;
+        }
+        if self.pupil_color.is_some() {
+            eye.pupil_color = self.pupil_color;
+        }
+        if let Some(rotation) = self.rotation {
+            eye.rotation = rotation;
+        }
+    }
+}
+
+/// Parse an eye override such as `top-right:frame=leaf,color=#E53935,rotation=90`
+///
+/// # Returns
+/// * `Ok(EyeOverrideArg)` - Eye and the settings given for it
+/// * `Err(String)` - Error message for an unknown eye, key or value
+pub fn parse_eye_override(s: &str) -> Result<EyeOverrideArg, String> {
+    let (position, settings) = s.split_once(':').unwrap_or((s, ""));
+    let mut eye = EyeOverrideArg {
+        position: position.trim().parse()?,
+        frame: None,
+        pupil: None,
+        color: None,
+        pupil_color: None,
+        rotation: None,
+    };
+
+    for setting in settings.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (key, value) = setting
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid eye setting '{}' (expected key=value)", setting))?;
+        match key.trim() {
+            "frame" => eye.frame = Some(value.parse()?),
+            "pupil" => eye.pupil = Some(value.parse()?),
+            "color" => eye.color = Some(parse_hex_color(value)?),
+            "pupil-color" | "pupil_color" => eye.pupil_color = Some(parse_hex_color(value)?),
+            "rotation" => {
+                let degrees: u16 = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid rotation '{}' (expected 0, 90, 180 or 270)", value))?;
+                if !degrees.is_multiple_of(90) {
+                    return Err(format!("Invalid rotation '{}' (expected 0, 90, 180 or 270)", value));
+                }
+                eye.rotation = Some(degrees % 360);
+            }
+            other => {
+                return Err(format!(
+                    "Unknown eye setting '{}' (expected frame, pupil, color, pupil-color or rotation)",
+                    other
+                ))
+            }
+        }
+    }
+
+    Ok(eye)
+}
+
+/// Value parser for a `u32` flag limited to a range
+///
+/// Flags take their limits from the constants the spec checks against,
+/// so the command line and `QrSpec::check_dimensions` always agree.
+fn ranged_u32(range: RangeInclusive<u32>) -> clap::builder::RangedI64ValueParser<u32> {
+    clap::value_parser!(u32).range(i64::from(*range.start())..=i64::from(*range.end()))
+}
+
+/// Load an image file referenced by a command-line flag
+///
+/// # Arguments
+/// * `path` - Image file path
+/// * `what` - Human-readable name used in the error message
+fn load_image(path: &Path, what: &str) -> Result<image::DynamicImage, String> {
+    image::open(path).map_err(|e| format!("Failed to load {} {}: {}", what, path.display(), e))
+}
+
+/// Parse a hex color such as `#1E90FF`, `1e90ff` or `#fff`
+///
+/// # Returns
+/// * `Ok([u8; 3])` - RGB color
+/// * `Err(String)` - Error message if the value is not a valid hex color
+pub fn parse_hex_color(s: &str) -> Result<[u8; 3], String> {
+    let hex = s.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid color '{}' (expected #RRGGBB)", s));
+    }
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return Err(format!("Invalid color '{}' (expected #RRGGBB)", s)),
+    };
+
+    let channel = |i: usize| {
+        u8::from_str_radix(&expanded[i..i + 2], 16)
+            .map_err(|_| format!("Invalid color '{}' (expected #RRGGBB)", s))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#1E90FF"), Ok([30, 144, 255]));
+        assert_eq!(parse_hex_color("000000"), Ok([0, 0, 0]));
+        assert_eq!(parse_hex_color("#fff"), Ok([255, 255, 255]));
+        assert!(parse_hex_color("#12345").is_err());
+        assert!(parse_hex_color("#GGGGGG").is_err());
+    }
+
+    #[test]
+    fn test_render_args_parse() {
+        let cli = Cli::try_parse_from([
+            "qrtistry", "render", "-o", "out.png", "--text", "hello",
+            "--ec-level", "high", "--module-style", "rounded-square", "--fg", "#112233",
+            "--timing-style", "circle", "--qr-version", "4+", "--mask", "6",
+        ]).unwrap();
+
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        let spec = args.style.build_spec().unwrap();
+
+        assert_eq!(args.text.as_deref(), Some("hello"));
+        assert_eq!(spec.ec_level, ErrorCorrectionLevel::High);
+        assert_eq!(spec.module_style, ModuleStyle::RoundedSquare);
+        assert_eq!(spec.fg_color, [0x11, 0x22, 0x33]);
+        assert_eq!(spec.timing_style, PatternStyle::Circle);
+        assert_eq!(spec.alignment_style, PatternStyle::Solid);
+        assert_eq!(spec.version, QrVersion::Minimum(4));
+        assert_eq!(spec.mask_pattern, Some(6));
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "out.png", "--mask", "8"]).is_err());
+    }
+
+    #[test]
+    fn test_symbol_flags() {
+        let cli = Cli::try_parse_from([
+            "qrtistry", "render", "-o", "label.svg", "--symbol", "rmqr", "--qr-version", "R13x43+",
+        ]).unwrap();
+
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        let spec = args.style.build_spec().unwrap();
+
+        assert_eq!(spec.symbol_type, SymbolType::Rectangular);
+        assert_eq!(spec.version, QrVersion::Minimum(18));
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "x.png", "--qr-version", "M5"]).is_err());
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "x.png", "--split", "17"]).is_err());
+    }
+
+    #[test]
+    fn test_content_flag() {
+        let cli = Cli::try_parse_from(["qrtistry", "render", "-o", "label.png", "--content", "gs1"]).unwrap();
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        assert_eq!(args.style.build_spec().unwrap().content_mode, ContentMode::Gs1);
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "x.png", "--content", "hibc"]).is_err());
+
+        let cli = Cli::try_parse_from(["qrtistry", "render", "-o", "label.png", "--charset", "ISO-8859-15"]).unwrap();
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        assert_eq!(args.style.build_spec().unwrap().character_set, CharacterSet::Iso8859_15);
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "x.png", "--charset", "ebcdic"]).is_err());
+    }
+
+    #[test]
+    fn test_eye_override_flags() {
+        let cli = Cli::try_parse_from([
+            "qrtistry", "render", "-o", "out.png", "--eye-style", "rounded",
+            "--eye", "top-right:frame=leaf,color=#E53935,rotation=90",
+            "--eye", "bottom-left:pupil=star",
+        ]).unwrap();
+
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        let spec = args.style.build_spec().unwrap();
+
+        let accent = spec.eye_design(EyePosition::TopRight);
+        assert_eq!(accent.style, EyeStyle::new(EyeFrameStyle::Leaf, EyePupilStyle::Rounded));
+        assert_eq!(accent.frame_color, Some([0xE5, 0x39, 0x35]));
+        assert_eq!(accent.quarter_turns, 1);
+
+        let star = spec.eye_design(EyePosition::BottomLeft);
+        assert_eq!(star.style, EyeStyle::new(EyeFrameStyle::Rounded, EyePupilStyle::Star));
+        assert!(parse_eye_override("middle:frame=leaf").is_err());
+        assert!(parse_eye_override("top-left:rotation=45").is_err());
+    }
+
+    #[test]
+    fn test_pdf_layout_flags() {
+        let cli = Cli::try_parse_from([
+            "qrtistry", "render", "-o", "out.pdf", "--page", "letter",
+            "--print-size", "1in", "--bleed", "3mm", "--crop-marks",
+        ]).unwrap();
+
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        let layout = args.style.build_spec().unwrap().pdf_options;
+
+        assert_eq!(layout.page_width_mm, PageSize::Letter.dimensions_mm().0);
+        assert_eq!(layout.qr_size_mm, 25.4);
+        assert_eq!(layout.bleed_mm, 3.0);
+        assert!(layout.crop_marks);
+    }
+
+    #[test]
+    fn test_export_option_flags() {
+        let cli = Cli::try_parse_from([
+            "qrtistry", "render", "-o", "favicon.ico", "--ico-sizes", "16,32",
+            "--webp-quality", "75", "--transparent",
+        ]).unwrap();
+
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        let options = args.style.build_spec().unwrap().export_options;
+
+        assert_eq!(options.ico_sizes, vec![16, 32]);
+        assert!(!options.webp_lossless);
+        assert_eq!(options.webp_quality, 75);
+        assert!(options.transparent_background);
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "x.jpg", "--jpeg-quality", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_dimension_ranges() {
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "a.png", "--size", "100"]).is_err());
+        assert!(Cli::try_parse_from(["qrtistry", "render", "-o", "a.png", "--border", "50"]).is_err());
+
+        // Presets bypass the flag ranges, so the spec is checked as well
+        let preset = std::env::temp_dir().join(format!("qrtistry_border_{}.json", std::process::id()));
+        let spec = QrSpec { size: 100, border: 50, ..QrSpec::default() };
+        std::fs::write(&preset, serde_json::to_string(&spec).unwrap()).unwrap();
+        let cli = Cli::try_parse_from(["qrtistry", "render", "-o", "a.png", "--preset", preset.to_str().unwrap()]).unwrap();
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("expected render subcommand");
+        };
+        let error = args.style.build_spec().err().unwrap();
+        std::fs::remove_file(&preset).unwrap();
+        assert!(error.contains("size 100"), "{}", error);
+    }
+}
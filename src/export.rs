@@ -0,0 +1,495 @@
+//! Headless export and preset reading
+//!
+//! Encodes a `QrSpec` to bytes or files without any UI interaction.
+//! Shared by the GUI, the command-line interface, batch generation and
+//! library users.
+//!
+//! Raster formats follow `QrSpec::export_options`: JPEG quality, lossless
+//! or lossy WebP, 16-bit TIFF, the sizes stored in an ICO file and whether
+//! the background stays transparent.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use image::codecs::ico::{IcoEncoder, IcoFrame};
+use image::codecs::jpeg::JpegEncoder;
+use image::{imageops, DynamicImage, ExtendedColorType, ImageFormat, Rgba, RgbImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::qr::{self, structured, QrSpec};
+
+/// Largest width and height of an image in an ICO file
+pub const MAX_ICO_SIZE: u32 = 256;
+
+/// Per-format settings for raster export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// JPEG quality (1-100)
+    pub jpeg_quality: u8,
+
+    /// Encode WebP losslessly (otherwise lossy at `webp_quality`)
+    pub webp_lossless: bool,
+
+    /// Lossy WebP quality (1-100)
+    pub webp_quality: u8,
+
+    /// Write TIFF with 16 bits per channel instead of 8
+    pub tiff_16_bit: bool,
+
+    /// Square sizes in pixels stored in an ICO file (1-256 each)
+    pub ico_sizes: Vec<u32>,
+
+    /// Leave the background transparent in PNG, WebP, TIFF and ICO output
+    /// (not available with a background image)
+    pub transparent_background: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            jpeg_quality: 90,
+            webp_lossless: true,
+            webp_quality: 90,
+            tiff_16_bit: false,
+            // Browser tab, taskbar, desktop and high-DPI favicon sizes
+            ico_sizes: vec![16, 32, 48, 256],
+            transparent_background: false,
+        }
+    }
+}
+
+/// Output file format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// SVG vector document
+    Svg,
+    /// Print-ready PDF document (uses `QrSpec::pdf_options`)
+    Pdf,
+    /// Raster image in any format supported by the `image` crate
+    Raster(ImageFormat),
+}
+
+impl OutputFormat {
+    /// Choose the output format from a file extension
+    ///
+    /// # Returns
+    /// * `Ok(OutputFormat)` - Format matching the extension
+    /// * `Err(String)` - Error message if the extension is missing or unknown
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "svg" => Ok(OutputFormat::Svg),
+            "pdf" => Ok(OutputFormat::Pdf),
+            _ => ImageFormat::from_extension(&extension)
+                .map(OutputFormat::Raster)
+                .ok_or_else(|| format!("Unsupported file extension '{}'", extension)),
+        }
+    }
+
+    /// Formats offered for saving, in menu order
+    pub const CHOICES: [OutputFormat; 8] = [
+        OutputFormat::Raster(ImageFormat::Png),
+        OutputFormat::Raster(ImageFormat::Jpeg),
+        OutputFormat::Raster(ImageFormat::WebP),
+        OutputFormat::Raster(ImageFormat::Bmp),
+        OutputFormat::Raster(ImageFormat::Tiff),
+        OutputFormat::Raster(ImageFormat::Ico),
+        OutputFormat::Svg,
+        OutputFormat::Pdf,
+    ];
+
+    /// Human-readable name for file dialogs and menus
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Svg => "SVG Vector",
+            OutputFormat::Pdf => "PDF Document",
+            OutputFormat::Raster(ImageFormat::Png) => "PNG Image",
+            OutputFormat::Raster(ImageFormat::Jpeg) => "JPEG Image",
+            OutputFormat::Raster(ImageFormat::WebP) => "WebP Image",
+            OutputFormat::Raster(ImageFormat::Bmp) => "BMP Image",
+            OutputFormat::Raster(ImageFormat::Tiff) => "TIFF Image",
+            OutputFormat::Raster(ImageFormat::Ico) => "ICO Icon",
+            OutputFormat::Raster(_) => "Raster Image",
+        }
+    }
+
+    /// File extensions of the format, preferred one first
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            OutputFormat::Svg => &["svg"],
+            OutputFormat::Pdf => &["pdf"],
+            OutputFormat::Raster(image_format) => image_format.extensions_str(),
+        }
+    }
+
+    /// Whether the format can keep the background transparent
+    pub fn supports_transparency(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Raster(ImageFormat::Png | ImageFormat::WebP | ImageFormat::Tiff | ImageFormat::Ico)
+        )
+    }
+}
+
+/// Generate the QR code and encode it in the given format
+///
+/// Raster formats use `spec.export_options`.
+///
+/// # Arguments
+/// * `spec` - Rendering specification containing QR code settings
+/// * `format` - Output format
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Encoded file contents
+/// * `Err(String)` - Error message if generation or encoding failed
+pub fn encode(spec: &QrSpec, format: OutputFormat) -> Result<Vec<u8>, String> {
+    match format {
+        OutputFormat::Svg => qr::generate_qr_svg(spec).map(String::into_bytes),
+        OutputFormat::Pdf => qr::generate_qr_pdf(spec, &spec.pdf_options),
+        OutputFormat::Raster(image_format) => {
+            let transparent = spec.export_options.transparent_background
+                && OutputFormat::Raster(image_format).supports_transparency();
+            let image = if transparent {
+                qr::generator::generate_transparent_qr_image(spec)?
+            } else {
+                qr::generate_qr_image(spec)?
+            };
+            encode_raster(&image, spec, image_format, transparent)
+        }
+    }
+}
+
+/// Encode a rendered image in a raster format using `spec.export_options`
+///
+/// Formats without (reliable) alpha support, JPEG and BMP, are flattened
+/// onto the background color first.
+///
+/// # Arguments
+/// * `image` - Rendered QR code or sheet
+/// * `spec` - Specification providing the background color and export options
+/// * `format` - Raster format
+/// * `transparent` - Whether the image has a transparent background (pads ICO images with transparency)
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Encoded file contents
+/// * `Err(String)` - Error message if an option is out of range or encoding failed
+fn encode_raster(image: &RgbaImage, spec: &QrSpec, format: ImageFormat, transparent: bool) -> Result<Vec<u8>, String> {
+    let options = &spec.export_options;
+    let encode_error = |e: image::ImageError| format!("Failed to encode image: {}", e);
+    let mut cursor = Cursor::new(Vec::new());
+
+    match format {
+        ImageFormat::Jpeg => {
+            let quality = check_quality(options.jpeg_quality, "JPEG")?;
+            JpegEncoder::new_with_quality(&mut cursor, quality)
+                .encode_image(&flatten(image, spec.bg_color))
+                .map_err(encode_error)?;
+        }
+        ImageFormat::Bmp => flatten(image, spec.bg_color).write_to(&mut cursor, format).map_err(encode_error)?,
+        // The image crate only writes lossless WebP
+        ImageFormat::WebP if !options.webp_lossless => {
+            let quality = check_quality(options.webp_quality, "WebP")?;
+            let encoded = webp::Encoder::from_rgba(image, image.width(), image.height()).encode(quality as f32);
+            return Ok(encoded.to_vec());
+        }
+        ImageFormat::Tiff if options.tiff_16_bit => DynamicImage::ImageRgba8(image.clone())
+            .into_rgba16()
+            .write_to(&mut cursor, format)
+            .map_err(encode_error)?,
+        ImageFormat::Ico => {
+            let [r, g, b] = spec.bg_color;
+            let padding = if transparent { Rgba([0, 0, 0, 0]) } else { Rgba([r, g, b, 255]) };
+            return encode_ico(image, &options.ico_sizes, padding);
+        }
+        _ => image.write_to(&mut cursor, format).map_err(encode_error)?,
+    }
+
+    Ok(cursor.into_inner())
+}
+
+/// Encode an icon holding the image at every requested size
+///
+/// Non-square images (rMQR) are centered on a square canvas first.
+///
+/// # Arguments
+/// * `image` - Rendered QR code
+/// * `sizes` - Square icon sizes in pixels (1-256)
+/// * `padding` - Color of the canvas around non-square images
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - ICO file contents with one PNG image per size, smallest first
+/// * `Err(String)` - Error message if no size or an invalid size is given
+fn encode_ico(image: &RgbaImage, sizes: &[u32], padding: Rgba<u8>) -> Result<Vec<u8>, String> {
+    if sizes.is_empty() {
+        return Err("Select at least one icon size".to_string());
+    }
+    if let Some(size) = sizes.iter().find(|size| !(1..=MAX_ICO_SIZE).contains(*size)) {
+        return Err(format!("Invalid icon size {} (expected 1-{})", size, MAX_ICO_SIZE));
+    }
+
+    let side = image.width().max(image.height());
+    let mut square = RgbaImage::from_pixel(side, side, padding);
+    imageops::replace(
+        &mut square,
+        image,
+        ((side - image.width()) / 2) as i64,
+        ((side - image.height()) / 2) as i64,
+    );
+
+    let mut sizes = sizes.to_vec();
+    sizes.sort_unstable();
+    sizes.dedup();
+    let icons: Vec<RgbaImage> = sizes
+        .iter()
+        .map(|&size| imageops::resize(&square, size, size, imageops::FilterType::Lanczos3))
+        .collect();
+    let frames = icons
+        .iter()
+        .map(|icon| IcoFrame::as_png(icon.as_raw(), icon.width(), icon.height(), ExtendedColorType::Rgba8))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to encode icon: {}", e))?;
+
+    let mut bytes = Vec::new();
+    IcoEncoder::new(&mut bytes)
+        .encode_images(&frames)
+        .map_err(|e| format!("Failed to encode icon: {}", e))?;
+    Ok(bytes)
+}
+
+/// Composite an image onto an opaque background color
+fn flatten(image: &RgbaImage, [r, g, b]: [u8; 3]) -> RgbImage {
+    let mut flat = RgbaImage::from_pixel(image.width(), image.height(), Rgba([r, g, b, 255]));
+    imageops::overlay(&mut flat, image, 0, 0);
+    DynamicImage::ImageRgba8(flat).into_rgb8()
+}
+
+/// Check that a quality setting is within 1-100
+fn check_quality(quality: u8, format: &str) -> Result<u8, String> {
+    if (1..=100).contains(&quality) {
+        Ok(quality)
+    } else {
+        Err(format!("Invalid {} quality {} (expected 1-100)", format, quality))
+    }
+}
+
+/// Generate the QR code and write it to a file without any UI interaction
+///
+/// The output format is chosen from the file extension: `.svg` and `.pdf`
+/// produce vector documents (PDF uses `spec.pdf_options` for the page
+/// layout), anything else is rasterized in the format matching the
+/// extension.
+///
+/// # Arguments
+/// * `spec` - Rendering specification containing QR code settings
+/// * `path` - Destination file
+///
+/// # Returns
+/// * `Ok((width, height))` - Output dimensions in pixels of the QR image
+/// * `Err(String)` - Error message if generation or writing failed
+pub fn export_to_path(spec: &QrSpec, path: &Path) -> Result<(u32, u32), String> {
+    let format = OutputFormat::from_path(path).map_err(|e| format!("Failed to save: {}", e))?;
+    let bytes = encode(spec, format).map_err(|e| format!("Error generating QR code: {}", e))?;
+    std::fs::write(path, bytes).map_err(|e| format!("Failed to save: {}", e))?;
+
+    qr::generator::output_size(spec)
+        .map_err(|e| format!("Error generating QR code: {}", e))
+}
+
+/// Write every code of a Structured Append sequence plus a combined sheet
+///
+/// For `out.png` the codes are written to `out-1of4.png` ... `out-4of4.png`
+/// in the format of the extension, and the sheet to `out-sheet.png`. The
+/// sheet is a raster image, so it is saved as PNG when the codes are SVG or
+/// PDF documents. With `export_options.transparent_background` the sheet
+/// is transparent too, if its format supports it.
+///
+/// # Arguments
+/// * `spec` - Rendering specification with `structured_append` set
+/// * `path` - Destination file the names are derived from
+///
+/// # Returns
+/// * `Ok(Vec<PathBuf>)` - Files written, in sequence order with the sheet last
+/// * `Err(String)` - Error message if splitting, generation or writing failed
+pub fn export_sequence(spec: &QrSpec, path: &Path) -> Result<Vec<PathBuf>, String> {
+    let format = OutputFormat::from_path(path).map_err(|e| format!("Failed to save: {}", e))?;
+    let parts = structured::split(spec).map_err(|e| format!("Error generating QR code: {}", e))?;
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("qrcode");
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("png");
+    let sibling = |suffix: &str, extension: &str| path.with_file_name(format!("{}-{}.{}", stem, suffix, extension));
+
+    let mut written = Vec::new();
+    for (index, part) in parts.iter().enumerate() {
+        let file = sibling(&format!("{}of{}", index + 1, parts.len()), extension);
+        let bytes = encode(part, format).map_err(|e| format!("Error generating QR code: {}", e))?;
+        std::fs::write(&file, bytes).map_err(|e| format!("Failed to save: {}", e))?;
+        written.push(file);
+    }
+
+    let (file, sheet_format) = match format {
+        OutputFormat::Raster(image_format) => (sibling("sheet", extension), image_format),
+        OutputFormat::Svg | OutputFormat::Pdf => (sibling("sheet", "png"), ImageFormat::Png),
+    };
+    let transparent = spec.export_options.transparent_background
+        && OutputFormat::Raster(sheet_format).supports_transparency();
+    let sheet = match transparent {
+        true => structured::render_transparent_sheet(spec),
+        false => structured::render_sheet(spec),
+    }
+    .map_err(|e| format!("Error generating QR code: {}", e))?;
+    let bytes = encode_raster(&sheet, spec, sheet_format, transparent)
+        .map_err(|e| format!("Error generating QR code: {}", e))?;
+    std::fs::write(&file, bytes).map_err(|e| format!("Failed to save: {}", e))?;
+    written.push(file);
+
+    Ok(written)
+}
+
+/// Read and parse a JSON preset file without any UI interaction
+///
+/// Shared by the GUI preset loader and the command-line interface.
+///
+/// # Arguments
+/// * `path` - Location of the preset JSON file
+///
+/// # Returns
+/// * `Ok(QrSpec)` - Rendering specification populated from the preset
+/// * `Err(String)` - Error message if the file cannot be read or parsed
+pub fn read_preset(path: &Path) -> Result<QrSpec, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read preset file: {}", e))?;
+    serde_json::from_str::<QrSpec>(&json)
+        .map_err(|e| format!("Failed to parse preset: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(OutputFormat::from_path(Path::new("a.SVG")), Ok(OutputFormat::Svg));
+        assert_eq!(OutputFormat::from_path(Path::new("a.pdf")), Ok(OutputFormat::Pdf));
+        assert_eq!(
+            OutputFormat::from_path(Path::new("a.png")),
+            Ok(OutputFormat::Raster(ImageFormat::Png))
+        );
+        assert!(OutputFormat::from_path(Path::new("a.xyz")).is_err());
+    }
+
+    #[test]
+    fn test_export_sequence() {
+        let dir = std::env::temp_dir().join(format!("qrtistry_sequence_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let spec = QrSpec {
+            qr_text: "Structured Append ".repeat(20),
+            structured_append: Some(2),
+            ..QrSpec::default()
+        };
+
+        let written = export_sequence(&spec, &dir.join("codes.svg")).unwrap();
+        let names: Vec<_> = written.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, ["codes-1of2.svg", "codes-2of2.svg", "codes-sheet.png"]);
+        assert!(written.iter().all(|p| p.exists()));
+
+        // A transparent export keeps the sheet transparent as well
+        let mut transparent = spec.clone();
+        transparent.export_options.transparent_background = true;
+        let written = export_sequence(&transparent, &dir.join("codes.png")).unwrap();
+        for file in &written {
+            assert_eq!(image::open(file).unwrap().to_rgba8().get_pixel(0, 0).0[3], 0, "{}", file.display());
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_encode_png() {
+        let bytes = encode(&QrSpec::default(), OutputFormat::Raster(ImageFormat::Png)).unwrap();
+        assert!(bytes.starts_with(b"\x89PNG"));
+    }
+
+    fn decode(bytes: &[u8]) -> DynamicImage {
+        image::load_from_memory(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_encode_every_choice() {
+        // Translucent modules must not break formats without alpha
+        let spec = QrSpec { qr_opacity: 0.8, ..QrSpec::default() };
+        for format in OutputFormat::CHOICES {
+            let bytes = encode(&spec, format).unwrap_or_else(|e| panic!("{}: {}", format.name(), e));
+            assert_eq!(OutputFormat::from_path(Path::new(&format!("qr.{}", format.extensions()[0]))), Ok(format));
+            if let OutputFormat::Raster(image_format) = format {
+                assert_eq!(image::guess_format(&bytes).unwrap(), image_format, "{}", format.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_raster_options() {
+        let mut spec = QrSpec::default();
+        let jpeg = OutputFormat::Raster(ImageFormat::Jpeg);
+        let webp = OutputFormat::Raster(ImageFormat::WebP);
+
+        let fine = encode(&spec, jpeg).unwrap();
+        spec.export_options.jpeg_quality = 20;
+        assert!(encode(&spec, jpeg).unwrap().len() < fine.len());
+        spec.export_options.jpeg_quality = 0;
+        assert!(encode(&spec, jpeg).is_err());
+
+        // Lossless WebP keeps every pixel, lossy WebP is a VP8 (not VP8L) stream
+        let lossless = encode(&spec, webp).unwrap();
+        assert_eq!(&lossless[12..16], b"VP8L");
+        assert_eq!(decode(&lossless).to_rgba8(), qr::generate_qr_image(&spec).unwrap());
+        spec.export_options.webp_lossless = false;
+        assert_eq!(&encode(&spec, webp).unwrap()[12..16], b"VP8 ");
+
+        spec.export_options.tiff_16_bit = true;
+        let tiff = decode(&encode(&spec, OutputFormat::Raster(ImageFormat::Tiff)).unwrap());
+        assert_eq!(tiff.color(), image::ColorType::Rgba16);
+    }
+
+    #[test]
+    fn test_ico_sizes() {
+        // rMQR is padded to a square before scaling
+        let mut spec = QrSpec {
+            qr_text: "12345".to_string(),
+            symbol_type: crate::types::SymbolType::Rectangular,
+            ..QrSpec::default()
+        };
+        spec.export_options.ico_sizes = vec![48, 16, 48, 32];
+        let bytes = encode(&spec, OutputFormat::Raster(ImageFormat::Ico)).unwrap();
+        // Header: reserved, type 1 (icon), three images of 16, 32 and 48 px
+        assert_eq!(&bytes[..6], &[0, 0, 1, 0, 3, 0]);
+        let sizes: Vec<u8> = (0..3).map(|i| bytes[6 + 16 * i]).collect();
+        assert_eq!(sizes, [16, 32, 48]);
+        assert_eq!(decode(&bytes).dimensions(), (48, 48));
+
+        spec.export_options.ico_sizes = vec![512];
+        assert!(encode(&spec, OutputFormat::Raster(ImageFormat::Ico)).is_err());
+        spec.export_options.ico_sizes.clear();
+        assert!(encode(&spec, OutputFormat::Raster(ImageFormat::Ico)).is_err());
+    }
+
+    #[test]
+    fn test_transparent_export() {
+        let mut spec = QrSpec::default();
+        spec.export_options.transparent_background = true;
+
+        let png = decode(&encode(&spec, OutputFormat::Raster(ImageFormat::Png)).unwrap()).to_rgba8();
+        assert_eq!(png.get_pixel(0, 0).0[3], 0);
+        let icon = decode(&encode(&spec, OutputFormat::Raster(ImageFormat::Ico)).unwrap()).to_rgba8();
+        assert_eq!(icon.get_pixel(0, 0).0[3], 0);
+
+        // JPEG has no alpha channel and keeps the background color
+        let jpeg = decode(&encode(&spec, OutputFormat::Raster(ImageFormat::Jpeg)).unwrap()).to_rgb8();
+        assert!(jpeg.get_pixel(0, 0).0.iter().all(|&c| c > 245));
+    }
+}
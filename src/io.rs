@@ -1,59 +1,76 @@
 //! File input/output operations
 //!
-//! Handles saving and loading:
-//! - QR code PNG export
+//! Handles saving and loading through native file dialogs:
+//! - QR code export in the format chosen in the export dialog
 //! - Configuration presets (JSON)
+//!
+//! The dialog-free work is done by the library's `export` module.
 
 use chrono::Local;
-use image;
+
+use qrtistry::export::{export_sequence, export_to_path, read_preset, OutputFormat};
 
 use crate::app::QrCodeApp;
-use crate::qr;
 
-/// Save QR code as PNG file with file dialog
+/// Save QR code in the given format with file dialog
 ///
 /// Opens a native file save dialog and exports the current QR code design
-/// as a PNG image file. Uses timestamp-based filename by default.
+/// using the spec's export options, with the logo fit applied as in the
+/// preview. The file extension is corrected to
+/// match the format if needed. Uses timestamp-based filename by default.
+/// A Structured Append sequence is saved as one numbered file per code
+/// plus a combined sheet.
 ///
 /// # Arguments
 /// * `app` - Application state containing QR code settings
-pub fn save_qr_code(app: &mut QrCodeApp) {
+/// * `format` - Output format chosen in the export dialog
+///
+/// # Returns
+/// `true` if the file was written
+pub fn save_qr_code(app: &mut QrCodeApp, format: OutputFormat) -> bool {
     // Validate input
-    if app.qr_text.is_empty() {
+    if app.spec.qr_text.is_empty() {
         app.status_message = "⚠️ Please enter text for the QR code".to_string();
-        return;
+        return false;
     }
 
     // Generate default filename with timestamp
+    let extensions = format.extensions();
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let default_filename = format!("qrcode_{}.png", timestamp);
+    let default_filename = format!("qrcode_{}.{}", timestamp, extensions[0]);
 
     // Open file save dialog
     let file = rfd::FileDialog::new()
         .set_file_name(&default_filename)
-        .add_filter("PNG Image", &["png"])
+        .add_filter(format.name(), extensions)
         .save_file();
 
-    if let Some(path) = file {
-        // Generate QR code image
-        match qr::generate_qr_image(app) {
-            Ok(image) => {
-                // Save to file
-                match image.save(&path) {
-                    Ok(_) => {
-                        app.status_message = format!("✅ Saved to: {}", path.display());
-                    }
-                    Err(e) => {
-                        app.status_message = format!("❌ Failed to save: {}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                app.status_message = format!("❌ Error generating QR code: {}", e);
-            }
+    if let Some(mut path) = file {
+        // The extension selects the encoder, so it must match the format
+        if OutputFormat::from_path(&path) != Ok(format) {
+            path.set_extension(extensions[0]);
         }
+
+        // Save what the preview shows: the logo fit applies to the file,
+        // not to the user's settings
+        let (spec, _) = app.fitted_spec();
+
+        // A Structured Append sequence is saved as numbered files plus a sheet
+        let result = match spec.structured_append {
+            Some(_) => export_sequence(&spec, &path).map(|written| {
+                format!("✅ Saved {} linked codes and a sheet next to: {}", written.len() - 1, path.display())
+            }),
+            None => export_to_path(&spec, &path).map(|_| format!("✅ Saved to: {}", path.display())),
+        };
+        let saved = result.is_ok();
+        app.status_message = match result {
+            Ok(message) => message,
+            Err(e) => format!("❌ {}", e),
+        };
+        saved
     } else {
         app.status_message = "Save cancelled".to_string();
+        false
     }
 }
 
@@ -103,10 +120,10 @@ pub fn save_preset(app: &mut QrCodeApp) {
 /// Load configuration preset from JSON file
 ///
 /// Opens a file open dialog and loads a previously saved configuration preset.
-/// Automatically generates preview after loading.
+/// Automatically generates preview after loading. The current tab is kept.
 ///
-/// **Note**: Image file paths in the preset are attempted to be reloaded,
-/// but may fail if files have moved.
+/// **Note**: Presets do not contain images, so any loaded logo or
+/// background image is cleared.
 ///
 /// # Arguments
 /// * `app` - Application state to update with loaded preset
@@ -118,48 +135,21 @@ pub fn load_preset(app: &mut QrCodeApp, ctx: &eframe::egui::Context) {
         .pick_file();
 
     if let Some(path) = file {
-        // Read JSON file
-        match std::fs::read_to_string(&path) {
-            Ok(json) => {
-                // Deserialize JSON to application state
-                match serde_json::from_str::<QrCodeApp>(&json) {
-                    Ok(mut loaded) => {
-                        // Preserve runtime-only fields that shouldn't be overwritten
-                        loaded.preview_texture = None;
-                        loaded.status_message = format!("✅ Preset loaded from: {}", path.display());
-                        loaded.selected_tab = app.selected_tab; // Keep current tab
-                        
-                        // Attempt to reload image files if paths exist
-                        // (May fail if files have been moved/deleted)
-                        if let Some(logo_path) = &loaded.logo_path {
-                            if let Ok(img) = image::open(logo_path) {
-                                loaded.logo_image = Some(img);
-                            } else {
-                                loaded.logo_path = None; // Clear if file not found
-                            }
-                        }
-                        
-                        if let Some(bg_path) = &loaded.bg_image_path {
-                            if let Ok(img) = image::open(bg_path) {
-                                loaded.bg_image = Some(img);
-                            } else {
-                                loaded.bg_image_path = None; // Clear if file not found
-                            }
-                        }
-                        
-                        // Update application state
-                        *app = loaded;
-                        
-                        // Auto-generate preview with new settings
-                        app.generate_preview(ctx);
-                    }
-                    Err(e) => {
-                        app.status_message = format!("❌ Failed to parse preset: {}", e);
-                    }
-                }
+        match read_preset(&path) {
+            Ok(spec) => {
+                // Presets only carry settings: images are not stored,
+                // so any loaded logo or background is cleared
+                app.spec = spec;
+                app.logo_path = None;
+                app.bg_image_path = None;
+                app.preview_texture = None;
+                app.status_message = format!("✅ Preset loaded from: {}", path.display());
+                
+                // Auto-generate preview with new settings
+                app.generate_preview(ctx);
             }
             Err(e) => {
-                app.status_message = format!("❌ Failed to read preset file: {}", e);
+                app.status_message = format!("❌ {}", e);
             }
         }
     } else {
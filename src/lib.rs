@@ -0,0 +1,65 @@
+//! QRtistry library
+//!
+//! The styled QR code renderer behind the QRtistry application, usable
+//! without the GUI from services, build tools and other crates.
+//!
+//! # Features
+//! - Standard QR, Micro QR and rectangular Micro QR (rMQR) symbols
+//! - Structured Append: long content split across up to 16 linked codes
+//! - ECI character sets (ISO-8859-x, Windows code pages, Shift JIS, ...)
+//!   for scanners that do not assume UTF-8
+//! - Structured payloads (URLs with UTM parameters, WiFi, vCard, MeCard,
+//!   calendar events, locations, SMS, email and phone) with correct escaping
+//! - Payment payloads (SEPA/EPC, Swiss QR-bill, UPI, Bitcoin) with IBAN,
+//!   amount and reference validation
+//! - Authenticator (`otpauth://`) provisioning codes whose secrets are
+//!   never written to presets
+//! - GS1 element strings (FNC1) and GS1 Digital Link URIs, with Application
+//!   Identifier validation
+//! - Module shapes (square, circle, rounded, dots) and eye styles
+//! - Solid colors and gradients (horizontal, vertical, diagonal, radial)
+//! - Logo overlay and background image blending, with logo occlusion
+//!   checked against the error correction budget
+//! - Output as `RgbaImage`, SVG, print-ready PDF or PNG, JPEG, WebP, BMP,
+//!   TIFF and ICO files, optionally with a transparent background
+//! - Batch generation from CSV/JSONL files
+//!
+//! # Example
+//! ```
+//! use qrtistry::{QrBuilder, GradientType, ModuleStyle};
+//!
+//! let image = QrBuilder::new("https://example.com")
+//!     .size(400)
+//!     .module_style(ModuleStyle::Dots)
+//!     .gradient(GradientType::Diagonal, [0, 121, 107])
+//!     .render()
+//!     .unwrap();
+//! assert_eq!(image.width(), image.height());
+//! ```
+//!
+//! # Modules
+//! - `QrBuilder`: Chainable builder API (the recommended entry point)
+//! - `qr`: Rendering pipeline driven by a serializable `QrSpec`
+//! - `types`: Style enums and color presets
+//! - `payload`: Structured content such as WiFi credentials, contacts, payments
+//!   and authenticator secrets
+//! - `export`: Encoding to bytes/files and reading presets
+//! - `batch`: CSV/JSONL batch generation
+//!
+//! The `gui` feature (enabled by default) builds the desktop application;
+//! depend on the library with `default-features = false` to skip the GUI
+//! dependencies.
+
+pub mod types;
+pub mod payload;
+pub mod qr;
+pub mod export;
+pub mod batch;
+mod builder;
+
+pub use builder::QrBuilder;
+pub use export::{ExportOptions, OutputFormat};
+pub use payload::Payload;
+pub use qr::QrSpec;
+pub use qr::pdf::PdfOptions;
+pub use types::{Antialiasing, CharacterSet, ContentMode, EncodingMode, ErrorCorrectionLevel, EyeFrameStyle, EyeOverride, EyePosition, EyePupilStyle, EyeStyle, GradientType, LogoFit, ModuleStyle, PatternStyle, QrVersion, SizingMode, SymbolType};
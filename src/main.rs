@@ -16,20 +16,25 @@
 //! - Transparency control for watermark effects
 //! - Real-time preview with large display area
 //! - Save/load preset configurations as JSON
-//! - Export to PNG with timestamp-based filenames
+//! - Export to PNG, JPEG, WebP, BMP, TIFF, ICO, SVG or print-ready PDF with
+//!   timestamp-based filenames
+//! - Batch generation from CSV/JSONL with filename templates
 //! - Resizable panel-based UI layout
 //!
 //! # Architecture
-//! The application is organized into logical modules:
-//! - `app`: Main application state and GUI update loop
-//! - `types`: Enums and data structures
-//! - `qr`: QR code generation, drawing, colors, and image operations
+//! Rendering is provided by the `qrtistry` library crate (`types`, `qr`,
+//! `export`, `batch` and the `QrBuilder` API). This binary adds:
+//! - `app`: Main application state (wrapping a `QrSpec`) and GUI update loop
 //! - `ui`: User interface rendering (tabs, preview, helpers)
-//! - `io`: File input/output operations
+//! - `io`: File dialogs for export and presets
+//! - `cli`: Headless command-line subcommands
 //!
 //! # Usage
 //! ```bash
 //! cargo run --release
+//!
+//! # Headless rendering (no window is opened)
+//! cargo run --release -- render --text "https://example.com" -o qr.png
 //! ```
 //!
 //! The application window opens at 1200×800px with:
@@ -38,23 +43,29 @@
 //! - Center panel: Large QR code preview
 //! - Bottom bar: Status messages
 
-use eframe;
-
-// Module declarations
+// Module declarations (rendering lives in the `qrtistry` library)
 mod app;
-mod types;
-mod qr;
 mod ui;
 mod io;
+mod cli;
+
+use clap::Parser;
 
 /// Application entry point
 ///
-/// Initializes the eframe window and starts the GUI event loop.
+/// Runs a headless subcommand if one was given on the command line,
+/// otherwise initializes the eframe window and starts the GUI event loop.
 ///
 /// # Returns
 /// * `Ok(())` - Application exited normally
 /// * `Err(eframe::Error)` - Application failed to start
 fn main() -> eframe::Result<()> {
+    // Headless mode: run the subcommand and exit without creating a window
+    let args = cli::Cli::parse();
+    if let Some(command) = args.command {
+        std::process::exit(cli::run(command));
+    }
+
     // Configure window options
     let options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
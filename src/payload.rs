@@ -0,0 +1,1878 @@
+//! Structured content payloads
+//!
+//! Phones act on QR content in a few well-known formats: opening a
+//! tracked link, joining a WiFi network, adding a contact or calendar
+//! event, opening a map, starting a text message, email or call, or
+//! paying an invoice. Each format escapes its values differently, so
+//! typing them by hand goes wrong easily. A `Payload` holds the fields and
+//! `Payload::to_text` builds the escaped string that is encoded as
+//! `QrSpec::qr_text`.
+//!
+//! Payment payloads (SEPA credit transfers, Swiss QR-bills, UPI and
+//! Bitcoin) check their account numbers, amounts and references before
+//! anything is encoded, since a typo there sends money to the wrong place.
+//!
+//! Presets store the payload next to the flattened text, so a saved
+//! design reopens in the form it was made with. Authenticator secrets are
+//! the exception: they are left out of presets, both from the payload and
+//! from the saved text, and have to be entered again after loading.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::ErrorCorrectionLevel;
+
+/// Kind of payload, as offered by the content type selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    Url,
+    Wifi,
+    VCard,
+    MeCard,
+    Event,
+    Geo,
+    Sms,
+    Email,
+    Phone,
+    Epc,
+    SwissQrBill,
+    Upi,
+    Bitcoin,
+    Otp,
+}
+
+impl PayloadKind {
+    /// Every kind, in menu order
+    pub const ALL: [PayloadKind; 14] = [
+        PayloadKind::Url,
+        PayloadKind::Wifi,
+        PayloadKind::VCard,
+        PayloadKind::MeCard,
+        PayloadKind::Event,
+        PayloadKind::Geo,
+        PayloadKind::Sms,
+        PayloadKind::Email,
+        PayloadKind::Phone,
+        PayloadKind::Epc,
+        PayloadKind::SwissQrBill,
+        PayloadKind::Upi,
+        PayloadKind::Bitcoin,
+        PayloadKind::Otp,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            PayloadKind::Url => "URL",
+            PayloadKind::Wifi => "WiFi",
+            PayloadKind::VCard => "vCard",
+            PayloadKind::MeCard => "MeCard",
+            PayloadKind::Event => "Calendar Event",
+            PayloadKind::Geo => "Location",
+            PayloadKind::Sms => "SMS",
+            PayloadKind::Email => "Email",
+            PayloadKind::Phone => "Phone",
+            PayloadKind::Epc => "SEPA Transfer (EPC)",
+            PayloadKind::SwissQrBill => "Swiss QR-bill",
+            PayloadKind::Upi => "UPI Payment",
+            PayloadKind::Bitcoin => "Bitcoin",
+            PayloadKind::Otp => "Authenticator",
+        }
+    }
+}
+
+/// Extra query parameter of a URL payload
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryParameter {
+    pub name: String,
+    pub value: String,
+}
+
+/// WiFi network authentication
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WifiSecurity {
+    /// WPA, WPA2 or WPA3 personal
+    #[default]
+    Wpa,
+    /// Legacy WEP
+    Wep,
+    /// Open network without a password
+    Open,
+}
+
+/// vCard format version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VCardVersion {
+    /// vCard 3.0 (RFC 2426), read by nearly every phone
+    #[default]
+    V3,
+    /// vCard 4.0 (RFC 6350)
+    V4,
+}
+
+/// One-time password scheme of an authenticator entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OtpType {
+    /// Time-based (RFC 6238), a new code every period
+    #[default]
+    Totp,
+    /// Counter-based (RFC 4226), a new code every time it is used
+    Hotp,
+}
+
+/// HMAC hash of an authenticator entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OtpAlgorithm {
+    /// SHA-1, the only one every authenticator app supports
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl OtpAlgorithm {
+    /// Every algorithm, in menu order
+    pub const ALL: [OtpAlgorithm; 3] = [OtpAlgorithm::Sha1, OtpAlgorithm::Sha256, OtpAlgorithm::Sha512];
+
+    /// Name as written in `otpauth` URIs
+    pub fn name(self) -> &'static str {
+        match self {
+            OtpAlgorithm::Sha1 => "SHA1",
+            OtpAlgorithm::Sha256 => "SHA256",
+            OtpAlgorithm::Sha512 => "SHA512",
+        }
+    }
+}
+
+/// Currency of a Swiss QR-bill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SwissCurrency {
+    #[default]
+    Chf,
+    Eur,
+}
+
+impl SwissCurrency {
+    /// ISO 4217 code
+    pub fn code(self) -> &'static str {
+        match self {
+            SwissCurrency::Chf => "CHF",
+            SwissCurrency::Eur => "EUR",
+        }
+    }
+}
+
+/// Structured postal address of a Swiss QR-bill party
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostalAddress {
+    pub name: String,
+    pub street: String,
+    pub building_number: String,
+    pub postal_code: String,
+    pub town: String,
+    /// Two-letter ISO 3166 country code
+    pub country: String,
+}
+
+impl PostalAddress {
+    /// Whether every field is blank
+    fn is_empty(&self) -> bool {
+        self.fields().iter().all(|field| field.is_empty())
+    }
+
+    /// Name, street, building number, postal code, town and country
+    fn fields(&self) -> [&str; 6] {
+        [&self.name, &self.street, &self.building_number, &self.postal_code, &self.town, &self.country]
+            .map(|field| field.trim())
+    }
+
+    /// Every field, for batch substitution
+    fn fields_mut(&mut self) -> Vec<&mut String> {
+        vec![
+            &mut self.name,
+            &mut self.street,
+            &mut self.building_number,
+            &mut self.postal_code,
+            &mut self.town,
+            &mut self.country,
+        ]
+    }
+}
+
+/// Contact details shared by vCard and MeCard
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Contact {
+    pub first_name: String,
+    pub last_name: String,
+    pub organization: String,
+    /// Job title (vCard only)
+    pub title: String,
+    pub phone: String,
+    pub email: String,
+    pub website: String,
+    pub street: String,
+    pub city: String,
+    pub region: String,
+    pub postal_code: String,
+    pub country: String,
+    pub note: String,
+}
+
+impl Contact {
+    /// Display name: "First Last", or the organization if there is no name
+    fn full_name(&self) -> String {
+        let name = format!("{} {}", self.first_name.trim(), self.last_name.trim());
+        match name.trim() {
+            "" => self.organization.trim().to_string(),
+            name => name.to_string(),
+        }
+    }
+
+    /// Address components: street, city, region, postal code and country
+    fn address(&self) -> [&str; 5] {
+        [&self.street, &self.city, &self.region, &self.postal_code, &self.country].map(|part| part.trim())
+    }
+
+    /// Every field, for batch substitution
+    fn fields_mut(&mut self) -> Vec<&mut String> {
+        vec![
+            &mut self.first_name,
+            &mut self.last_name,
+            &mut self.organization,
+            &mut self.title,
+            &mut self.phone,
+            &mut self.email,
+            &mut self.website,
+            &mut self.street,
+            &mut self.city,
+            &mut self.region,
+            &mut self.postal_code,
+            &mut self.country,
+            &mut self.note,
+        ]
+    }
+}
+
+/// Structured content of a QR code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Payload {
+    /// Web link with UTM campaign parameters for analytics
+    Url {
+        /// Page address; `https://` is added if no scheme is given
+        url: String,
+        /// `utm_source`, e.g. "flyer"
+        source: String,
+        /// `utm_medium`, e.g. "print"
+        medium: String,
+        /// `utm_campaign`, e.g. "spring_sale"
+        campaign: String,
+        /// `utm_term`
+        term: String,
+        /// `utm_content`, e.g. to tell apart placements of one campaign
+        content: String,
+        /// Further query parameters, appended after the UTM ones
+        #[serde(default)]
+        params: Vec<QueryParameter>,
+    },
+    /// Network credentials (`WIFI:T:WPA;S:...;P:...;;`)
+    Wifi {
+        ssid: String,
+        password: String,
+        security: WifiSecurity,
+        hidden: bool,
+    },
+    /// Contact card (`BEGIN:VCARD`)
+    VCard { version: VCardVersion, contact: Contact },
+    /// Compact contact card (`MECARD:N:...;;`)
+    MeCard { contact: Contact },
+    /// Calendar entry (`BEGIN:VEVENT`); times are local to the reader
+    Event {
+        summary: String,
+        location: String,
+        description: String,
+        /// "YYYY-MM-DD HH:MM", or "YYYY-MM-DD" for all-day events
+        start: String,
+        /// Same format as `start`; empty for none
+        end: String,
+        all_day: bool,
+    },
+    /// Map location (`geo:` URI, WGS 84 degrees)
+    Geo { latitude: f64, longitude: f64 },
+    /// Text message (`SMSTO:number:message`)
+    Sms { number: String, message: String },
+    /// Email draft (`mailto:` URI)
+    Email { to: String, subject: String, body: String },
+    /// Phone call (`tel:` URI)
+    Phone { number: String },
+    /// SEPA credit transfer (EPC069-12 "BCD" code, read by European banking apps)
+    Epc {
+        /// Beneficiary name
+        name: String,
+        iban: String,
+        /// Beneficiary bank; optional within the EEA
+        bic: String,
+        /// Euros, e.g. "12.50"; empty to let the payer enter it
+        amount: String,
+        /// Four-letter ISO 20022 purpose code, e.g. "GDDS"
+        purpose: String,
+        /// ISO 11649 creditor reference ("RF..."); excludes `text`
+        reference: String,
+        /// Free-text remittance information; excludes `reference`
+        text: String,
+        /// Note shown to the payer
+        information: String,
+    },
+    /// Swiss QR-bill (Swiss Payment Standards 2.2, `SPC` code)
+    SwissQrBill {
+        /// Swiss or Liechtenstein IBAN or QR-IBAN
+        iban: String,
+        creditor: PostalAddress,
+        /// Empty to let the payer enter it
+        amount: String,
+        currency: SwissCurrency,
+        /// Payer; leave every field empty to let the payer fill it in
+        debtor: PostalAddress,
+        /// QR reference (27 digits, required with a QR-IBAN), creditor
+        /// reference ("RF...") or empty
+        reference: String,
+        /// Unstructured message
+        message: String,
+    },
+    /// Indian UPI payment intent (`upi://pay` URI)
+    Upi {
+        /// Payee virtual payment address, e.g. "shop@upi"
+        vpa: String,
+        /// Payee name
+        name: String,
+        /// Rupees, e.g. "250.00"; empty to let the payer enter it
+        amount: String,
+        note: String,
+        /// Transaction reference
+        reference: String,
+    },
+    /// Bitcoin payment request (BIP21 `bitcoin:` URI)
+    Bitcoin {
+        address: String,
+        /// BTC, up to 8 decimals; empty to let the payer enter it
+        amount: String,
+        label: String,
+        message: String,
+    },
+    /// Two-factor authenticator provisioning (`otpauth://` URI, Google
+    /// Authenticator key URI format)
+    Otp {
+        otp_type: OtpType,
+        /// Service name shown in the app, e.g. "Acme"
+        issuer: String,
+        /// User name or email address
+        account: String,
+        /// Base32 shared secret; never written to presets
+        #[serde(skip_serializing, default)]
+        secret: String,
+        algorithm: OtpAlgorithm,
+        /// Code length (6 or 8)
+        digits: u8,
+        /// Seconds each TOTP code is valid
+        period: u32,
+        /// Initial HOTP counter
+        counter: u64,
+    },
+}
+
+impl Payload {
+    /// Empty payload of a kind
+    pub fn new(kind: PayloadKind) -> Self {
+        match kind {
+            PayloadKind::Url => Payload::Url {
+                url: String::new(),
+                source: String::new(),
+                medium: String::new(),
+                campaign: String::new(),
+                term: String::new(),
+                content: String::new(),
+                params: Vec::new(),
+            },
+            PayloadKind::Wifi => Payload::Wifi {
+                ssid: String::new(),
+                password: String::new(),
+                security: WifiSecurity::Wpa,
+                hidden: false,
+            },
+            PayloadKind::VCard => Payload::VCard { version: VCardVersion::V3, contact: Contact::default() },
+            PayloadKind::MeCard => Payload::MeCard { contact: Contact::default() },
+            PayloadKind::Event => Payload::Event {
+                summary: String::new(),
+                location: String::new(),
+                description: String::new(),
+                start: String::new(),
+                end: String::new(),
+                all_day: false,
+            },
+            PayloadKind::Geo => Payload::Geo { latitude: 0.0, longitude: 0.0 },
+            PayloadKind::Sms => Payload::Sms { number: String::new(), message: String::new() },
+            PayloadKind::Email => Payload::Email { to: String::new(), subject: String::new(), body: String::new() },
+            PayloadKind::Phone => Payload::Phone { number: String::new() },
+            PayloadKind::Epc => Payload::Epc {
+                name: String::new(),
+                iban: String::new(),
+                bic: String::new(),
+                amount: String::new(),
+                purpose: String::new(),
+                reference: String::new(),
+                text: String::new(),
+                information: String::new(),
+            },
+            PayloadKind::SwissQrBill => Payload::SwissQrBill {
+                iban: String::new(),
+                creditor: PostalAddress { country: "CH".to_string(), ..PostalAddress::default() },
+                amount: String::new(),
+                currency: SwissCurrency::Chf,
+                debtor: PostalAddress::default(),
+                reference: String::new(),
+                message: String::new(),
+            },
+            PayloadKind::Upi => Payload::Upi {
+                vpa: String::new(),
+                name: String::new(),
+                amount: String::new(),
+                note: String::new(),
+                reference: String::new(),
+            },
+            PayloadKind::Bitcoin => Payload::Bitcoin {
+                address: String::new(),
+                amount: String::new(),
+                label: String::new(),
+                message: String::new(),
+            },
+            PayloadKind::Otp => Payload::Otp {
+                otp_type: OtpType::Totp,
+                issuer: String::new(),
+                account: String::new(),
+                secret: String::new(),
+                algorithm: OtpAlgorithm::Sha1,
+                digits: 6,
+                period: 30,
+                counter: 0,
+            },
+        }
+    }
+
+    /// Kind of this payload
+    pub fn kind(&self) -> PayloadKind {
+        match self {
+            Payload::Url { .. } => PayloadKind::Url,
+            Payload::Wifi { .. } => PayloadKind::Wifi,
+            Payload::VCard { .. } => PayloadKind::VCard,
+            Payload::MeCard { .. } => PayloadKind::MeCard,
+            Payload::Event { .. } => PayloadKind::Event,
+            Payload::Geo { .. } => PayloadKind::Geo,
+            Payload::Sms { .. } => PayloadKind::Sms,
+            Payload::Email { .. } => PayloadKind::Email,
+            Payload::Phone { .. } => PayloadKind::Phone,
+            Payload::Epc { .. } => PayloadKind::Epc,
+            Payload::SwissQrBill { .. } => PayloadKind::SwissQrBill,
+            Payload::Upi { .. } => PayloadKind::Upi,
+            Payload::Bitcoin { .. } => PayloadKind::Bitcoin,
+            Payload::Otp { .. } => PayloadKind::Otp,
+        }
+    }
+
+    /// Error correction level the format mandates, if any
+    ///
+    /// Swiss QR-bills must use level M so the cross in their center
+    /// does not make them unreadable.
+    pub fn required_ec_level(&self) -> Option<ErrorCorrectionLevel> {
+        match self {
+            Payload::SwissQrBill { .. } => Some(ErrorCorrectionLevel::Medium),
+            _ => None,
+        }
+    }
+
+    /// Payload of another kind, keeping the fields both kinds share
+    ///
+    /// Contacts carry over between vCard and MeCard, and the number
+    /// between SMS and phone; anything else starts empty.
+    pub fn converted(&self, kind: PayloadKind) -> Self {
+        match (self, kind) {
+            (Payload::VCard { contact, .. }, PayloadKind::MeCard) => Payload::MeCard { contact: contact.clone() },
+            (Payload::MeCard { contact }, PayloadKind::VCard) => {
+                Payload::VCard { version: VCardVersion::V3, contact: contact.clone() }
+            }
+            (Payload::Sms { number, .. }, PayloadKind::Phone) => Payload::Phone { number: number.clone() },
+            (Payload::Phone { number }, PayloadKind::Sms) => {
+                Payload::Sms { number: number.clone(), message: String::new() }
+            }
+            _ if self.kind() == kind => self.clone(),
+            _ => Payload::new(kind),
+        }
+    }
+
+    /// Every free-text field, for substituting batch row values
+    ///
+    /// Numbers, choices and switches are not included. The authenticator
+    /// secret is, so each row can enroll its own account.
+    pub fn text_fields_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            Payload::Url { url, source, medium, campaign, term, content, params } => {
+                let mut fields = vec![url, source, medium, campaign, term, content];
+                for param in params {
+                    fields.extend([&mut param.name, &mut param.value]);
+                }
+                fields
+            }
+            Payload::Wifi { ssid, password, .. } => vec![ssid, password],
+            Payload::VCard { contact, .. } | Payload::MeCard { contact } => contact.fields_mut(),
+            Payload::Event { summary, location, description, start, end, .. } => {
+                vec![summary, location, description, start, end]
+            }
+            Payload::Geo { .. } => Vec::new(),
+            Payload::Sms { number, message } => vec![number, message],
+            Payload::Email { to, subject, body } => vec![to, subject, body],
+            Payload::Phone { number } => vec![number],
+            Payload::Epc { name, iban, bic, amount, purpose, reference, text, information } => {
+                vec![name, iban, bic, amount, purpose, reference, text, information]
+            }
+            Payload::SwissQrBill { iban, creditor, amount, debtor, reference, message, .. } => {
+                let mut fields = vec![iban, amount, reference, message];
+                fields.extend(creditor.fields_mut());
+                fields.extend(debtor.fields_mut());
+                fields
+            }
+            Payload::Upi { vpa, name, amount, note, reference } => vec![vpa, name, amount, note, reference],
+            Payload::Bitcoin { address, amount, label, message } => vec![address, amount, label, message],
+            Payload::Otp { issuer, account, secret, .. } => vec![issuer, account, secret],
+        }
+    }
+
+    /// Build the text to encode
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Payload in its wire format, with every value escaped
+    /// * `Err(String)` - Error message naming the missing or invalid field
+    pub fn to_text(&self) -> Result<String, String> {
+        match self {
+            Payload::Url { url, source, medium, campaign, term, content, params } => {
+                let utm = [
+                    ("utm_source", source),
+                    ("utm_medium", medium),
+                    ("utm_campaign", campaign),
+                    ("utm_term", term),
+                    ("utm_content", content),
+                ];
+                url_text(url, &utm, params)
+            }
+            Payload::Wifi { ssid, password, security, hidden } => wifi_text(ssid, password, *security, *hidden),
+            Payload::VCard { version, contact } => vcard_text(contact, *version),
+            Payload::MeCard { contact } => mecard_text(contact),
+            Payload::Event { summary, location, description, start, end, all_day } => {
+                event_text(summary, location, description, start, end, *all_day)
+            }
+            Payload::Geo { latitude, longitude } => {
+                if !(-90.0..=90.0).contains(latitude) {
+                    return Err(format!("Latitude must be between -90 and 90 (found {})", latitude));
+                }
+                if !(-180.0..=180.0).contains(longitude) {
+                    return Err(format!("Longitude must be between -180 and 180 (found {})", longitude));
+                }
+                Ok(format!("geo:{},{}", degrees(*latitude), degrees(*longitude)))
+            }
+            Payload::Sms { number, message } => Ok(format!("SMSTO:{}:{}", phone_number(number)?, message)),
+            Payload::Email { to, subject, body } => {
+                let to = to.trim();
+                if to.is_empty() {
+                    return Err("Email address is required".to_string());
+                }
+                if !to.contains('@') || to.contains(char::is_whitespace) {
+                    return Err(format!("'{}' is not an email address", to));
+                }
+                let query: Vec<String> = [("subject", subject), ("body", body)]
+                    .iter()
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(|(name, value)| format!("{}={}", name, percent_encode(value)))
+                    .collect();
+                if query.is_empty() {
+                    Ok(format!("mailto:{}", to))
+                } else {
+                    Ok(format!("mailto:{}?{}", to, query.join("&")))
+                }
+            }
+            Payload::Phone { number } => Ok(format!("tel:{}", phone_number(number)?)),
+            Payload::Epc { name, iban, bic, amount, purpose, reference, text, information } => {
+                epc_text(name, iban, bic, amount, purpose, reference, text, information)
+            }
+            Payload::SwissQrBill { iban, creditor, amount, currency, debtor, reference, message } => {
+                swiss_qr_bill_text(iban, creditor, amount, *currency, debtor, reference, message)
+            }
+            Payload::Upi { vpa, name, amount, note, reference } => upi_text(vpa, name, amount, note, reference),
+            Payload::Bitcoin { address, amount, label, message } => bitcoin_text(address, amount, label, message),
+            Payload::Otp { otp_type, issuer, account, secret, algorithm, digits, period, counter } => {
+                otp_text(*otp_type, issuer, account, secret, *algorithm, *digits, *period, *counter)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Formats
+// ============================================================================
+
+/// Web link with UTM and custom query parameters appended
+///
+/// Parameters go after any query the link already has and before its
+/// fragment. Names and values are percent-encoded.
+fn url_text(url: &str, utm: &[(&str, &String)], params: &[QueryParameter]) -> Result<String, String> {
+    // === Base Address ===
+    let url = url.trim();
+    if url.is_empty() {
+        return Err("URL is required".to_string());
+    }
+    if url.contains(char::is_whitespace) {
+        return Err("URL cannot contain spaces (write them as %20)".to_string());
+    }
+    // A "://" after the path, query or fragment starts (e.g. "?next=https://...")
+    // belongs to the link itself, not to a scheme
+    let has_scheme = url.find("://").is_some_and(|at| !url[..at].contains(['/', '?', '#']));
+    let url = if has_scheme { url.to_string() } else { format!("https://{}", url) };
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", &url));
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return Err(format!("Only http and https links are supported (found '{}://')", scheme));
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_name = host.rsplit_once(':').map_or(host, |(name, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) { name } else { host }
+    });
+    let valid_host = !host_name.is_empty()
+        && !host_name.starts_with(['.', '-'])
+        && host_name.chars().all(|c| c.is_alphanumeric() || matches!(c, '.' | '-'));
+    if !valid_host {
+        return Err(format!("'{}' is not a valid host name", host));
+    }
+
+    // === Query Parameters ===
+    let mut added = Vec::new();
+    for &(name, value) in utm {
+        if !value.trim().is_empty() {
+            added.push(format!("{}={}", name, percent_encode(value.trim())));
+        }
+    }
+    if !added.is_empty() && utm[0].1.trim().is_empty() {
+        return Err("utm_source is required when other UTM parameters are set".to_string());
+    }
+    for param in params {
+        let (name, value) = (param.name.trim(), param.value.trim());
+        match (name.is_empty(), value.is_empty()) {
+            (true, true) => {}
+            (true, false) => return Err(format!("Query parameter '{}' needs a name", value)),
+            (false, _) => added.push(format!("{}={}", percent_encode(name), percent_encode(value))),
+        }
+    }
+
+    let (address, fragment) = match url.split_once('#') {
+        Some((address, fragment)) => (address, format!("#{}", fragment)),
+        None => (url.as_str(), String::new()),
+    };
+    if added.is_empty() {
+        return Ok(format!("{}{}", address, fragment));
+    }
+    let separator = match address.split_once('?') {
+        Some((_, "")) => "",
+        Some(_) => "&",
+        None => "?",
+    };
+    Ok(format!("{}{}{}{}", address, separator, added.join("&"), fragment))
+}
+
+/// `WIFI:` network configuration (ZXing format, also read by iOS and Android)
+fn wifi_text(ssid: &str, password: &str, security: WifiSecurity, hidden: bool) -> Result<String, String> {
+    if ssid.is_empty() {
+        return Err("Network name (SSID) is required".to_string());
+    }
+    let escape = |value: &str| escape_with(value, &['\\', ';', ',', ':', '"']);
+
+    let mut text = match security {
+        WifiSecurity::Wpa => {
+            if !(8..=63).contains(&password.chars().count()) {
+                return Err("WPA passwords are 8-63 characters long".to_string());
+            }
+            format!("WIFI:T:WPA;S:{};P:{};", escape(ssid), escape(password))
+        }
+        WifiSecurity::Wep => {
+            if password.is_empty() {
+                return Err("WEP networks need a password".to_string());
+            }
+            format!("WIFI:T:WEP;S:{};P:{};", escape(ssid), escape(password))
+        }
+        WifiSecurity::Open => format!("WIFI:T:nopass;S:{};", escape(ssid)),
+    };
+    if hidden {
+        text.push_str("H:true;");
+    }
+    text.push(';');
+    Ok(text)
+}
+
+/// vCard 3.0 or 4.0 contact card, lines separated by CRLF
+fn vcard_text(contact: &Contact, version: VCardVersion) -> Result<String, String> {
+    let full_name = contact.full_name();
+    if full_name.is_empty() {
+        return Err("A name or organization is required".to_string());
+    }
+    let escape = |value: &str| escape_with(value.trim(), &['\\', ';', ',']).replace('\n', "\\n");
+
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        match version {
+            VCardVersion::V3 => "VERSION:3.0".to_string(),
+            VCardVersion::V4 => "VERSION:4.0".to_string(),
+        },
+        format!("N:{};{};;;", escape(&contact.last_name), escape(&contact.first_name)),
+        format!("FN:{}", escape(&full_name)),
+    ];
+    let mut push = |property: &str, value: &str| {
+        if !value.trim().is_empty() {
+            lines.push(format!("{}:{}", property, value));
+        }
+    };
+    push("ORG", &escape(&contact.organization));
+    push("TITLE", &escape(&contact.title));
+    if !contact.phone.trim().is_empty() {
+        match version {
+            VCardVersion::V3 => push("TEL;TYPE=CELL", &escape(&contact.phone)),
+            VCardVersion::V4 => push("TEL;VALUE=uri;TYPE=cell", &format!("tel:{}", phone_number(&contact.phone)?)),
+        }
+    }
+    push("EMAIL", &escape(&contact.email));
+    push("URL", contact.website.trim());
+    if contact.address().iter().any(|part| !part.is_empty()) {
+        let parts: Vec<String> = contact.address().iter().map(|part| escape(part)).collect();
+        let property = match version {
+            VCardVersion::V3 => "ADR;TYPE=WORK",
+            VCardVersion::V4 => "ADR;TYPE=work",
+        };
+        push(property, &format!(";;{}", parts.join(";")));
+    }
+    push("NOTE", &escape(&contact.note));
+    lines.push("END:VCARD".to_string());
+
+    Ok(lines.join("\r\n"))
+}
+
+/// `MECARD:` contact (NTT DoCoMo format; ORG as read by ZXing)
+fn mecard_text(contact: &Contact) -> Result<String, String> {
+    if contact.full_name().is_empty() {
+        return Err("A name or organization is required".to_string());
+    }
+    let escape = |value: &str| escape_with(value.trim(), &['\\', ';', ',', ':']).replace('\n', " ");
+
+    let name = match (contact.last_name.trim(), contact.first_name.trim()) {
+        ("", "") => escape(&contact.organization),
+        ("", first) => escape(first),
+        (last, "") => escape(last),
+        (last, first) => format!("{},{}", escape(last), escape(first)),
+    };
+    let mut text = format!("MECARD:N:{};", name);
+    let mut push = |property: &str, value: String| {
+        if !value.is_empty() {
+            text.push_str(&format!("{}:{};", property, value));
+        }
+    };
+    push("ORG", escape(&contact.organization));
+    push("TEL", escape(&contact.phone));
+    push("EMAIL", escape(&contact.email));
+    push("URL", escape(&contact.website));
+    if contact.address().iter().any(|part| !part.is_empty()) {
+        let parts: Vec<String> = contact.address().iter().map(|part| escape(part)).collect();
+        // PO box and extended address come first and stay empty
+        push("ADR", format!(",,{}", parts.join(",")));
+    }
+    push("NOTE", escape(&contact.note));
+    text.push(';');
+    Ok(text)
+}
+
+/// iCalendar `VEVENT` with floating (reader-local) times
+fn event_text(
+    summary: &str,
+    location: &str,
+    description: &str,
+    start: &str,
+    end: &str,
+    all_day: bool,
+) -> Result<String, String> {
+    if summary.trim().is_empty() {
+        return Err("Event title is required".to_string());
+    }
+    let start_at = parse_date_time(start, "Start", all_day)?;
+    let end_at = match end.trim() {
+        "" => None,
+        _ => Some(parse_date_time(end, "End", all_day)?),
+    };
+    if end_at.is_some_and(|end_at| end_at < start_at) {
+        return Err("The event ends before it starts".to_string());
+    }
+    let escape = |value: &str| escape_with(value.trim(), &['\\', ';', ',']).replace('\n', "\\n");
+
+    let mut lines = vec!["BEGIN:VEVENT".to_string(), format!("SUMMARY:{}", escape(summary))];
+    if all_day {
+        lines.push(format!("DTSTART;VALUE=DATE:{}", start_at.date()));
+        // The end date of an all-day event is exclusive
+        if let Some(end_at) = end_at {
+            lines.push(format!("DTEND;VALUE=DATE:{}", end_at.next_day().date()));
+        }
+    } else {
+        lines.push(format!("DTSTART:{}", start_at.date_time()));
+        if let Some(end_at) = end_at {
+            lines.push(format!("DTEND:{}", end_at.date_time()));
+        }
+    }
+    if !location.trim().is_empty() {
+        lines.push(format!("LOCATION:{}", escape(location)));
+    }
+    if !description.trim().is_empty() {
+        lines.push(format!("DESCRIPTION:{}", escape(description)));
+    }
+    lines.push("END:VEVENT".to_string());
+
+    Ok(lines.join("\r\n"))
+}
+
+// ============================================================================
+// Payments
+// ============================================================================
+
+/// Largest amount a SEPA transfer or Swiss QR-bill can carry, in cents
+const MAX_TRANSFER_CENTS: u64 = 99_999_999_999;
+
+/// Largest bitcoin amount (the 21 million coin supply), in satoshis
+const MAX_SATOSHIS: u64 = 21_000_000 * 100_000_000;
+
+/// Largest EPC payload in bytes (EPC069-12, section 3.2)
+const MAX_EPC_BYTES: usize = 331;
+
+/// Largest Swiss QR-bill payload in characters
+const MAX_SWISS_CHARACTERS: usize = 997;
+
+/// EPC069-12 version 002 SEPA credit transfer, lines separated by LF
+#[allow(clippy::too_many_arguments)]
+fn epc_text(
+    name: &str,
+    iban: &str,
+    bic: &str,
+    amount: &str,
+    purpose: &str,
+    reference: &str,
+    text: &str,
+    information: &str,
+) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Beneficiary name is required".to_string());
+    }
+    check_length("Beneficiary name", name, 70)?;
+    let iban = normalize_iban(iban)?;
+    let bic = bic.trim().to_uppercase();
+    if !bic.is_empty() && !is_bic(&bic) {
+        return Err(format!("'{}' is not a BIC (8 or 11 characters, e.g. DEUTDEFF)", bic));
+    }
+    let amount = match parse_amount(amount, "Amount", 2, MAX_TRANSFER_CENTS)? {
+        Some(cents) => format!("EUR{}", format_amount(cents, 2)),
+        None => String::new(),
+    };
+    let purpose = purpose.trim().to_uppercase();
+    let purpose_ok = purpose.len() == 4 && purpose.chars().all(|c| c.is_ascii_alphanumeric());
+    if !purpose.is_empty() && !purpose_ok {
+        return Err(format!("'{}' is not a purpose code (four letters, e.g. GDDS)", purpose));
+    }
+    let reference = match reference.trim() {
+        "" => String::new(),
+        reference => creditor_reference(reference)?,
+    };
+    let text = text.trim();
+    if !reference.is_empty() && !text.is_empty() {
+        return Err("Use either a creditor reference or a remittance text, not both".to_string());
+    }
+    check_length("Remittance text", text, 140)?;
+    let information = information.trim();
+    check_length("Note to payer", information, 70)?;
+
+    let lines = [
+        "BCD", "002", "1", "SCT", &bic, name, &iban, &amount, &purpose, &reference, text, information,
+    ];
+    // Trailing empty elements are left out
+    let used = lines.iter().rposition(|line| !line.is_empty()).map_or(0, |last| last + 1);
+    let payload = lines[..used].join("\n");
+    if payload.len() > MAX_EPC_BYTES {
+        return Err(format!(
+            "SEPA transfer data is {} bytes long (at most {} allowed); shorten the texts",
+            payload.len(), MAX_EPC_BYTES
+        ));
+    }
+    Ok(payload)
+}
+
+/// Swiss QR-bill (`SPC`, version 0200) with structured addresses, lines separated by CRLF
+fn swiss_qr_bill_text(
+    iban: &str,
+    creditor: &PostalAddress,
+    amount: &str,
+    currency: SwissCurrency,
+    debtor: &PostalAddress,
+    reference: &str,
+    message: &str,
+) -> Result<String, String> {
+    // === Account and Reference ===
+    let iban = normalize_iban(iban)?;
+    if !iban.starts_with("CH") && !iban.starts_with("LI") {
+        return Err("Swiss QR-bills need a Swiss (CH) or Liechtenstein (LI) IBAN".to_string());
+    }
+    // QR-IBANs have an institution ID of 30000-31999
+    let qr_iban = matches!(iban[4..9].parse::<u32>(), Ok(30000..=31999));
+    let compact: String = reference.chars().filter(|c| !c.is_whitespace()).collect();
+    let (reference_type, reference) = if compact.is_empty() {
+        ("NON", String::new())
+    } else if compact.chars().all(|c| c.is_ascii_digit()) {
+        ("QRR", qr_reference(&compact)?)
+    } else {
+        ("SCOR", creditor_reference(&compact)?)
+    };
+    match (qr_iban, reference_type) {
+        (true, "QRR") | (false, "SCOR" | "NON") => {}
+        (true, _) => return Err("A QR-IBAN needs a 27-digit QR reference".to_string()),
+        (false, _) => return Err("QR references need a QR-IBAN (institution ID 30000-31999)".to_string()),
+    }
+
+    // === Parties and Amount ===
+    let mut lines = vec!["SPC".to_string(), "0200".to_string(), "1".to_string(), iban];
+    lines.extend(swiss_address(creditor, "Creditor")?);
+    // Ultimate creditor: reserved for future use and left empty
+    lines.extend(std::iter::repeat_n(String::new(), 7));
+    lines.push(match parse_amount(amount, "Amount", 2, MAX_TRANSFER_CENTS)? {
+        Some(cents) => format_amount(cents, 2),
+        None => String::new(),
+    });
+    lines.push(currency.code().to_string());
+    if debtor.is_empty() {
+        lines.extend(std::iter::repeat_n(String::new(), 7));
+    } else {
+        lines.extend(swiss_address(debtor, "Debtor")?);
+    }
+
+    // === Payment Information ===
+    let message = message.trim();
+    check_length("Message", message, 140)?;
+    check_swiss_characters("Message", message)?;
+    lines.extend([reference_type.to_string(), reference, message.to_string(), "EPD".to_string()]);
+
+    let payload = lines.join("\r\n");
+    let characters = payload.chars().count();
+    if characters > MAX_SWISS_CHARACTERS {
+        return Err(format!(
+            "QR-bill data is {} characters long (at most {} allowed); shorten the texts",
+            characters, MAX_SWISS_CHARACTERS
+        ));
+    }
+    Ok(payload)
+}
+
+/// Address block of a Swiss QR-bill: type `S` followed by the six fields
+fn swiss_address(address: &PostalAddress, party: &str) -> Result<Vec<String>, String> {
+    let [name, street, building_number, postal_code, town, country] = address.fields();
+    for (label, value) in [("name", name), ("postal code", postal_code), ("town", town), ("country", country)] {
+        if value.is_empty() {
+            return Err(format!("{} {} is required", party, label));
+        }
+    }
+    let country = country.to_uppercase();
+    if !(country.len() == 2 && country.chars().all(|c| c.is_ascii_uppercase())) {
+        return Err(format!("{} country must be a two-letter code like CH (found '{}')", party, country));
+    }
+    let limits = [("name", name, 70), ("street", street, 70), ("building number", building_number, 16),
+        ("postal code", postal_code, 16), ("town", town, 35)];
+    for (label, value, limit) in limits {
+        check_length(&format!("{} {}", party, label), value, limit)?;
+        check_swiss_characters(&format!("{} {}", party, label), value)?;
+    }
+    Ok(["S", name, street, building_number, postal_code, town, &country].map(str::to_string).to_vec())
+}
+
+/// UPI payment intent; every value except the address is percent-encoded
+fn upi_text(vpa: &str, name: &str, amount: &str, note: &str, reference: &str) -> Result<String, String> {
+    let vpa = vpa.trim();
+    if vpa.is_empty() {
+        return Err("UPI ID (VPA) is required".to_string());
+    }
+    let valid = match vpa.split_once('@') {
+        Some((handle, provider)) => {
+            handle.len() >= 2
+                && handle.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+                && provider.len() >= 2
+                && provider.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(format!("'{}' is not a UPI ID (e.g. shop@upi)", vpa));
+    }
+    let reference = reference.trim();
+    if reference.len() > 35 || !reference.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Transaction reference must be up to 35 letters and digits".to_string());
+    }
+
+    let mut text = format!("upi://pay?pa={}", vpa);
+    if !name.trim().is_empty() {
+        text.push_str(&format!("&pn={}", percent_encode(name.trim())));
+    }
+    if let Some(paise) = parse_amount(amount, "Amount", 2, MAX_TRANSFER_CENTS)? {
+        text.push_str(&format!("&am={}", format_amount(paise, 2)));
+    }
+    text.push_str("&cu=INR");
+    if !note.trim().is_empty() {
+        text.push_str(&format!("&tn={}", percent_encode(note.trim())));
+    }
+    if !reference.is_empty() {
+        text.push_str(&format!("&tr={}", reference));
+    }
+    Ok(text)
+}
+
+/// BIP21 payment request
+fn bitcoin_text(address: &str, amount: &str, label: &str, message: &str) -> Result<String, String> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err("Bitcoin address is required".to_string());
+    }
+    check_bitcoin_address(address)?;
+
+    let mut query = Vec::new();
+    if let Some(satoshis) = parse_amount(amount, "Amount", 8, MAX_SATOSHIS)? {
+        let btc = format_amount(satoshis, 8);
+        query.push(format!("amount={}", btc.trim_end_matches('0').trim_end_matches('.')));
+    }
+    for (name, value) in [("label", label), ("message", message)] {
+        if !value.trim().is_empty() {
+            query.push(format!("{}={}", name, percent_encode(value.trim())));
+        }
+    }
+    if query.is_empty() {
+        Ok(format!("bitcoin:{}", address))
+    } else {
+        Ok(format!("bitcoin:{}?{}", address, query.join("&")))
+    }
+}
+
+// ============================================================================
+// Authenticators
+// ============================================================================
+
+/// Base32 alphabet of RFC 4648, used for authenticator secrets
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Bytes in a generated secret (160 bits, as RFC 4226 recommends)
+const GENERATED_SECRET_BYTES: usize = 20;
+
+/// Shortest accepted secret in Base32 characters (80 bits)
+const MIN_SECRET_CHARACTERS: usize = 16;
+
+/// Create a random Base32 authenticator secret
+///
+/// The secret comes from the operating system's secure random number
+/// generator and never leaves this machine except in the QR code.
+///
+/// # Returns
+/// * `Ok(String)` - 32 Base32 characters (160 bits)
+/// * `Err(String)` - Error message if no secure randomness is available
+pub fn generate_otp_secret() -> Result<String, String> {
+    let mut bytes = [0u8; GENERATED_SECRET_BYTES];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate a secret: {}", e))?;
+    Ok(base32_encode(&bytes))
+}
+
+/// Remove the secret from `otpauth://` URIs
+///
+/// Used when presets are written so an authenticator secret never lands
+/// in a file; any other text is returned unchanged.
+pub fn redact_otp_secret(text: &str) -> String {
+    let is_otp = text.get(..10).is_some_and(|scheme| scheme.eq_ignore_ascii_case("otpauth://"));
+    let Some((base, query)) = text.split_once('?').filter(|_| is_otp) else {
+        return text.to_string();
+    };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|parameter| {
+            let name = parameter.split('=').next().unwrap_or("");
+            !name.eq_ignore_ascii_case("secret")
+        })
+        .collect();
+    if kept.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, kept.join("&"))
+    }
+}
+
+/// `otpauth://` key URI for TOTP or HOTP authenticator apps
+#[allow(clippy::too_many_arguments)]
+fn otp_text(
+    otp_type: OtpType,
+    issuer: &str,
+    account: &str,
+    secret: &str,
+    algorithm: OtpAlgorithm,
+    digits: u8,
+    period: u32,
+    counter: u64,
+) -> Result<String, String> {
+    let (issuer, account) = (issuer.trim(), account.trim());
+    if account.is_empty() {
+        return Err("Account name is required".to_string());
+    }
+    if issuer.contains(':') || account.contains(':') {
+        return Err("Issuer and account name cannot contain ':'".to_string());
+    }
+    let secret = otp_secret(secret)?;
+    if digits != 6 && digits != 8 {
+        return Err(format!("Codes must have 6 or 8 digits (found {})", digits));
+    }
+
+    let label = if issuer.is_empty() {
+        percent_encode(account)
+    } else {
+        format!("{}:{}", percent_encode(issuer), percent_encode(account))
+    };
+    let scheme = match otp_type {
+        OtpType::Totp => "totp",
+        OtpType::Hotp => "hotp",
+    };
+    let mut text = format!("otpauth://{}/{}?secret={}", scheme, label, secret);
+    if !issuer.is_empty() {
+        text.push_str(&format!("&issuer={}", percent_encode(issuer)));
+    }
+    text.push_str(&format!("&algorithm={}&digits={}", algorithm.name(), digits));
+    match otp_type {
+        OtpType::Totp => {
+            if period == 0 {
+                return Err("The code period must be at least 1 second".to_string());
+            }
+            text.push_str(&format!("&period={}", period));
+        }
+        OtpType::Hotp => text.push_str(&format!("&counter={}", counter)),
+    }
+    Ok(text)
+}
+
+/// Base32 secret in upper case without spaces or padding, checked for
+/// its alphabet and length
+fn otp_secret(secret: &str) -> Result<String, String> {
+    let compact: String = secret
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .trim_end_matches('=')
+        .to_uppercase();
+    if compact.is_empty() {
+        return Err("Secret is required (enter it or generate one)".to_string());
+    }
+    if let Some(c) = compact.chars().find(|&c| !c.is_ascii_uppercase() && !('2'..='7').contains(&c)) {
+        return Err(format!("Secret must be Base32 (A-Z and 2-7), found '{}'", c));
+    }
+    // Lengths that leave a partial byte cannot come from Base32 encoding
+    if compact.len() < MIN_SECRET_CHARACTERS || matches!(compact.len() % 8, 1 | 3 | 6) {
+        return Err(format!(
+            "Secret must be at least {} Base32 characters long (80 bits)",
+            MIN_SECRET_CHARACTERS
+        ));
+    }
+    Ok(compact)
+}
+
+/// Base32 (RFC 4648) without padding
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        text.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    text
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Local date and time of a calendar event (ordered chronologically)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct DateTime {
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+impl DateTime {
+    /// iCalendar date, e.g. "20261105"
+    fn date(&self) -> String {
+        format!("{:04}{:02}{:02}", self.year, self.month, self.day)
+    }
+
+    /// iCalendar local date-time, e.g. "20261105T140000"
+    fn date_time(&self) -> String {
+        format!("{}T{:02}{:02}00", self.date(), self.hour, self.minute)
+    }
+
+    /// The following day at the same time
+    fn next_day(&self) -> Self {
+        let mut next = *self;
+        next.day += 1;
+        if next.day > days_in_month(next.year, next.month) {
+            next.day = 1;
+            next.month += 1;
+            if next.month > 12 {
+                next.month = 1;
+                next.year += 1;
+            }
+        }
+        next
+    }
+}
+
+/// Parse "YYYY-MM-DD" or "YYYY-MM-DD HH:MM" (a `T` may separate the time)
+///
+/// A time is required unless the event is all-day, where it is ignored.
+fn parse_date_time(value: &str, label: &str, all_day: bool) -> Result<DateTime, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("{} date is required", label));
+    }
+    let invalid = || {
+        if all_day {
+            format!("{} must be a date like 2026-11-05 (found '{}')", label, value)
+        } else {
+            format!("{} must be a date and time like 2026-11-05 14:00 (found '{}')", label, value)
+        }
+    };
+
+    let (date, time) = value.split_once([' ', 'T']).unwrap_or((value, ""));
+    let numbers = |text: &str, separator: char| -> Option<Vec<u32>> {
+        text.split(separator).map(|part| part.trim().parse().ok()).collect()
+    };
+    let Some([year, month, day]) = numbers(date, '-').and_then(|parts| <[u32; 3]>::try_from(parts).ok()) else {
+        return Err(invalid());
+    };
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return Err(invalid());
+    }
+
+    let (hour, minute) = match (all_day, time.trim()) {
+        (true, _) => (0, 0),
+        (false, "") => return Err(invalid()),
+        (false, time) => match numbers(time, ':').as_deref() {
+            Some(&[hour, minute]) if hour < 24 && minute < 60 => (hour, minute),
+            _ => return Err(invalid()),
+        },
+    };
+    Ok(DateTime { year, month, day, hour, minute })
+}
+
+/// Number of days in a month of the Gregorian calendar
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Phone number with spaces removed, checked for dialable characters
+fn phone_number(number: &str) -> Result<String, String> {
+    let compact: String = number.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err("Phone number is required".to_string());
+    }
+    let valid = compact.chars().enumerate().all(|(i, c)| {
+        c.is_ascii_digit() || matches!(c, '-' | '.' | '(' | ')') || (c == '+' && i == 0)
+    });
+    if !valid || !compact.chars().any(|c| c.is_ascii_digit()) {
+        return Err(format!("'{}' is not a phone number", number.trim()));
+    }
+    Ok(compact)
+}
+
+/// Coordinate in degrees with at most six decimals (about 10 cm)
+fn degrees(value: f64) -> String {
+    let text = format!("{:.6}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+/// Parse an amount like "1250" or "12.50" into minor units
+///
+/// # Arguments
+/// * `value` - Amount as typed, with a dot before the decimals
+/// * `label` - Field name for error messages
+/// * `decimals` - Number of decimals of the currency
+/// * `max` - Largest allowed amount in minor units
+///
+/// # Returns
+/// * `Ok(None)` - The field is empty
+/// * `Ok(Some(units))` - Amount in minor units (cents, satoshis, ...)
+/// * `Err(String)` - Error message if the amount is malformed, zero or too large
+fn parse_amount(value: &str, label: &str, decimals: u32, max: u64) -> Result<Option<u64>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || format!("{} must be a number with up to {} decimals like 12.50 (found '{}')", label, decimals, value);
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if whole.is_empty() || !digits(whole) || !digits(fraction) || fraction.len() > decimals as usize {
+        return Err(invalid());
+    }
+    let scale = 10u64.pow(decimals);
+    let fraction_units = format!("{:0<width$}", fraction, width = decimals as usize).parse::<u64>().unwrap_or(0);
+    let units = whole
+        .parse::<u64>()
+        .ok()
+        .and_then(|whole| whole.checked_mul(scale))
+        .and_then(|units| units.checked_add(fraction_units))
+        .filter(|&units| units <= max)
+        .ok_or_else(|| format!("{} is too large (at most {})", label, format_amount(max, decimals)))?;
+    if units == 0 {
+        return Err(format!("{} must be greater than zero", label));
+    }
+    Ok(Some(units))
+}
+
+/// Minor units as a decimal amount, e.g. 1250 cents as "12.50"
+fn format_amount(units: u64, decimals: u32) -> String {
+    let scale = 10u64.pow(decimals);
+    format!("{}.{:0width$}", units / scale, units % scale, width = decimals as usize)
+}
+
+/// IBAN without spaces in upper case, checked with its ISO 13616 check digits
+fn normalize_iban(iban: &str) -> Result<String, String> {
+    let compact: String = iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    if compact.is_empty() {
+        return Err("IBAN is required".to_string());
+    }
+    // ASCII is checked first so the byte slices below fall on characters
+    let well_formed = compact.chars().all(|c| c.is_ascii_alphanumeric())
+        && (15..=34).contains(&compact.len())
+        && compact[..2].chars().all(|c| c.is_ascii_uppercase())
+        && compact[2..4].chars().all(|c| c.is_ascii_digit());
+    if !well_formed {
+        return Err(format!("'{}' is not an IBAN", iban.trim()));
+    }
+    let rearranged = format!("{}{}", &compact[4..], &compact[..4]);
+    if mod_97(&rearranged) != 1 {
+        return Err(format!("IBAN '{}' has wrong check digits (check for typos)", iban.trim()));
+    }
+    Ok(compact)
+}
+
+/// ISO 11649 creditor reference ("RF" + check digits + up to 21 characters)
+fn creditor_reference(reference: &str) -> Result<String, String> {
+    let compact: String = reference.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    let well_formed = compact.chars().all(|c| c.is_ascii_alphanumeric())
+        && compact.len() >= 5
+        && compact.len() <= 25
+        && compact.starts_with("RF")
+        && compact[2..4].chars().all(|c| c.is_ascii_digit());
+    if !well_formed {
+        return Err(format!("'{}' is not a creditor reference (RF followed by up to 23 characters)", reference));
+    }
+    let rearranged = format!("{}{}", &compact[4..], &compact[..4]);
+    if mod_97(&rearranged) != 1 {
+        return Err(format!("Creditor reference '{}' has wrong check digits", reference));
+    }
+    Ok(compact)
+}
+
+/// Swiss QR reference: 27 digits, the last a modulo 10 recursive check digit
+fn qr_reference(reference: &str) -> Result<String, String> {
+    const TABLE: [u32; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+    if reference.len() != 27 || !reference.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("'{}' is not a QR reference (27 digits)", reference));
+    }
+    let digits: Vec<u32> = reference.chars().filter_map(|c| c.to_digit(10)).collect();
+    let carry = digits[..26].iter().fold(0, |carry, &digit| TABLE[((carry + digit) % 10) as usize]);
+    if (10 - carry) % 10 != digits[26] {
+        return Err(format!("QR reference '{}' has a wrong check digit", reference));
+    }
+    Ok(reference.to_string())
+}
+
+/// Remainder modulo 97 of an alphanumeric string with letters counted as 10-35
+fn mod_97(text: &str) -> u32 {
+    text.chars().fold(0, |remainder, c| {
+        let value = c.to_digit(36).unwrap_or(0);
+        if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        }
+    })
+}
+
+/// BIC (ISO 9362): bank, country and location code, optional branch code
+fn is_bic(bic: &str) -> bool {
+    bic.is_ascii()
+        && (bic.len() == 8 || bic.len() == 11)
+        && bic[..6].chars().all(|c| c.is_ascii_uppercase())
+        && bic[6..].chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Reject values longer than a format allows
+fn check_length(label: &str, value: &str, limit: usize) -> Result<(), String> {
+    let length = value.chars().count();
+    if length > limit {
+        return Err(format!("{} is {} characters long (at most {} allowed)", label, length, limit));
+    }
+    Ok(())
+}
+
+/// Reject characters outside the Latin set Swiss QR-bills allow
+/// (printable ASCII, Latin-1 Supplement, Latin Extended-A, Ș/ș/Ț/ț and €)
+fn check_swiss_characters(label: &str, value: &str) -> Result<(), String> {
+    let allowed = |c: char| {
+        matches!(c, ' '..='~' | '\u{A0}'..='\u{17F}' | '\u{218}'..='\u{21B}' | '€')
+    };
+    match value.chars().find(|&c| !allowed(c)) {
+        Some(c) => Err(format!("{} contains '{}', which Swiss QR-bills do not allow", label, c)),
+        None => Ok(()),
+    }
+}
+
+/// Check the form of a Bitcoin address
+///
+/// Bech32 and Bech32m addresses (`bc1...`, `tb1...`) are verified with
+/// their checksum. Legacy Base58 addresses (`1...`, `3...`, testnet `m`,
+/// `n`, `2`) are only checked for their alphabet and length, since their
+/// checksum needs SHA-256.
+fn check_bitcoin_address(address: &str) -> Result<(), String> {
+    let invalid = || format!("'{}' is not a Bitcoin address", address);
+    let lower = address.to_lowercase();
+    if lower.starts_with("bc1") || lower.starts_with("tb1") {
+        // Mixed case is not allowed
+        if address != lower && address != address.to_uppercase() {
+            return Err(invalid());
+        }
+        if !(14..=90).contains(&lower.len()) || !bech32_checksum_ok(&lower) {
+            return Err(format!("'{}' is not a valid Bitcoin address (check for typos)", address));
+        }
+        return Ok(());
+    }
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let legacy = address.starts_with(['1', '3', 'm', 'n', '2'])
+        && (26..=35).contains(&address.len())
+        && address.chars().all(|c| BASE58.contains(c));
+    if !legacy {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// Verify the checksum of a lowercase Bech32 (BIP173) or Bech32m (BIP350) string
+fn bech32_checksum_ok(text: &str) -> bool {
+    const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let Some((hrp, data)) = text.rsplit_once('1') else {
+        return false;
+    };
+    let Some(values) = data.chars().map(|c| CHARSET.find(c).map(|v| v as u32)).collect::<Option<Vec<u32>>>() else {
+        return false;
+    };
+    if values.len() < 7 {
+        return false;
+    }
+    let expanded = hrp.bytes().map(|b| (b >> 5) as u32).chain([0]).chain(hrp.bytes().map(|b| (b & 31) as u32));
+    let checksum = expanded.chain(values.iter().copied()).fold(1u32, |chk, value| {
+        let top = chk >> 25;
+        let mut chk = ((chk & 0x1ffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+        chk
+    });
+    // Witness version 0 uses Bech32, later versions Bech32m
+    match values[0] {
+        0 => checksum == 1,
+        _ => checksum == 0x2bc830a3,
+    }
+}
+
+/// Prefix every reserved character with a backslash
+fn escape_with(value: &str, reserved: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if reserved.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    // Line breaks in mailto bodies are CRLF (RFC 6068)
+    let value = value.replace("\r\n", "\n").replace('\n', "\r\n");
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact() -> Contact {
+        Contact {
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+            organization: "Analytical Engines, Ltd.".to_string(),
+            phone: "+44 20 7946 0958".to_string(),
+            email: "ada@example.com".to_string(),
+            city: "London".to_string(),
+            note: "Met at the expo;\nfollow up".to_string(),
+            ..Contact::default()
+        }
+    }
+
+    #[test]
+    fn test_url_with_campaign_parameters() {
+        let url = |url: &str, source: &str, campaign: &str| Payload::Url {
+            url: url.to_string(),
+            source: source.to_string(),
+            medium: String::new(),
+            campaign: campaign.to_string(),
+            term: String::new(),
+            content: String::new(),
+            params: vec![QueryParameter { name: "lang".to_string(), value: "de-CH".to_string() }],
+        };
+        assert_eq!(
+            url("shop.example.com/sale", "newsletter", "Spring 2026").to_text().unwrap(),
+            "https://shop.example.com/sale?utm_source=newsletter&utm_campaign=Spring%202026&lang=de-CH"
+        );
+        // Parameters join an existing query and stay in front of the fragment
+        assert_eq!(
+            url("http://example.com:8080/p?id=7#reviews", "qr", "").to_text().unwrap(),
+            "http://example.com:8080/p?id=7&utm_source=qr&lang=de-CH#reviews"
+        );
+        // A link inside the query is not taken for the scheme
+        assert_eq!(
+            url("example.com/?next=https://x", "qr", "").to_text().unwrap(),
+            "https://example.com/?next=https://x&utm_source=qr&lang=de-CH"
+        );
+
+        assert!(url("", "qr", "").to_text().unwrap_err().contains("required"));
+        assert!(url("example.com/a b", "", "").to_text().unwrap_err().contains("spaces"));
+        assert!(url("ftp://example.com", "", "").to_text().unwrap_err().contains("http"));
+        assert!(url("https://exa_mple.com", "", "").to_text().is_err());
+        assert!(url("example.com", "", "launch").to_text().unwrap_err().contains("utm_source"));
+    }
+
+    #[test]
+    fn test_wifi_escaping() {
+        let wifi = Payload::Wifi {
+            ssid: "Café;Guest".to_string(),
+            password: r#"p@ss:wo\rd,"x""#.to_string(),
+            security: WifiSecurity::Wpa,
+            hidden: true,
+        };
+        assert_eq!(wifi.to_text().unwrap(), r#"WIFI:T:WPA;S:Café\;Guest;P:p@ss\:wo\\rd\,\"x\";H:true;;"#);
+
+        let open = Payload::Wifi { ssid: "Lobby".to_string(), password: "ignored".to_string(), security: WifiSecurity::Open, hidden: false };
+        assert_eq!(open.to_text().unwrap(), "WIFI:T:nopass;S:Lobby;;");
+
+        let short = Payload::Wifi { ssid: "Lobby".to_string(), password: "short".to_string(), security: WifiSecurity::Wpa, hidden: false };
+        assert!(short.to_text().is_err());
+    }
+
+    #[test]
+    fn test_contact_cards() {
+        let v3 = Payload::VCard { version: VCardVersion::V3, contact: contact() }.to_text().unwrap();
+        assert_eq!(
+            v3,
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Lovelace;Ada;;;\r\nFN:Ada Lovelace\r\nORG:Analytical Engines\\, Ltd.\r\n\
+             TEL;TYPE=CELL:+44 20 7946 0958\r\nEMAIL:ada@example.com\r\nADR;TYPE=WORK:;;;London;;;\r\n\
+             NOTE:Met at the expo\\;\\nfollow up\r\nEND:VCARD"
+        );
+        let v4 = Payload::VCard { version: VCardVersion::V4, contact: contact() }.to_text().unwrap();
+        assert!(v4.contains("VERSION:4.0\r\n") && v4.contains("TEL;VALUE=uri;TYPE=cell:tel:+442079460958\r\n"));
+
+        let mecard = Payload::MeCard { contact: contact() }.to_text().unwrap();
+        assert_eq!(
+            mecard,
+            "MECARD:N:Lovelace,Ada;ORG:Analytical Engines\\, Ltd.;TEL:+44 20 7946 0958;EMAIL:ada@example.com;\
+             ADR:,,,London,,,;NOTE:Met at the expo\\; follow up;;"
+        );
+
+        assert!(Payload::MeCard { contact: Contact::default() }.to_text().is_err());
+        let converted = Payload::MeCard { contact: contact() }.converted(PayloadKind::VCard);
+        assert_eq!(converted, Payload::VCard { version: VCardVersion::V3, contact: contact() });
+    }
+
+    #[test]
+    fn test_calendar_events() {
+        let event = |start: &str, end: &str, all_day: bool| Payload::Event {
+            summary: "Launch, day 1".to_string(),
+            location: "Hall B".to_string(),
+            description: String::new(),
+            start: start.to_string(),
+            end: end.to_string(),
+            all_day,
+        };
+        assert_eq!(
+            event("2026-11-05 14:00", "2026-11-05T15:30", false).to_text().unwrap(),
+            "BEGIN:VEVENT\r\nSUMMARY:Launch\\, day 1\r\nDTSTART:20261105T140000\r\nDTEND:20261105T153000\r\n\
+             LOCATION:Hall B\r\nEND:VEVENT"
+        );
+        // All-day end dates are exclusive, so the last day rolls over
+        let all_day = event("2028-02-28", "2028-02-29", true).to_text().unwrap();
+        assert!(all_day.contains("DTSTART;VALUE=DATE:20280228\r\nDTEND;VALUE=DATE:20280301\r\n"));
+
+        assert!(event("2026-11-05", "", false).to_text().unwrap_err().contains("date and time"));
+        assert!(event("2027-02-29", "", true).to_text().is_err());
+        assert!(event("2026-11-05 14:00", "2026-11-05 13:00", false).to_text().unwrap_err().contains("before"));
+    }
+
+    #[test]
+    fn test_uri_payloads() {
+        let geo = Payload::Geo { latitude: 51.500729, longitude: -0.124625 };
+        assert_eq!(geo.to_text().unwrap(), "geo:51.500729,-0.124625");
+        assert_eq!(Payload::Geo { latitude: 48.0, longitude: 2.5 }.to_text().unwrap(), "geo:48,2.5");
+        assert!(Payload::Geo { latitude: 91.0, longitude: 0.0 }.to_text().is_err());
+
+        let sms = Payload::Sms { number: "+1 555 0100".to_string(), message: "Code: 42".to_string() };
+        assert_eq!(sms.to_text().unwrap(), "SMSTO:+15550100:Code: 42");
+        assert_eq!(Payload::Phone { number: "(555) 0100".to_string() }.to_text().unwrap(), "tel:(555)0100");
+        assert!(Payload::Phone { number: "call me".to_string() }.to_text().is_err());
+
+        let email = Payload::Email {
+            to: "sales@example.com".to_string(),
+            subject: "Quote & samples".to_string(),
+            body: "Hi,\nplease send".to_string(),
+        };
+        assert_eq!(
+            email.to_text().unwrap(),
+            "mailto:sales@example.com?subject=Quote%20%26%20samples&body=Hi%2C%0D%0Aplease%20send"
+        );
+        assert!(Payload::Email { to: "sales".to_string(), subject: String::new(), body: String::new() }.to_text().is_err());
+    }
+
+    #[test]
+    fn test_sepa_transfer() {
+        let transfer = |iban: &str, amount: &str, reference: &str, text: &str| Payload::Epc {
+            name: "Red Cross of Belgium".to_string(),
+            iban: iban.to_string(),
+            bic: "bpotbeb1".to_string(),
+            amount: amount.to_string(),
+            purpose: "CHAR".to_string(),
+            reference: reference.to_string(),
+            text: text.to_string(),
+            information: String::new(),
+        };
+        assert_eq!(
+            transfer("BE72 0000 0000 1616", "1", "", "Urgency fund").to_text().unwrap(),
+            "BCD\n002\n1\nSCT\nBPOTBEB1\nRed Cross of Belgium\nBE72000000001616\nEUR1.00\nCHAR\n\nUrgency fund"
+        );
+        let referenced = transfer("DE89370400440532013000", "", "rf18 5390 0754 7034", "").to_text().unwrap();
+        assert!(referenced.ends_with("DE89370400440532013000\n\nCHAR\nRF18539007547034"));
+
+        assert!(transfer("DE89 3704 0044 0532 0130 01", "1", "", "").to_text().unwrap_err().contains("check digits"));
+        assert!(transfer("BE72000000001616", "12.345", "", "").to_text().unwrap_err().contains("2 decimals"));
+        assert!(transfer("BE72000000001616", "0.00", "", "").to_text().unwrap_err().contains("greater than zero"));
+        assert!(transfer("BE72000000001616", "1", "RF19 5390 0754 7034", "").to_text().is_err());
+        assert!(transfer("BE72000000001616", "1", "RF18539007547034", "Invoice 7").to_text().unwrap_err().contains("not both"));
+    }
+
+    #[test]
+    fn test_swiss_qr_bill() {
+        let bill = |iban: &str, reference: &str| Payload::SwissQrBill {
+            iban: iban.to_string(),
+            creditor: PostalAddress {
+                name: "Robert Schneider AG".to_string(),
+                street: "Rue du Lac".to_string(),
+                building_number: "1268".to_string(),
+                postal_code: "2501".to_string(),
+                town: "Biel".to_string(),
+                country: "ch".to_string(),
+            },
+            amount: "1949.75".to_string(),
+            currency: SwissCurrency::Chf,
+            debtor: PostalAddress::default(),
+            reference: reference.to_string(),
+            message: "Order of 15 June 2020".to_string(),
+        };
+        let text = bill("CH44 3199 9123 0008 8901 2", "21 00000 00003 13947 14300 09017").to_text().unwrap();
+        let lines: Vec<&str> = text.split("\r\n").collect();
+        assert_eq!(lines.len(), 31);
+        assert_eq!(&lines[..11], ["SPC", "0200", "1", "CH4431999123000889012", "S", "Robert Schneider AG",
+            "Rue du Lac", "1268", "2501", "Biel", "CH"]);
+        assert!(lines[11..18].iter().all(|line| line.is_empty()));
+        assert_eq!(&lines[18..20], ["1949.75", "CHF"]);
+        assert!(lines[20..27].iter().all(|line| line.is_empty()));
+        assert_eq!(&lines[27..], ["QRR", "210000000003139471430009017", "Order of 15 June 2020", "EPD"]);
+
+        // Ordinary IBANs take creditor references or none, QR-IBANs only QR references
+        assert!(bill("CH56 0483 5012 3456 7800 9", "RF18 5390 0754 7034").to_text().unwrap().contains("\r\nSCOR\r\nRF18539007547034\r\n"));
+        assert!(bill("CH56 0483 5012 3456 7800 9", "").to_text().unwrap().contains("\r\nNON\r\n\r\n"));
+        assert!(bill("CH56 0483 5012 3456 7800 9", "210000000003139471430009017").to_text().unwrap_err().contains("QR-IBAN"));
+        assert!(bill("CH44 3199 9123 0008 8901 2", "").to_text().unwrap_err().contains("27-digit"));
+        assert!(bill("CH44 3199 9123 0008 8901 2", "210000000003139471430009018").to_text().unwrap_err().contains("check digit"));
+        assert!(bill("DE89370400440532013000", "").to_text().unwrap_err().contains("Liechtenstein"));
+        assert_eq!(bill("CH4431999123000889012", "").required_ec_level(), Some(ErrorCorrectionLevel::Medium));
+    }
+
+    #[test]
+    fn test_non_ascii_account_details() {
+        let transfer = |iban: &str, bic: &str, reference: &str| Payload::Epc {
+            name: "Red Cross of Belgium".to_string(),
+            iban: iban.to_string(),
+            bic: bic.to_string(),
+            amount: "1".to_string(),
+            purpose: String::new(),
+            reference: reference.to_string(),
+            text: String::new(),
+            information: String::new(),
+        };
+        // Multi-byte characters are rejected instead of splitting a byte slice
+        assert!(transfer("DÉ89370400440532013000", "", "").to_text().is_err());
+        assert!(transfer("€€€€€", "", "").to_text().is_err());
+        assert!(transfer("BE72000000001616", "a€€b", "").to_text().is_err());
+        assert!(transfer("BE72000000001616", "", "RF€1").to_text().is_err());
+
+        let bill = Payload::SwissQrBill {
+            iban: "CH56 0483 5012 3456 7800 9".to_string(),
+            creditor: PostalAddress {
+                name: "Robert Schneider AG".to_string(),
+                postal_code: "2501".to_string(),
+                town: "Biel".to_string(),
+                country: "CH".to_string(),
+                ..PostalAddress::default()
+            },
+            amount: String::new(),
+            currency: SwissCurrency::Chf,
+            debtor: PostalAddress::default(),
+            reference: "RF€1".to_string(),
+            message: String::new(),
+        };
+        assert!(bill.to_text().unwrap_err().contains("creditor reference"));
+    }
+
+    #[test]
+    fn test_upi_and_bitcoin() {
+        let upi = Payload::Upi {
+            vpa: "corner.shop@okaxis".to_string(),
+            name: "Corner Shop".to_string(),
+            amount: "250".to_string(),
+            note: "Order #42".to_string(),
+            reference: "INV42".to_string(),
+        };
+        assert_eq!(
+            upi.to_text().unwrap(),
+            "upi://pay?pa=corner.shop@okaxis&pn=Corner%20Shop&am=250.00&cu=INR&tn=Order%20%2342&tr=INV42"
+        );
+        let mut no_handle = upi.clone();
+        if let Payload::Upi { vpa, .. } = &mut no_handle {
+            *vpa = "corner.shop".to_string();
+        }
+        assert!(no_handle.to_text().unwrap_err().contains("UPI ID"));
+
+        let bitcoin = |address: &str, amount: &str| Payload::Bitcoin {
+            address: address.to_string(),
+            amount: amount.to_string(),
+            label: "Donation".to_string(),
+            message: String::new(),
+        };
+        assert_eq!(
+            bitcoin("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", "0.00150000").to_text().unwrap(),
+            "bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq?amount=0.0015&label=Donation"
+        );
+        assert!(bitcoin("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0", "").to_text().is_ok());
+        assert!(bitcoin("1BoatSLRHtKNngkdXEeobR76b53LETtpyT", "1").to_text().is_ok());
+        // One character off fails the Bech32 checksum
+        assert!(bitcoin("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdr", "").to_text().is_err());
+        assert!(bitcoin("1BoatSLRHtKNngkdXEeobR76b53LETtpyT", "0.000000001").to_text().is_err());
+    }
+
+    #[test]
+    fn test_authenticator_uris() {
+        let otp = |otp_type: OtpType, secret: &str| Payload::Otp {
+            otp_type,
+            issuer: "Acme Co".to_string(),
+            account: "ada@example.com".to_string(),
+            secret: secret.to_string(),
+            algorithm: OtpAlgorithm::Sha256,
+            digits: 6,
+            period: 30,
+            counter: 7,
+        };
+        assert_eq!(
+            otp(OtpType::Totp, "jbsw y3dp ehpk 3pxp").to_text().unwrap(),
+            "otpauth://totp/Acme%20Co:ada%40example.com?secret=JBSWY3DPEHPK3PXP&issuer=Acme%20Co\
+             &algorithm=SHA256&digits=6&period=30"
+        );
+        assert!(otp(OtpType::Hotp, "JBSWY3DPEHPK3PXP").to_text().unwrap().ends_with("&digits=6&counter=7"));
+
+        assert!(otp(OtpType::Totp, "").to_text().unwrap_err().contains("generate"));
+        assert!(otp(OtpType::Totp, "JBSWY3DPEHPK3PX1").to_text().unwrap_err().contains("'1'"));
+        assert!(otp(OtpType::Totp, "JBSWY3DP").to_text().is_err());
+
+        let secret = generate_otp_secret().unwrap();
+        assert_eq!(secret.len(), 32);
+        assert!(otp_secret(&secret).is_ok());
+        assert_ne!(secret, generate_otp_secret().unwrap());
+        assert_eq!(base32_encode(b"Hello!"), "JBSWY3DPEE");
+    }
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        assert_eq!(
+            redact_otp_secret("otpauth://totp/Acme:ada?secret=JBSWY3DPEHPK3PXP&issuer=Acme"),
+            "otpauth://totp/Acme:ada?issuer=Acme"
+        );
+        assert_eq!(redact_otp_secret("OTPAUTH://hotp/x?SECRET=ABC"), "OTPAUTH://hotp/x");
+        assert_eq!(redact_otp_secret("https://example.com/?secret=1"), "https://example.com/?secret=1");
+
+        let mut otp = Payload::new(PayloadKind::Otp);
+        if let Payload::Otp { account, secret, .. } = &mut otp {
+            *account = "ada".to_string();
+            *secret = "JBSWY3DPEHPK3PXP".to_string();
+        }
+        let json = serde_json::to_string(&otp).unwrap();
+        assert!(!json.contains("JBSWY3DP") && !json.contains("secret"));
+        let loaded: Payload = serde_json::from_str(&json).unwrap();
+        assert!(loaded.to_text().unwrap_err().contains("Secret is required"));
+    }
+
+    #[test]
+    fn test_payload_round_trips_through_json() {
+        let payload = Payload::VCard { version: VCardVersion::V4, contact: contact() };
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(json.starts_with(r#"{"type":"VCard","version":"V4","contact":{"first_name":"Ada""#));
+        assert_eq!(serde_json::from_str::<Payload>(&json).unwrap(), payload);
+    }
+}
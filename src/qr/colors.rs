@@ -5,7 +5,7 @@
 
 use image::Rgba;
 
-use crate::app::QrCodeApp;
+use crate::qr::spec::QrSpec;
 use crate::types::GradientType;
 
 /// Calculate gradient color based on pixel position
@@ -16,7 +16,7 @@ use crate::types::GradientType;
 /// # Arguments
 /// * `x`, `y` - Pixel coordinates in the image
 /// * `width`, `height` - Total image dimensions
-/// * `app` - Application state containing gradient settings
+/// * `spec` - Rendering specification containing gradient settings
 ///
 /// # Returns
 /// RGBA color interpolated based on position and gradient type
@@ -31,10 +31,10 @@ pub fn get_gradient_color(
     y: u32, 
     width: u32, 
     height: u32, 
-    app: &QrCodeApp
+    spec: &QrSpec
 ) -> Rgba<u8> {
     // Calculate interpolation factor (0.0 to 1.0)
-    let t = match app.gradient_type {
+    let t = match spec.gradient_type {
         GradientType::Horizontal => {
             // Progress from left (0.0) to right (1.0)
             x as f32 / width as f32
@@ -61,8 +61,8 @@ pub fn get_gradient_color(
 
     // Linearly interpolate between the two colors
     interpolate_rgb(
-        app.fg_color, 
-        app.gradient_color, 
+        spec.fg_color, 
+        spec.gradient_color, 
         t
     )
 }
@@ -80,7 +80,7 @@ pub fn get_gradient_color(
 /// Interpolated RGBA color (fully opaque)
 ///
 /// # Examples
-/// ```
+/// ```ignore
 /// // Get the midpoint color between black and white
 /// let mid = interpolate_rgb([0, 0, 0], [255, 255, 255], 0.5);
 /// // Result: Rgba([127, 127, 127, 255])
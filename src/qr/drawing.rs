@@ -5,116 +5,158 @@
 //! - Circle (smooth, modern)
 //! - Rounded Square (soft edges)
 //! - Dots (minimalist)
+//! - Liquid, Vertical Lines and Horizontal Lines (connected to neighbors)
 //!
-//! Also handles special eye (finder pattern) styling.
+//! Also handles special eye (finder pattern) styling: each eye's frame and
+//! pupil are filled as whole paths (see `eyes`). Timing and alignment
+//! patterns have their own styles (solid by default).
+//!
+//! Curved shapes are anti-aliased: each pixel on a shape's edge is blended
+//! with the existing image by the fraction of its sub-pixel samples that
+//! fall inside the shape (see `Antialiasing`).
 
 use image::{Rgba, RgbaImage};
 
-use crate::app::QrCodeApp;
-use crate::types::{ModuleStyle, EyeStyle};
+use crate::qr::eyes;
+use crate::qr::matrix::{Neighbors, QrMatrix};
+use crate::qr::path::{Path, Point};
+use crate::qr::spec::QrSpec;
+use crate::types::{Antialiasing, EyePosition, ModuleStyle, PatternStyle};
 use crate::qr::colors;
 
 /// Draw a data module (non-eye module) with the selected style
 ///
 /// Applies the current module style setting and gradient colors if enabled.
+/// Connected styles (liquid, lines) look at the neighboring modules.
 ///
 /// # Arguments
 /// * `image` - Image buffer to draw into
-/// * `app` - Application state for style settings
+/// * `spec` - Rendering specification for style settings
+/// * `matrix` - QR matrix, for neighbor lookups
 /// * `x`, `y` - Module coordinates in QR matrix
 /// * `px`, `py` - Pixel coordinates in image
 /// * `size` - Size of module in pixels
+#[allow(clippy::too_many_arguments)]
 pub fn draw_data_module(
     image: &mut RgbaImage, 
-    app: &QrCodeApp, 
-    _x: usize, 
-    _y: usize, 
+    spec: &QrSpec, 
+    matrix: &QrMatrix,
+    x: usize, 
+    y: usize, 
     px: u32, 
     py: u32, 
     size: u32
 ) {
-    // Determine module color (gradient or solid)
-    let color = if app.use_gradient {
-        colors::get_gradient_color(px, py, image.width(), image.height(), app)
-    } else {
-        Rgba([app.fg_color[0], app.fg_color[1], app.fg_color[2], 255])
-    };
+    let color = module_color(image, spec, px, py);
 
     // Draw module with selected style
-    match app.module_style {
+    match spec.module_style {
         ModuleStyle::Square => {
             draw_square(image, px, py, size, color);
         }
         ModuleStyle::Circle => {
-            draw_circle(image, px, py, size, color);
+            draw_circle(image, px, py, size, color, spec.antialiasing);
         }
         ModuleStyle::RoundedSquare => {
-            draw_rounded_square(image, px, py, size, color, app);
+            draw_rounded_square(image, px, py, size, color, spec);
         }
         ModuleStyle::Dots => {
-            draw_dot(image, px, py, size, color);
+            draw_dot(image, px, py, size, color, spec.antialiasing);
+        }
+        ModuleStyle::Liquid => {
+            let neighbors = matrix.neighbors(x, y);
+            draw_liquid(image, px, py, size, color, neighbors, spec.antialiasing);
+        }
+        ModuleStyle::VerticalLines | ModuleStyle::HorizontalLines => {
+            let neighbors = matrix.neighbors(x, y);
+            let vertical = spec.module_style == ModuleStyle::VerticalLines;
+            draw_line_segment(image, px, py, size, color, neighbors, vertical, spec.antialiasing);
         }
     }
 }
 
-/// Draw an eye module (finder pattern) with the selected style
+/// Draw a timing pattern module with the timing pattern style
 ///
-/// Finder patterns are the three 7x7 squares in the QR code corners.
-/// Can have different styles and colors from data modules.
+/// `PatternStyle::MatchModules` is drawn with `draw_data_module` instead.
 ///
 /// # Arguments
 /// * `image` - Image buffer to draw into
-/// * `app` - Application state for style settings
-/// * `x`, `y` - Module coordinates in QR matrix
+/// * `spec` - Rendering specification for style settings
 /// * `px`, `py` - Pixel coordinates in image
 /// * `size` - Size of module in pixels
-/// * `eye_positions` - List of eye starting positions to determine which eye
-pub fn draw_eye_module(
-    image: &mut RgbaImage, 
-    app: &QrCodeApp, 
-    x: usize, 
-    y: usize, 
-    px: u32, 
+pub fn draw_timing_module(image: &mut RgbaImage, spec: &QrSpec, px: u32, py: u32, size: u32) {
+    let color = module_color(image, spec, px, py);
+
+    match spec.timing_style {
+        PatternStyle::Solid | PatternStyle::MatchModules => draw_square(image, px, py, size, color),
+        PatternStyle::Rounded => {
+            fill_rounded_square(image, px, py, size, color, size / 4, spec.antialiasing);
+        }
+        PatternStyle::Circle => draw_circle(image, px, py, size, color, spec.antialiasing),
+    }
+}
+
+/// Draw a whole 5x5 alignment pattern with the alignment pattern style
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `spec` - Rendering specification for style settings
+/// * `px`, `py` - Pixel coordinates of the pattern's top-left module
+/// * `size` - Size of one module in pixels
+pub fn draw_alignment(image: &mut RgbaImage, spec: &QrSpec, px: u32, py: u32, size: u32) {
+    // Gradient color at the pattern center, like eyes
+    let center = |p: u32| p + size * 5 / 2;
+    let color = module_color(image, spec, center(px), center(py));
+
+    let path = eyes::alignment_path(spec.alignment_style, px as f32, py as f32, size as f32);
+    fill_path(image, &path, color, spec.antialiasing);
+}
+
+/// Foreground color at a pixel (gradient or solid)
+fn module_color(image: &RgbaImage, spec: &QrSpec, px: u32, py: u32) -> Rgba<u8> {
+    if spec.use_gradient {
+        colors::get_gradient_color(px, py, image.width(), image.height(), spec)
+    } else {
+        Rgba([spec.fg_color[0], spec.fg_color[1], spec.fg_color[2], 255])
+    }
+}
+
+/// Draw a whole eye (finder pattern) with the selected frame and pupil styles
+///
+/// Finder patterns are the three 7x7 squares in the QR code corners.
+/// The frame and pupil are filled as whole shapes and can have different
+/// styles and colors from data modules; each eye can be overridden.
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `spec` - Rendering specification for style settings
+/// * `position` - Which eye this is (selects its override)
+/// * `px`, `py` - Pixel coordinates of the eye's top-left module
+/// * `size` - Size of one module in pixels
+pub fn draw_eye(
+    image: &mut RgbaImage,
+    spec: &QrSpec,
+    position: EyePosition,
+    px: u32,
     py: u32,
-    size: u32, 
-    eye_positions: &[(usize, usize)]
+    size: u32
 ) {
-    // Determine eye color (custom, gradient, or foreground)
-    let color = if app.use_custom_eye_color {
-        Rgba([app.eye_color[0], app.eye_color[1], app.eye_color[2], 255])
-    } else if app.use_gradient {
-        colors::get_gradient_color(px, py, image.width(), image.height(), app)
-    } else {
-        Rgba([app.fg_color[0], app.fg_color[1], app.fg_color[2], 255])
+    let design = spec.eye_design(position);
+
+    // Uncolored parts use the gradient at the eye center, or the foreground
+    let center = |p: u32| p + size * 7 / 2;
+    let module_color = module_color(image, spec, center(px), center(py));
+    let color = |custom: Option<[u8; 3]>| match custom {
+        Some(c) => Rgba([c[0], c[1], c[2], 255]),
+        None => module_color,
     };
 
-    // Find which eye this module belongs to and its relative position
-    for (ex, ey) in eye_positions {
-        if x >= *ex && x < ex + 7 && y >= *ey && y < ey + 7 {
-            let rel_x = x - ex;
-            let rel_y = y - ey;
-            
-            // Draw eye with selected style
-            match app.eye_style {
-                EyeStyle::Standard => {
-                    draw_square(image, px, py, size, color);
-                }
-                EyeStyle::Circle => {
-                    draw_eye_circle(image, px, py, size, color, rel_x, rel_y);
-                }
-                EyeStyle::RoundedSquare => {
-                    draw_eye_rounded(image, px, py, size, color, app);
-                }
-                EyeStyle::Flower => {
-                    draw_eye_flower(image, px, py, size, color, rel_x, rel_y);
-                }
-                EyeStyle::Diamond => {
-                    draw_eye_diamond(image, px, py, size, color, rel_x, rel_y);
-                }
-            }
-            break;
-        }
-    }
+    let (x, y, module_size) = (px as f32, py as f32, size as f32);
+    let turns = design.quarter_turns;
+    let frame = eyes::frame_path(design.style.frame, turns, x, y, module_size);
+    let pupil = eyes::pupil_path(design.style.pupil, turns, x, y, module_size);
+    fill_path(image, &frame, color(design.frame_color), spec.antialiasing);
+    fill_path(image, &pupil, color(design.pupil_color), spec.antialiasing);
 }
 
 // ============================================================================
@@ -143,91 +185,217 @@ pub fn draw_square(image: &mut RgbaImage, x: u32, y: u32, size: u32, color: Rgba
 /// Draw a filled circle module
 ///
 /// Creates smooth, modern-looking QR codes.
-/// Uses distance formula for anti-aliased edges.
+/// Edge pixels are blended by coverage for anti-aliased edges.
 ///
 /// # Arguments
 /// * `image` - Image buffer to draw into
 /// * `x`, `y` - Top-left corner pixel coordinates
 /// * `size` - Bounding box size in pixels
 /// * `color` - RGBA color
-pub fn draw_circle(image: &mut RgbaImage, x: u32, y: u32, size: u32, color: Rgba<u8>) {
+/// * `quality` - Anti-aliasing quality
+pub fn draw_circle(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    quality: Antialiasing
+) {
     let radius = size as f32 / 2.0;
-    let center_x = x as f32 + radius;
-    let center_y = y as f32 + radius;
 
-    for dy in 0..size {
-        for dx in 0..size {
-            let px = x + dx;
-            let py = y + dy;
-            
-            // Calculate distance from center
-            let dist = ((px as f32 - center_x).powi(2) + (py as f32 - center_y).powi(2)).sqrt();
-            
-            // Only fill pixels within the circle
-            if dist <= radius && px < image.width() && py < image.height() {
-                image.put_pixel(px, py, color);
-            }
-        }
-    }
+    fill_shape(image, x, y, size, color, quality, |sx, sy| {
+        (sx - radius).powi(2) + (sy - radius).powi(2) <= radius * radius
+    });
 }
 
 /// Draw a rounded square module
 ///
 /// Combines the reliability of squares with softer, more appealing aesthetics.
-/// Corner radius is adjustable via app settings.
+/// Corner radius is adjustable via the specification.
 ///
 /// # Arguments
 /// * `image` - Image buffer to draw into
 /// * `x`, `y` - Top-left corner pixel coordinates
 /// * `size` - Size in pixels
 /// * `color` - RGBA color
-/// * `app` - Application state for corner radius settings
+/// * `spec` - Rendering specification for corner radius settings
 pub fn draw_rounded_square(
     image: &mut RgbaImage, 
     x: u32, 
     y: u32, 
     size: u32, 
     color: Rgba<u8>,
-    app: &QrCodeApp
+    spec: &QrSpec
 ) {
     // Calculate corner radius
-    let radius = if app.use_rounded_corners {
-        (size as f32 * app.corner_radius) as u32
+    let radius = if spec.use_rounded_corners {
+        (size as f32 * spec.corner_radius) as u32
     } else {
         (size as f32 * 0.2) as u32 // Default 20% rounding
     };
 
-    for dy in 0..size {
-        for dx in 0..size {
-            let px = x + dx;
-            let py = y + dy;
-
-            // Determine if we're in a corner region
-            let in_corner = (dx < radius && dy < radius) ||                      // Top-left
-                           (dx >= size - radius && dy < radius) ||               // Top-right
-                           (dx < radius && dy >= size - radius) ||               // Bottom-left
-                           (dx >= size - radius && dy >= size - radius);         // Bottom-right
-
-            if in_corner {
-                // Calculate distance from nearest corner center
-                let corner_x = if dx < radius { radius } else { size - radius };
-                let corner_y = if dy < radius { radius } else { size - radius };
-                
-                let dist = ((dx as f32 - corner_x as f32).powi(2) + 
-                           (dy as f32 - corner_y as f32).powi(2)).sqrt();
-                
-                // Only fill if within corner radius
-                if dist <= radius as f32 && px < image.width() && py < image.height() {
-                    image.put_pixel(px, py, color);
-                }
-            } else if px < image.width() && py < image.height() {
-                // Not in corner, fill normally
-                image.put_pixel(px, py, color);
-            }
-        }
+    fill_rounded_square(image, x, y, size, color, radius, spec.antialiasing);
+}
+
+/// Fill a square with circular corners of a given radius
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `x`, `y` - Top-left corner pixel coordinates
+/// * `size` - Size in pixels
+/// * `color` - RGBA color
+/// * `radius` - Corner radius in pixels
+/// * `quality` - Anti-aliasing quality
+fn fill_rounded_square(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    radius: u32,
+    quality: Antialiasing
+) {
+    let full = [0.0, 0.0, size as f32, size as f32];
+    fill_rounded_rect(image, x, y, size, color, full, [radius as f32; 4], quality);
+}
+
+/// Draw a liquid module that merges with its neighbors
+///
+/// Each corner is fully rounded only when both modules next to it are
+/// light, so runs of modules join into smooth blobs while isolated
+/// modules become circles.
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `x`, `y` - Top-left corner pixel coordinates
+/// * `size` - Size in pixels
+/// * `color` - RGBA color
+/// * `neighbors` - Dark neighbors of this module
+/// * `quality` - Anti-aliasing quality
+pub fn draw_liquid(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    neighbors: Neighbors,
+    quality: Antialiasing
+) {
+    let full = [0.0, 0.0, size as f32, size as f32];
+    let radii = liquid_radii(neighbors, size as f32);
+    fill_rounded_rect(image, x, y, size, color, full, radii, quality);
+}
+
+/// Draw one module of a vertical or horizontal line
+///
+/// Consecutive dark modules along the line direction join into a single
+/// capsule; the ends of each run are rounded.
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `x`, `y` - Top-left corner pixel coordinates
+/// * `size` - Size in pixels
+/// * `color` - RGBA color
+/// * `neighbors` - Dark neighbors of this module
+/// * `vertical` - Lines run top to bottom (otherwise left to right)
+/// * `quality` - Anti-aliasing quality
+#[allow(clippy::too_many_arguments)]
+pub fn draw_line_segment(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    neighbors: Neighbors,
+    vertical: bool,
+    quality: Antialiasing
+) {
+    let (rect, radii) = line_segment_geometry(neighbors, vertical, size as f32);
+    fill_rounded_rect(image, x, y, size, color, rect, radii, quality);
+}
+
+/// Corner radii (top-left, top-right, bottom-right, bottom-left) of a liquid module
+///
+/// Shared with the vector renderer so both produce the same shape.
+pub(crate) fn liquid_radii(neighbors: Neighbors, size: f32) -> [f32; 4] {
+    let round = |a: bool, b: bool| if a || b { 0.0 } else { size / 2.0 };
+    [
+        round(neighbors.top, neighbors.left),
+        round(neighbors.top, neighbors.right),
+        round(neighbors.bottom, neighbors.right),
+        round(neighbors.bottom, neighbors.left),
+    ]
+}
+
+/// Rectangle (left, top, right, bottom) and corner radii of a line segment module
+///
+/// The line is 80% of a module wide so parallel lines stay apart.
+/// Shared with the vector renderer so both produce the same shape.
+pub(crate) fn line_segment_geometry(
+    neighbors: Neighbors,
+    vertical: bool,
+    size: f32
+) -> ([f32; 4], [f32; 4]) {
+    let inset = size * 0.1;
+    let cap = size / 2.0 - inset;
+
+    if vertical {
+        let top = if neighbors.top { 0.0 } else { cap };
+        let bottom = if neighbors.bottom { 0.0 } else { cap };
+        ([inset, 0.0, size - inset, size], [top, top, bottom, bottom])
+    } else {
+        let left = if neighbors.left { 0.0 } else { cap };
+        let right = if neighbors.right { 0.0 } else { cap };
+        ([0.0, inset, size, size - inset], [left, right, right, left])
     }
 }
 
+/// Fill a rectangle with individually rounded corners
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `x`, `y` - Top-left corner pixel coordinates of the module box
+/// * `size` - Module box size in pixels
+/// * `color` - RGBA color
+/// * `rect` - Left, top, right and bottom edges relative to the box
+/// * `radii` - Corner radii: top-left, top-right, bottom-right, bottom-left
+/// * `quality` - Anti-aliasing quality
+#[allow(clippy::too_many_arguments)]
+fn fill_rounded_rect(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    rect: [f32; 4],
+    radii: [f32; 4],
+    quality: Antialiasing
+) {
+    let [left, top, right, bottom] = rect;
+    // A radius beyond half the rectangle would leave no straight edge
+    let max_radius = (right - left).min(bottom - top) / 2.0;
+    let radii = radii.map(|r| r.clamp(0.0, max_radius));
+
+    fill_shape(image, x, y, size, color, quality, |sx, sy| {
+        if sx < left || sx > right || sy < top || sy > bottom {
+            return false;
+        }
+
+        // Round off whichever corner this point is nearest to
+        let is_left = sx < (left + right) / 2.0;
+        let is_top = sy < (top + bottom) / 2.0;
+        let radius = match (is_top, is_left) {
+            (true, true) => radii[0],
+            (true, false) => radii[1],
+            (false, false) => radii[2],
+            (false, true) => radii[3],
+        };
+        let dx = if is_left { left + radius - sx } else { sx - (right - radius) };
+        let dy = if is_top { top + radius - sy } else { sy - (bottom - radius) };
+        dx <= 0.0 || dy <= 0.0 || dx * dx + dy * dy <= radius * radius
+    });
+}
+
 /// Draw a small dot module
 ///
 /// Creates minimalist, artistic QR codes with small circular dots.
@@ -238,133 +406,241 @@ pub fn draw_rounded_square(
 /// * `x`, `y` - Top-left corner pixel coordinates
 /// * `size` - Bounding box size in pixels
 /// * `color` - RGBA color
-pub fn draw_dot(image: &mut RgbaImage, x: u32, y: u32, size: u32, color: Rgba<u8>) {
+/// * `quality` - Anti-aliasing quality
+pub fn draw_dot(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    quality: Antialiasing
+) {
     let radius = size as f32 * 0.35; // Dot is 70% of module size
-    let center_x = x as f32 + size as f32 / 2.0;
-    let center_y = y as f32 + size as f32 / 2.0;
+    let center = size as f32 / 2.0;
+
+    fill_shape(image, x, y, size, color, quality, |sx, sy| {
+        (sx - center).powi(2) + (sy - center).powi(2) <= radius * radius
+    });
+}
+
+/// Fill a convex shape inside a square box with anti-aliased edges
+///
+/// `inside` receives sample coordinates relative to the box's top-left
+/// corner, in pixels. Pixels whose four corners are all inside are filled
+/// directly (exact for convex shapes); all others are sampled on an
+/// N x N sub-pixel grid and blended by the covered fraction.
+///
+/// # Arguments
+/// * `image` - Image buffer to draw into
+/// * `x`, `y` - Top-left corner pixel coordinates of the box
+/// * `size` - Box size in pixels
+/// * `color` - RGBA color
+/// * `quality` - Anti-aliasing quality (samples per axis)
+/// * `inside` - Whether a point lies inside the shape
+fn fill_shape(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    size: u32,
+    color: Rgba<u8>,
+    quality: Antialiasing,
+    inside: impl Fn(f32, f32) -> bool
+) {
+    let samples = quality.samples_per_axis();
+    let step = 1.0 / samples as f32;
+    let total = (samples * samples) as f32;
 
     for dy in 0..size {
         for dx in 0..size {
-            let px = x + dx;
-            let py = y + dy;
-            
-            let dist = ((px as f32 - center_x).powi(2) + (py as f32 - center_y).powi(2)).sqrt();
-            
-            if dist <= radius && px < image.width() && py < image.height() {
-                image.put_pixel(px, py, color);
+            let (px, py) = (x + dx, y + dy);
+            if px >= image.width() || py >= image.height() {
+                continue;
             }
+
+            let (fx, fy) = (dx as f32, dy as f32);
+            let fully_inside = inside(fx, fy)
+                && inside(fx + 1.0, fy)
+                && inside(fx, fy + 1.0)
+                && inside(fx + 1.0, fy + 1.0);
+
+            let coverage = if fully_inside {
+                1.0
+            } else {
+                let mut hits = 0;
+                for sy in 0..samples {
+                    for sx in 0..samples {
+                        let sample_x = fx + (sx as f32 + 0.5) * step;
+                        let sample_y = fy + (sy as f32 + 0.5) * step;
+                        if inside(sample_x, sample_y) {
+                            hits += 1;
+                        }
+                    }
+                }
+                hits as f32 / total
+            };
+
+            blend_pixel(image, px, py, color, coverage);
         }
     }
 }
 
+/// Blend a color over an existing pixel by a coverage fraction (0.0-1.0)
+fn blend_pixel(image: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>, coverage: f32) {
+    if coverage <= 0.0 {
+        return;
+    }
+    if coverage >= 1.0 {
+        image.put_pixel(x, y, color);
+        return;
+    }
+
+    let existing = image.get_pixel_mut(x, y);
+    for channel in 0..4 {
+        let blended = color[channel] as f32 * coverage + existing[channel] as f32 * (1.0 - coverage);
+        existing[channel] = blended.round() as u8;
+    }
+}
+
 // ============================================================================
-// Eye (Finder Pattern) Specific Drawing Functions
+// Path Rasterization
 // ============================================================================
 
-/// Draw circular eye pattern
+/// Fill a path with anti-aliased edges
 ///
-/// Creates eyes with circular modules, maintaining the finder pattern structure.
+/// Scanline rasterizer using the nonzero winding rule: each pixel row is
+/// split into sub-rows (one per sample), the spans inside the path are
+/// found on each sub-row, and pixels are blended by the covered fraction.
+/// With anti-aliasing off, a pixel is filled if its center is inside.
 ///
 /// # Arguments
-/// * `image` - Image buffer
-/// * `px`, `py` - Pixel coordinates
-/// * `size` - Module size
+/// * `image` - Image buffer to draw into
+/// * `path` - Outline in pixel coordinates
 /// * `color` - RGBA color
-/// * `rel_x`, `rel_y` - Relative position within the 7x7 eye (0-6)
-fn draw_eye_circle(
-    image: &mut RgbaImage, 
-    px: u32, 
-    py: u32, 
-    size: u32, 
-    color: Rgba<u8>, 
-    rel_x: usize, 
-    rel_y: usize
-) {
-    // Draw outer ring and center dot as circles
-    // Outer ring: edges of the 7x7 square (excluding immediate neighbors of center)
-    // Center dot: the single center module
-    if (rel_x <= 1 || rel_x >= 5 || rel_y <= 1 || rel_y >= 5) && !(rel_x == 3 && rel_y == 3) {
-        draw_circle(image, px, py, size, color);
-    } else if rel_x == 3 && rel_y == 3 {
-        draw_circle(image, px, py, size, color);
+/// * `quality` - Anti-aliasing quality (sub-rows per pixel)
+pub fn fill_path(image: &mut RgbaImage, path: &Path, color: Rgba<u8>, quality: Antialiasing) {
+    let Some(((min_x, min_y), (max_x, max_y))) = path.bounds() else {
+        return;
+    };
+
+    // Clip the bounding box to the image
+    let x0 = min_x.floor().max(0.0) as u32;
+    let y0 = min_y.floor().max(0.0) as u32;
+    let x1 = (max_x.ceil().max(0.0) as u32).min(image.width());
+    let y1 = (max_y.ceil().max(0.0) as u32).min(image.height());
+    if x0 >= x1 || y0 >= y1 {
+        return;
     }
-}
 
-/// Draw rounded square eye pattern
-///
-/// Uses rounded squares for a softer eye appearance.
-fn draw_eye_rounded(
-    image: &mut RgbaImage, 
-    px: u32, 
-    py: u32, 
-    size: u32, 
-    color: Rgba<u8>, 
-    app: &QrCodeApp
-) {
-    draw_rounded_square(image, px, py, size, color, app);
+    let edges: Vec<(Point, Point)> = path
+        .flatten()
+        .iter()
+        .flat_map(|polygon| polygon.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>())
+        .collect();
+
+    let samples = quality.samples_per_axis();
+    let weight = 1.0 / samples as f32;
+    let mut coverage = vec![0.0f32; (x1 - x0) as usize];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+
+    for py in y0..y1 {
+        coverage.fill(0.0);
+
+        for sub_row in 0..samples {
+            let sy = py as f32 + (sub_row as f32 + 0.5) * weight;
+
+            // Where the sub-row crosses the outline, and in which direction
+            crossings.clear();
+            for &((ax, ay), (bx, by)) in &edges {
+                if (ay <= sy) != (by <= sy) {
+                    let t = (sy - ay) / (by - ay);
+                    crossings.push((ax + t * (bx - ax), if by > ay { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for i in 0..crossings.len().saturating_sub(1) {
+                winding += crossings[i].1;
+                if winding != 0 {
+                    let (start, end) = (crossings[i].0 - x0 as f32, crossings[i + 1].0 - x0 as f32);
+                    add_span(&mut coverage, start, end, weight, quality == Antialiasing::Off);
+                }
+            }
+        }
+
+        for (i, &covered) in coverage.iter().enumerate() {
+            blend_pixel(image, x0 + i as u32, py, color, covered.min(1.0));
+        }
+    }
 }
 
-/// Draw flower-shaped eye pattern
-///
-/// Alternates between circles and rounded squares for a decorative petal effect.
+/// Add the horizontal coverage of one span to a row of pixels
 ///
 /// # Arguments
-/// * `image` - Image buffer
-/// * `px`, `py` - Pixel coordinates
-/// * `size` - Module size
-/// * `color` - RGBA color
-/// * `rel_x`, `rel_y` - Relative position within eye
-fn draw_eye_flower(
-    image: &mut RgbaImage, 
-    px: u32, 
-    py: u32, 
-    size: u32, 
-    color: Rgba<u8>, 
-    rel_x: usize, 
-    rel_y: usize
-) {
-    let is_outer = rel_x == 0 || rel_x == 6 || rel_y == 0 || rel_y == 6;
-    let is_inner = (rel_x >= 2 && rel_x <= 4) && (rel_y >= 2 && rel_y <= 4);
-    
-    if is_outer || is_inner {
-        // Alternate pattern based on position
-        if (rel_x + rel_y) % 2 == 0 {
-            draw_circle(image, px, py, size, color);
+/// * `coverage` - Coverage per pixel of the row
+/// * `start`, `end` - Span edges in pixels relative to the row start
+/// * `weight` - Share of the pixel height this sub-row stands for
+/// * `hard` - Only count pixels whose center lies in the span
+fn add_span(coverage: &mut [f32], start: f32, end: f32, weight: f32, hard: bool) {
+    let first = start.floor().max(0.0) as usize;
+    let last = (end.ceil().max(0.0) as usize).min(coverage.len());
+
+    for (i, pixel) in coverage.iter_mut().enumerate().take(last).skip(first) {
+        let left = i as f32;
+        let covered = if hard {
+            let center = left + 0.5;
+            if center >= start && center < end { 1.0 } else { 0.0 }
         } else {
-            // Use default rounding for flower petals
-            let default_app = crate::app::QrCodeApp::default();
-            draw_rounded_square(image, px, py, size, color, &default_app);
-        }
+            (end.min(left + 1.0) - start.max(left)).max(0.0)
+        };
+        *pixel += covered * weight;
     }
 }
 
-/// Draw diamond-shaped eye pattern
-///
-/// Creates a geometric diamond shape using Manhattan distance.
-///
-/// # Arguments
-/// * `image` - Image buffer
-/// * `px`, `py` - Pixel coordinates
-/// * `size` - Module size
-/// * `color` - RGBA color
-/// * `rel_x`, `rel_y` - Relative position within eye
-fn draw_eye_diamond(
-    image: &mut RgbaImage, 
-    px: u32, 
-    py: u32, 
-    size: u32, 
-    color: Rgba<u8>, 
-    rel_x: usize, 
-    rel_y: usize
-) {
-    let cx = 3; // Center x
-    let cy = 3; // Center y
-    
-    // Calculate Manhattan distance from center
-    let dist = ((rel_x as i32 - cx).abs() + (rel_y as i32 - cy).abs()) as usize;
-    
-    // Draw only modules at specific distances to form diamond
-    if dist == 3 || dist == 1 {
-        draw_square(image, px, py, size, color);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+    fn circle(quality: Antialiasing) -> RgbaImage {
+        let mut image = RgbaImage::from_pixel(20, 20, WHITE);
+        draw_circle(&mut image, 0, 0, 20, BLACK, quality);
+        image
+    }
+
+    #[test]
+    fn test_antialiased_edges_are_blended() {
+        let image = circle(Antialiasing::Standard);
+        assert_eq!(*image.get_pixel(10, 10), BLACK);
+        assert_eq!(*image.get_pixel(0, 0), WHITE);
+
+        let partial = image.pixels().filter(|p| p[0] != 0 && p[0] != 255).count();
+        assert!(partial > 0, "edge pixels should be partially covered");
+    }
+
+    #[test]
+    fn test_antialiasing_off_has_hard_edges() {
+        let image = circle(Antialiasing::Off);
+        assert!(image.pixels().all(|p| p[0] == 0 || p[0] == 255));
+    }
+
+    #[test]
+    fn test_liquid_rounds_only_exposed_corners() {
+        let isolated = liquid_radii(Neighbors::default(), 10.0);
+        assert_eq!(isolated, [5.0; 4]);
+
+        // A module with a dark neighbor below keeps its bottom corners square
+        let above = liquid_radii(Neighbors { bottom: true, ..Default::default() }, 10.0);
+        assert_eq!(above, [5.0, 5.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_full_radius_rounded_square_is_a_circle() {
+        let spec = QrSpec { use_rounded_corners: true, corner_radius: 1.0, ..Default::default() };
+        let mut rounded = RgbaImage::from_pixel(20, 20, WHITE);
+        draw_rounded_square(&mut rounded, 0, 0, 20, BLACK, &spec);
+        assert_eq!(rounded, circle(Antialiasing::Standard));
     }
 }
@@ -0,0 +1,145 @@
+//! ECI character sets
+//!
+//! Byte-mode content is just bytes. Phone scanners read it as UTF-8, but
+//! older readers follow the QR default of ISO-8859-1 (or Shift JIS in
+//! Japan) and garble anything else. Choosing a `CharacterSet` transcodes
+//! the text into that set and announces it with an ECI designator, which
+//! the encoder writes in front of the content.
+//!
+//! Characters the set cannot represent are replaced with `?`;
+//! `unrepresentable` lists them so the user can be warned first.
+
+use encoding_rs::Encoding;
+
+use crate::types::CharacterSet;
+
+/// Byte written for characters the character set cannot represent
+const REPLACEMENT: u8 = b'?';
+
+/// Convert text to the bytes of a character set
+///
+/// # Arguments
+/// * `text` - Content to encode
+/// * `charset` - Target character set
+///
+/// # Returns
+/// Encoded bytes, with every unrepresentable character replaced by `?`
+pub fn transcode(text: &str, charset: CharacterSet) -> Vec<u8> {
+    match charset {
+        CharacterSet::Auto | CharacterSet::Utf8 => text.as_bytes().to_vec(),
+        _ => text
+            .chars()
+            .flat_map(|c| encode_char(c, charset).unwrap_or_else(|| vec![REPLACEMENT]))
+            .collect(),
+    }
+}
+
+/// Characters of the text that the character set cannot represent
+///
+/// Each character is listed once, in order of first appearance.
+pub fn unrepresentable(text: &str, charset: CharacterSet) -> Vec<char> {
+    let mut missing = Vec::new();
+    for c in text.chars() {
+        if !missing.contains(&c) && encode_char(c, charset).is_none() {
+            missing.push(c);
+        }
+    }
+    missing
+}
+
+/// Warning about characters the character set cannot represent
+///
+/// # Returns
+/// Message naming the characters that will be stored as `?`, or `None`
+/// if the text fits the set
+pub fn warning(text: &str, charset: CharacterSet) -> Option<String> {
+    let missing = unrepresentable(text, charset);
+    if missing.is_empty() {
+        return None;
+    }
+    let listed: Vec<String> = missing.iter().map(|c| format!("'{}'", c)).collect();
+    Some(format!("{} cannot be represented in {} and will be encoded as '?'", listed.join(", "), charset.name()))
+}
+
+/// Convert bytes of a character set back to text
+///
+/// Invalid sequences become U+FFFD.
+pub fn decode(bytes: &[u8], charset: CharacterSet) -> String {
+    match codec(charset) {
+        Some(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+        None => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Bytes of one character, or `None` if the set cannot represent it
+fn encode_char(c: char, charset: CharacterSet) -> Option<Vec<u8>> {
+    let mut buffer = [0; 4];
+    let utf8 = c.encode_utf8(&mut buffer);
+    match codec(charset) {
+        Some(encoding) => {
+            let (bytes, _, had_errors) = encoding.encode(utf8);
+            (!had_errors).then(|| bytes.into_owned())
+        }
+        // ISO-8859-1 is the first 256 code points
+        None => u8::try_from(c as u32).ok().map(|b| vec![b]),
+    }
+}
+
+/// `encoding_rs` codec of a character set (`None` for ISO-8859-1, which
+/// `encoding_rs` treats as Windows-1252)
+fn codec(charset: CharacterSet) -> Option<&'static Encoding> {
+    Some(match charset {
+        CharacterSet::Auto | CharacterSet::Utf8 => encoding_rs::UTF_8,
+        CharacterSet::Iso8859_1 => return None,
+        CharacterSet::Iso8859_2 => encoding_rs::ISO_8859_2,
+        CharacterSet::Iso8859_5 => encoding_rs::ISO_8859_5,
+        CharacterSet::Iso8859_7 => encoding_rs::ISO_8859_7,
+        CharacterSet::Iso8859_15 => encoding_rs::ISO_8859_15,
+        CharacterSet::Windows1250 => encoding_rs::WINDOWS_1250,
+        CharacterSet::Windows1251 => encoding_rs::WINDOWS_1251,
+        CharacterSet::Windows1252 => encoding_rs::WINDOWS_1252,
+        CharacterSet::ShiftJis => encoding_rs::SHIFT_JIS,
+        CharacterSet::Big5 => encoding_rs::BIG5,
+        CharacterSet::Gb18030 => encoding_rs::GB18030,
+        CharacterSet::EucKr => encoding_rs::EUC_KR,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcode_round_trips() {
+        assert_eq!(transcode("Café", CharacterSet::Iso8859_1), b"Caf\xE9");
+        assert_eq!(transcode("5 €", CharacterSet::Iso8859_15), b"5 \xA4");
+        assert_eq!(transcode("Café", CharacterSet::Utf8), "Café".as_bytes());
+
+        for charset in CharacterSet::ALL {
+            let text = match charset {
+                CharacterSet::ShiftJis => "品名 ｶﾀｶﾅ",
+                CharacterSet::Big5 => "產品名稱",
+                CharacterSet::Gb18030 => "产品名称",
+                CharacterSet::EucKr => "제품 이름",
+                CharacterSet::Iso8859_5 | CharacterSet::Windows1251 => "Товар",
+                CharacterSet::Iso8859_7 => "Προϊόν",
+                CharacterSet::Iso8859_2 | CharacterSet::Windows1250 => "Žluťoučký",
+                _ => "Crème brûlée",
+            };
+            assert!(unrepresentable(text, charset).is_empty(), "{:?}", charset);
+            assert_eq!(decode(&transcode(text, charset), charset), text, "{:?}", charset);
+        }
+    }
+
+    #[test]
+    fn test_unrepresentable_characters_are_replaced() {
+        let text = "Ωmega € Ω";
+        assert_eq!(unrepresentable(text, CharacterSet::Iso8859_1), vec!['Ω', '€']);
+        assert_eq!(transcode(text, CharacterSet::Iso8859_1), b"?mega ? ?");
+        assert!(unrepresentable(text, CharacterSet::Utf8).is_empty());
+        assert_eq!(
+            warning(text, CharacterSet::Iso8859_1).as_deref(),
+            Some("'Ω', '€' cannot be represented in ISO-8859-1 and will be encoded as '?'")
+        );
+    }
+}
@@ -0,0 +1,610 @@
+//! Content encoding with explicit version, mask and segment mode
+//!
+//! `QrCode::with_error_correction_level` picks the version, mask pattern
+//! and segmentation automatically. This module drives the lower-level bit
+//! and canvas APIs of the `qrcode` crate instead, so each choice can be
+//! forced from the `QrSpec`, and reports what was used (version, mask and
+//! remaining capacity) for display.
+//!
+//! Micro QR goes through the same `qrcode` APIs with `Version::Micro`;
+//! rMQR symbols are built by `rmqr::encode`.
+//!
+//! Symbols that are part of a Structured Append sequence get the sequence
+//! header (position, count and parity) in front of their content. GS1
+//! element strings are encoded after an FNC1 indicator, and content in a
+//! chosen character set after its ECI designator.
+
+use qrcode::bits::Bits;
+use qrcode::canvas::{Canvas, MaskPattern};
+use qrcode::optimize::{Optimizer, Parser};
+use qrcode::types::QrError;
+use qrcode::types::Mode;
+use qrcode::{ec, EcLevel, Version};
+
+use crate::qr::{eci, gs1};
+use crate::qr::matrix::QrMatrix;
+use crate::qr::rmqr;
+use crate::qr::spec::QrSpec;
+use crate::qr::structured::SequencePosition;
+use crate::types::{CharacterSet, ContentMode, EncodingMode, ErrorCorrectionLevel, QrVersion, SymbolType};
+
+/// Mask patterns indexed by their number in the format information
+const MASK_PATTERNS: [MaskPattern; 8] = [
+    MaskPattern::Checkerboard,
+    MaskPattern::HorizontalLines,
+    MaskPattern::VerticalLines,
+    MaskPattern::DiagonalLines,
+    MaskPattern::LargeCheckerboard,
+    MaskPattern::Fields,
+    MaskPattern::Diamonds,
+    MaskPattern::Meadow,
+];
+
+/// Micro QR mask patterns indexed by their number in the format information
+const MICRO_MASK_PATTERNS: [MaskPattern; 4] = [
+    MaskPattern::HorizontalLines,
+    MaskPattern::LargeCheckerboard,
+    MaskPattern::Diamonds,
+    MaskPattern::Meadow,
+];
+
+/// Mode indicator of the Structured Append header
+const STRUCTURED_APPEND_MODE: u32 = 0b0011;
+
+/// Bits of the Structured Append header: mode, position, count and parity
+const SEQUENCE_HEADER_BITS: usize = 4 + 4 + 4 + 8;
+
+/// Characters allowed in alphanumeric mode besides digits and A-Z
+const ALPHANUMERIC_SYMBOLS: &str = " $%*+-./:";
+
+/// What the encoder chose for the content
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingInfo {
+    /// Kind of symbol
+    pub symbol: SymbolType,
+    /// Version (QR 1-40, Micro QR 1-4, rMQR 1-32)
+    pub version: i16,
+    /// Width of the symbol in modules, without border
+    pub width: usize,
+    /// Height of the symbol in modules, without border
+    pub height: usize,
+    /// Error correction level
+    pub ec_level: ErrorCorrectionLevel,
+    /// Mask pattern (0-7, Micro QR 0-3, always 0 for rMQR)
+    pub mask: u8,
+    /// Segment mode the content was encoded with
+    pub mode: EncodingMode,
+    /// Bits used by the content (including mode and length headers)
+    pub data_bits: usize,
+    /// Data bits the version holds at this error correction level
+    pub capacity_bits: usize,
+}
+
+impl EncodingInfo {
+    /// Share of the capacity used by the content (0.0-1.0)
+    pub fn usage(&self) -> f32 {
+        self.data_bits as f32 / self.capacity_bits as f32
+    }
+
+    /// Approximate number of characters that still fit in the same mode
+    ///
+    /// Counted in bytes for automatic and byte mode.
+    pub fn remaining_chars(&self) -> usize {
+        let free = self.capacity_bits.saturating_sub(self.data_bits);
+        (free as f32 / self.mode.bits_per_char()) as usize
+    }
+
+    /// Version name, e.g. "Version 3", "M2" or "R11x43"
+    pub fn version_name(&self) -> String {
+        match self.symbol {
+            SymbolType::Qr => format!("Version {}", self.version),
+            SymbolType::Micro => format!("M{}", self.version),
+            SymbolType::Rectangular => rmqr::size_name(self.version as u8),
+        }
+    }
+
+    /// One-line description, e.g. "Version 3 (29x29 modules), mask 5, 62% used"
+    pub fn summary(&self) -> String {
+        let unit = match self.mode {
+            EncodingMode::Auto | EncodingMode::Byte => "bytes",
+            EncodingMode::Numeric => "digits",
+            EncodingMode::Alphanumeric | EncodingMode::Kanji => "characters",
+        };
+        format!(
+            "{} ({}x{} modules), mask {}, {:.0}% used, ~{} more {}",
+            self.version_name(),
+            self.width,
+            self.height,
+            self.mask,
+            self.usage() * 100.0,
+            self.remaining_chars(),
+            unit
+        )
+    }
+}
+
+/// Encoded symbol and the choices that produced it
+#[derive(Debug, Clone)]
+pub struct EncodedQr {
+    /// Module colors and function pattern positions
+    pub matrix: QrMatrix,
+    /// Version, mask and capacity information
+    pub info: EncodingInfo,
+    /// Data and error correction codewords in placement order (empty for rMQR)
+    pub codewords: Vec<u8>,
+}
+
+/// Encode the content of a specification
+///
+/// Uses `spec.symbol_type`, `spec.version`, `spec.mask_pattern`,
+/// `spec.encoding_mode` and `spec.character_set`; automatic choices for standard QR codes match
+/// what `QrCode::with_error_correction_level` does.
+///
+/// # Returns
+/// * `Ok(EncodedQr)` - Encoded matrix with version and capacity details
+/// * `Err(String)` - Error message if the content does not fit or contains
+///   characters the selected mode cannot encode (characters outside the
+///   character set are replaced with `?`, see `eci::unrepresentable`)
+pub fn encode(spec: &QrSpec) -> Result<EncodedQr, String> {
+    // === Step 1: Validate Settings ===
+    let (max_version, max_mask) = match spec.symbol_type {
+        SymbolType::Qr => (40, 7),
+        SymbolType::Micro => (4, 3),
+        SymbolType::Rectangular => (rmqr::SIZES.len() as u8, 0),
+    };
+    if let Some(mask) = spec.mask_pattern.filter(|&m| m > max_mask) {
+        return Err(format!("Invalid {} mask pattern {} (expected 0-{})", spec.symbol_type.name(), mask, max_mask));
+    }
+    let versions = match spec.version {
+        QrVersion::Auto => 1..=max_version,
+        QrVersion::Minimum(v) => v..=max_version,
+        QrVersion::Exact(v) => v..=v,
+    };
+    if !(1..=max_version).contains(versions.start()) {
+        return Err(format!(
+            "Invalid {} version {} (expected 1-{})",
+            spec.symbol_type.name(), versions.start(), max_version
+        ));
+    }
+    if spec.sequence_position.is_some() && spec.symbol_type != SymbolType::Qr {
+        return Err(format!("Structured Append is not available for {}", spec.symbol_type.name()));
+    }
+    let fnc1 = gs1::uses_fnc1(spec);
+    if fnc1 && spec.symbol_type != SymbolType::Qr {
+        return Err(format!(
+            "GS1 element strings are not available for {} (use a Digital Link URI)",
+            spec.symbol_type.name()
+        ));
+    }
+    let charset = spec.character_set;
+    if charset != CharacterSet::Auto {
+        if spec.symbol_type != SymbolType::Qr {
+            return Err(format!("ECI character sets are not available for {}", spec.symbol_type.name()));
+        }
+        if fnc1 {
+            return Err("GS1 element strings use their own character set (choose Auto)".to_string());
+        }
+    }
+    // Payload fields are checked before anything is encoded
+    if let Some(payload) = &spec.payload {
+        payload.to_text()?;
+        if let Some(level) = payload.required_ec_level() {
+            if spec.ec_level != level {
+                return Err(format!(
+                    "{} codes must use the {:?} error correction level",
+                    payload.kind().name(), level
+                ));
+            }
+            if spec.symbol_type != SymbolType::Qr {
+                return Err(format!("{} codes must be standard QR codes", payload.kind().name()));
+            }
+        }
+    }
+    let data = match spec.content_mode {
+        ContentMode::Gs1 if fnc1 => gs1::element_string(&gs1::parse(&spec.qr_text)?).into_bytes(),
+        ContentMode::Gs1 => {
+            gs1::parse(&spec.qr_text)?;
+            mode_data(&spec.qr_text, spec.encoding_mode, charset)?
+        }
+        ContentMode::Text => mode_data(&spec.qr_text, spec.encoding_mode, charset)?,
+    };
+
+    if spec.symbol_type == SymbolType::Rectangular {
+        return encode_rmqr(spec, &data, versions);
+    }
+
+    // === Step 2: Find the Smallest Version That Fits ===
+    let ec_level = spec.ec_level.to_ec_level();
+    let mut fitted = None;
+    let mut last_error = QrError::DataTooLong;
+    for number in versions {
+        let version = match spec.symbol_type {
+            SymbolType::Micro => Version::Micro(number as i16),
+            _ => Version::Normal(number as i16),
+        };
+        match encode_bits(&data, spec.encoding_mode, charset, version, ec_level, fnc1, spec.sequence_position) {
+            Ok(stream) => {
+                fitted = Some((number as i16, version, stream));
+                break;
+            }
+            // Micro versions also reject some levels and modes; try the next size
+            Err(e @ (QrError::InvalidVersion | QrError::UnsupportedCharacterSet))
+                if spec.symbol_type == SymbolType::Micro =>
+            {
+                last_error = e;
+            }
+            Err(QrError::DataTooLong) => continue,
+            Err(e) => return Err(format!("Failed to create QR code: {}", e)),
+        }
+    }
+    let Some((number, version, (bytes, data_bits, capacity_bits))) = fitted else {
+        return Err(match (spec.version, last_error) {
+            (QrVersion::Exact(v), QrError::DataTooLong) => format!(
+                "Content does not fit in version {} at {:?} error correction",
+                v, spec.ec_level
+            ),
+            (_, QrError::DataTooLong) => "Failed to create QR code: data too long".to_string(),
+            (_, e) => format!(
+                "Failed to create {} at {:?} error correction: {}",
+                spec.symbol_type.name(), spec.ec_level, e
+            ),
+        });
+    };
+
+    // === Step 3: Place Codewords ===
+    let (data_codewords, ec_codewords) = ec::construct_codewords(&bytes, version, ec_level)
+        .map_err(|e| format!("Failed to create QR code: {}", e))?;
+    let mut canvas = Canvas::new(version, ec_level);
+    canvas.draw_all_functional_patterns();
+    canvas.draw_data(&data_codewords, &ec_codewords);
+
+    // === Step 4: Apply the Mask ===
+    let patterns: &[MaskPattern] = match version {
+        Version::Micro(_) => &MICRO_MASK_PATTERNS,
+        Version::Normal(_) => &MASK_PATTERNS,
+    };
+    let (colors, mask) = match spec.mask_pattern {
+        Some(mask) => {
+            canvas.apply_mask(patterns[mask as usize]);
+            (canvas.into_colors(), mask)
+        }
+        None => {
+            // The crate does not say which pattern won, so find the one
+            // that produces the same modules
+            let best = canvas.apply_best_mask().into_colors();
+            let mask = (0..patterns.len())
+                .find(|&i| {
+                    let mut masked = canvas.clone();
+                    masked.apply_mask(patterns[i]);
+                    masked.into_colors() == best
+                })
+                .unwrap_or(0) as u8;
+            (best, mask)
+        }
+    };
+
+    let matrix = QrMatrix::from_colors(version, &colors);
+    let info = EncodingInfo {
+        symbol: spec.symbol_type,
+        version: number,
+        width: matrix.width(),
+        height: matrix.height(),
+        ec_level: spec.ec_level,
+        mask,
+        mode: spec.encoding_mode,
+        data_bits,
+        capacity_bits,
+    };
+
+    let codewords = [data_codewords, ec_codewords].concat();
+
+    Ok(EncodedQr { matrix, info, codewords })
+}
+
+/// Encode an rMQR symbol (Medium or High error correction only)
+fn encode_rmqr(
+    spec: &QrSpec,
+    data: &[u8],
+    versions: std::ops::RangeInclusive<u8>,
+) -> Result<EncodedQr, String> {
+    let high = match spec.ec_level {
+        ErrorCorrectionLevel::Medium => false,
+        ErrorCorrectionLevel::High => true,
+        level => return Err(format!("rMQR supports Medium and High error correction only (not {:?})", level)),
+    };
+
+    let symbol = rmqr::encode(data, spec.encoding_mode, versions, high)?;
+    let info = EncodingInfo {
+        symbol: SymbolType::Rectangular,
+        version: symbol.version as i16,
+        width: symbol.matrix.width(),
+        height: symbol.matrix.height(),
+        ec_level: spec.ec_level,
+        mask: 0,
+        mode: spec.encoding_mode,
+        data_bits: symbol.data_bits,
+        capacity_bits: symbol.capacity_bits,
+    };
+
+    Ok(EncodedQr { matrix: symbol.matrix, info, codewords: Vec::new() })
+}
+
+/// Content bytes for a segment mode, checked against the mode's character set
+///
+/// Automatic and byte mode store the text transcoded to `charset`.
+pub(crate) fn mode_data(text: &str, mode: EncodingMode, charset: CharacterSet) -> Result<Vec<u8>, String> {
+    match mode {
+        EncodingMode::Auto | EncodingMode::Byte => Ok(eci::transcode(text, charset)),
+        EncodingMode::Numeric => match text.chars().find(|c| !c.is_ascii_digit()) {
+            None => Ok(text.as_bytes().to_vec()),
+            Some(c) => Err(format!("Numeric mode only encodes digits 0-9 (found '{}')", c)),
+        },
+        EncodingMode::Alphanumeric => {
+            let allowed = |c: char| c.is_ascii_digit() || c.is_ascii_uppercase() || ALPHANUMERIC_SYMBOLS.contains(c);
+            match text.chars().find(|&c| !allowed(c)) {
+                None => Ok(text.as_bytes().to_vec()),
+                Some(c) => Err(format!(
+                    "Alphanumeric mode only encodes 0-9, A-Z and '{}' (found '{}')",
+                    ALPHANUMERIC_SYMBOLS, c
+                )),
+            }
+        }
+        EncodingMode::Kanji => {
+            if !matches!(charset, CharacterSet::Auto | CharacterSet::ShiftJis) {
+                return Err(format!("Kanji mode stores Shift JIS, not {}", charset.name()));
+            }
+            let (bytes, _, had_errors) = encoding_rs::SHIFT_JIS.encode(text);
+            let is_kanji = |pair: &[u8]| {
+                let code = u16::from_be_bytes([pair[0], pair[1]]);
+                (0x8140..=0x9FFC).contains(&code) || (0xE040..=0xEBBF).contains(&code)
+            };
+            if had_errors || bytes.len() % 2 != 0 || !bytes.chunks(2).all(is_kanji) {
+                return Err("Kanji mode only encodes double-byte Shift JIS characters".to_string());
+            }
+            Ok(bytes.into_owned())
+        }
+    }
+}
+
+/// Encode content bytes for one version
+///
+/// With `fnc1` the content is a GS1 element string: it follows an FNC1
+/// indicator and is segmented automatically (see `push_segments`).
+///
+/// A character set other than `Auto` puts its ECI designator in front of
+/// the content. Automatic segmentation then stores Shift JIS lookalike
+/// byte pairs as bytes, since scanners would decode Kanji segments as
+/// Shift JIS regardless of the designator.
+///
+/// With a sequence position the Structured Append header is written in
+/// front of the content. `Bits` can only append segments, so the stream is
+/// then terminated and padded here rather than by `push_terminator`.
+///
+/// # Returns
+/// * `Ok((bytes, data_bits, capacity_bits))` - Padded data codewords, the
+///   bits used by the content and the bits the version holds
+/// * `Err(QrError::DataTooLong)` - The content does not fit this version
+fn encode_bits(
+    data: &[u8],
+    mode: EncodingMode,
+    charset: CharacterSet,
+    version: Version,
+    ec_level: EcLevel,
+    fnc1: bool,
+    sequence: Option<SequencePosition>,
+) -> Result<(Vec<u8>, usize, usize), QrError> {
+    let mut bits = Bits::new(version);
+    if let Some(designator) = charset.eci_designator() {
+        bits.push_eci_designator(designator)?;
+    }
+    if fnc1 {
+        bits.push_fnc1_first_position()?;
+        push_segments(&mut bits, data, version, true)?;
+    } else {
+        match mode {
+            EncodingMode::Auto if !matches!(charset, CharacterSet::Auto | CharacterSet::ShiftJis) => {
+                push_segments(&mut bits, data, version, false)?
+            }
+            EncodingMode::Auto => bits.push_optimal_data(data)?,
+            EncodingMode::Numeric => bits.push_numeric_data(data)?,
+            EncodingMode::Alphanumeric => bits.push_alphanumeric_data(data)?,
+            EncodingMode::Byte => bits.push_byte_data(data)?,
+            EncodingMode::Kanji => bits.push_kanji_data(data)?,
+        }
+    }
+    let capacity_bits = bits.max_len(ec_level)?;
+
+    let Some(position) = sequence else {
+        let data_bits = bits.len();
+        bits.push_terminator(ec_level)?;
+        return Ok((bits.into_bytes(), data_bits, capacity_bits));
+    };
+
+    let data_bits = SEQUENCE_HEADER_BITS + bits.len();
+    if data_bits > capacity_bits {
+        return Err(QrError::DataTooLong);
+    }
+    let mut stream = Vec::with_capacity(capacity_bits);
+    push_bits(&mut stream, STRUCTURED_APPEND_MODE, 4);
+    push_bits(&mut stream, position.index as u32, 4);
+    push_bits(&mut stream, position.total as u32 - 1, 4);
+    push_bits(&mut stream, position.parity as u32, 8);
+    let content_bits = bits.len();
+    let content = bits.into_bytes();
+    stream.extend((0..content_bits).map(|i| content[i / 8] >> (7 - i % 8) & 1 == 1));
+
+    Ok((terminate_and_pad(stream, capacity_bits, 4), data_bits, capacity_bits))
+}
+
+/// Push automatically segmented data without Kanji segments
+///
+/// With `fnc1` the data is a GS1 element string: its separators are
+/// written as `%` in alphanumeric segments (a literal `%` doubled) and as
+/// GS in byte segments.
+fn push_segments(bits: &mut Bits, data: &[u8], version: Version, fnc1: bool) -> Result<(), QrError> {
+    let separator = gs1::GROUP_SEPARATOR as u8;
+    for segment in Optimizer::new(Parser::new(data), version) {
+        let slice = &data[segment.begin..segment.end];
+        match segment.mode {
+            Mode::Numeric => bits.push_numeric_data(slice)?,
+            Mode::Alphanumeric if fnc1 => {
+                let escaped: Vec<u8> = slice
+                    .iter()
+                    .flat_map(|&b| match b {
+                        b'%' => vec![b'%', b'%'],
+                        b if b == separator => vec![b'%'],
+                        b => vec![b],
+                    })
+                    .collect();
+                bits.push_alphanumeric_data(&escaped)?
+            }
+            Mode::Alphanumeric => bits.push_alphanumeric_data(slice)?,
+            Mode::Byte | Mode::Kanji => bits.push_byte_data(slice)?,
+        }
+    }
+    Ok(())
+}
+
+/// Append the lowest `count` bits of `value`, most significant first
+pub(crate) fn push_bits(bits: &mut Vec<bool>, value: u32, count: usize) {
+    bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+}
+
+/// Terminate a bit stream, pack it into bytes and pad it to the capacity
+///
+/// Writes up to `terminator_bits` zero bits, fills the last byte with
+/// zeros and then alternates the pad codewords 0xEC and 0x11.
+pub(crate) fn terminate_and_pad(mut bits: Vec<bool>, capacity_bits: usize, terminator_bits: usize) -> Vec<u8> {
+    let terminator = capacity_bits.saturating_sub(bits.len()).min(terminator_bits);
+    bits.extend(std::iter::repeat_n(false, terminator));
+    while !bits.len().is_multiple_of(8) {
+        bits.push(false);
+    }
+    let mut codewords: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+        .collect();
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() * 8 >= capacity_bits {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qrcode::QrCode;
+
+    #[test]
+    fn test_automatic_choices_match_qrcode() {
+        let spec = QrSpec { qr_text: "https://example.com/product/12345".to_string(), ..QrSpec::default() };
+        let encoded = encode(&spec).unwrap();
+        let code = QrCode::with_error_correction_level(&spec.qr_text, spec.ec_level.to_ec_level()).unwrap();
+
+        assert_eq!(Version::Normal(encoded.info.version), code.version());
+        assert_eq!(QrMatrix::new(&code).width(), encoded.matrix.width());
+        for y in 0..code.width() as isize {
+            for x in 0..code.width() as isize {
+                assert_eq!(encoded.matrix.is_dark(x, y), QrMatrix::new(&code).is_dark(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_version_and_mask() {
+        let mut spec = QrSpec { qr_text: "hello".to_string(), ..QrSpec::default() };
+        spec.version = QrVersion::Minimum(5);
+        spec.mask_pattern = Some(3);
+        let info = encode(&spec).unwrap().info;
+        assert_eq!((info.version, info.width, info.mask), (5, 37, 3));
+
+        spec.version = QrVersion::Exact(1);
+        spec.qr_text = "x".repeat(100);
+        assert!(encode(&spec).is_err());
+    }
+
+    #[test]
+    fn test_restricted_modes() {
+        let mut spec = QrSpec { qr_text: "0123456789".repeat(4), ..QrSpec::default() };
+        spec.encoding_mode = EncodingMode::Numeric;
+        let numeric = encode(&spec).unwrap().info;
+        spec.encoding_mode = EncodingMode::Byte;
+        let byte = encode(&spec).unwrap().info;
+        assert!(numeric.data_bits < byte.data_bits);
+
+        spec.encoding_mode = EncodingMode::Alphanumeric;
+        spec.qr_text = "lowercase".to_string();
+        assert!(encode(&spec).is_err());
+
+        spec.encoding_mode = EncodingMode::Kanji;
+        spec.qr_text = "漢字".to_string();
+        assert_eq!(encode(&spec).unwrap().info.data_bits, 4 + 8 + 2 * 13);
+    }
+
+    #[test]
+    fn test_micro_and_rectangular_symbols() {
+        let mut spec = QrSpec { qr_text: "12345".to_string(), ..QrSpec::default() };
+        spec.symbol_type = SymbolType::Micro;
+        let info = encode(&spec).unwrap().info;
+        assert_eq!((info.version_name(), info.width, info.height), ("M2".to_string(), 13, 13));
+
+        // M1 only has error detection, so a Medium request skips it
+        spec.ec_level = ErrorCorrectionLevel::Low;
+        assert_eq!(encode(&spec).unwrap().info.version, 1);
+        spec.mask_pattern = Some(5);
+        assert!(encode(&spec).is_err());
+
+        spec.mask_pattern = None;
+        spec.symbol_type = SymbolType::Rectangular;
+        assert!(encode(&spec).is_err(), "rMQR has no Low level");
+        spec.ec_level = ErrorCorrectionLevel::Medium;
+        let info = encode(&spec).unwrap().info;
+        assert_eq!((info.version_name(), info.width, info.height), ("R11x27".to_string(), 27, 11));
+        assert!(info.summary().starts_with("R11x27 (27x11 modules)"));
+    }
+
+    #[test]
+    fn test_gs1_starts_with_fnc1() {
+        let mut spec = QrSpec {
+            qr_text: "(01)09501101530003(10)AB%1(17)251231".to_string(),
+            content_mode: ContentMode::Gs1,
+            ..QrSpec::default()
+        };
+        // FNC1 in first position (0101), then the numeric GTIN segment (0001)
+        assert_eq!(encode(&spec).unwrap().codewords[0], 0x51);
+
+        spec.qr_text = "(01)09501101530004".to_string();
+        assert!(encode(&spec).unwrap_err().contains("check digit"));
+
+        // Digital Link URIs are validated but encoded as plain text
+        spec.qr_text = "https://id.gs1.org/01/09501101530003".to_string();
+        let link = encode(&spec).unwrap();
+        let plain = encode(&QrSpec { content_mode: ContentMode::Text, ..spec.clone() }).unwrap();
+        assert_eq!(link.codewords, plain.codewords);
+
+        spec.qr_text = "(01)09501101530003".to_string();
+        spec.symbol_type = SymbolType::Rectangular;
+        assert!(encode(&spec).is_err());
+    }
+
+    #[test]
+    fn test_eci_designator_and_transcoding() {
+        let mut spec = QrSpec { qr_text: "Café".to_string(), ..QrSpec::default() };
+        let utf8 = encode(&spec).unwrap().info.data_bits;
+
+        // ECI 0111 with designator 3, then byte mode 0100 and 4 Latin-1 bytes
+        spec.character_set = CharacterSet::Iso8859_1;
+        let encoded = encode(&spec).unwrap();
+        assert_eq!(&encoded.codewords[..2], &[0x70, 0x34]);
+        assert_eq!(encoded.info.data_bits, utf8 + 12 - 8);
+
+        spec.encoding_mode = EncodingMode::Kanji;
+        assert!(encode(&spec).is_err());
+        spec.encoding_mode = EncodingMode::Auto;
+        spec.symbol_type = SymbolType::Micro;
+        assert!(encode(&spec).is_err());
+    }
+}
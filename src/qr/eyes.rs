@@ -0,0 +1,134 @@
+//! Finder pattern (eye) and alignment pattern geometry
+//!
+//! Each eye is drawn as two whole shapes: the frame (the outer ring of the
+//! 7x7 pattern) and the pupil (the 3x3 center). Shapes are defined in
+//! module units within the eye's 7x7 box, optionally rotated around its
+//! center (so asymmetric shapes such as the leaf can point inward), then
+//! scaled to pixels. Alignment patterns (a 5x5 ring around one module) are
+//! built the same way. They are shared by the raster, SVG and PDF renderers.
+
+use crate::qr::path::{Path, Point};
+use crate::types::{EyeFrameStyle, EyePupilStyle, PatternStyle};
+
+/// Center of the eye box in module units
+const CENTER: Point = (3.5, 3.5);
+
+/// Outline of an eye frame in pixel coordinates
+///
+/// # Arguments
+/// * `style` - Frame shape
+/// * `quarter_turns` - Clockwise rotation in quarter turns
+/// * `x`, `y` - Pixel position of the eye's top-left module
+/// * `module_size` - Size of one module in pixels
+pub fn frame_path(style: EyeFrameStyle, quarter_turns: u8, x: f32, y: f32, module_size: f32) -> Path {
+    let path = match style {
+        EyeFrameStyle::Square => ring(
+            Path::new().rect(0.0, 0.0, 7.0, 7.0),
+            Path::new().rect(1.0, 1.0, 5.0, 5.0),
+        ),
+        // Small radii keep the corners scanners use to find the eye
+        EyeFrameStyle::Rounded => ring(
+            Path::new().rounded_rect(0.0, 0.0, 7.0, 7.0, [0.75; 4]),
+            Path::new().rounded_rect(1.0, 1.0, 5.0, 5.0, [0.25; 4]),
+        ),
+        EyeFrameStyle::Circle => ring(
+            Path::new().circle(3.5, 3.5, 3.5),
+            Path::new().circle(3.5, 3.5, 2.5),
+        ),
+        EyeFrameStyle::Leaf => ring(
+            Path::new().rounded_rect(0.0, 0.0, 7.0, 7.0, [3.0, 0.0, 3.0, 0.0]),
+            Path::new().rounded_rect(1.0, 1.0, 5.0, 5.0, [2.0, 0.0, 2.0, 0.0]),
+        ),
+        EyeFrameStyle::Dotted => {
+            // One dot per module of the ring
+            (0..7)
+                .flat_map(|row| (0..7).map(move |col| (col, row)))
+                .filter(|&(col, row)| col == 0 || col == 6 || row == 0 || row == 6)
+                .fold(Path::new(), |path, (col, row)| {
+                    path.circle(col as f32 + 0.5, row as f32 + 0.5, 0.45)
+                })
+        }
+        EyeFrameStyle::Diamond => ring(
+            Path::new().polygon(&diamond(3.5)),
+            Path::new().polygon(&diamond(2.5)),
+        ),
+    };
+
+    path.rotated(quarter_turns, CENTER).transformed(module_size, x, y)
+}
+
+/// Outline of an eye pupil in pixel coordinates
+///
+/// # Arguments
+/// * `style` - Pupil shape
+/// * `quarter_turns` - Clockwise rotation in quarter turns
+/// * `x`, `y` - Pixel position of the eye's top-left module
+/// * `module_size` - Size of one module in pixels
+pub fn pupil_path(style: EyePupilStyle, quarter_turns: u8, x: f32, y: f32, module_size: f32) -> Path {
+    let path = match style {
+        EyePupilStyle::Square => Path::new().rect(2.0, 2.0, 3.0, 3.0),
+        EyePupilStyle::Rounded => Path::new().rounded_rect(2.0, 2.0, 3.0, 3.0, [1.0; 4]),
+        EyePupilStyle::Circle => Path::new().circle(3.5, 3.5, 1.5),
+        EyePupilStyle::Diamond => Path::new().polygon(&diamond(1.5)),
+        EyePupilStyle::Star => Path::new().polygon(&star(1.6, 0.7)),
+    };
+
+    path.rotated(quarter_turns, CENTER).transformed(module_size, x, y)
+}
+
+/// Outline of a whole alignment pattern (ring and center) in pixel coordinates
+///
+/// `PatternStyle::MatchModules` patterns are drawn module by module instead
+/// and get the solid outline here.
+///
+/// # Arguments
+/// * `style` - Pattern shape
+/// * `x`, `y` - Pixel position of the pattern's top-left module
+/// * `module_size` - Size of one module in pixels
+pub fn alignment_path(style: PatternStyle, x: f32, y: f32, module_size: f32) -> Path {
+    let path = match style {
+        PatternStyle::Solid | PatternStyle::MatchModules => ring(
+            Path::new().rect(0.0, 0.0, 5.0, 5.0),
+            Path::new().rect(1.0, 1.0, 3.0, 3.0),
+        )
+        .rect(2.0, 2.0, 1.0, 1.0),
+        PatternStyle::Rounded => ring(
+            Path::new().rounded_rect(0.0, 0.0, 5.0, 5.0, [0.75; 4]),
+            Path::new().rounded_rect(1.0, 1.0, 3.0, 3.0, [0.25; 4]),
+        )
+        .rounded_rect(2.0, 2.0, 1.0, 1.0, [0.25; 4]),
+        PatternStyle::Circle => ring(
+            Path::new().circle(2.5, 2.5, 2.5),
+            Path::new().circle(2.5, 2.5, 1.5),
+        )
+        .circle(2.5, 2.5, 0.5),
+    };
+
+    path.transformed(module_size, x, y)
+}
+
+/// Outer outline with the inner outline cut out
+fn ring(outer: Path, inner: Path) -> Path {
+    outer.append(inner.reversed())
+}
+
+/// Corners of a diamond centered in the eye (clockwise from the top)
+fn diamond(radius: f32) -> [Point; 4] {
+    [
+        (3.5, 3.5 - radius),
+        (3.5 + radius, 3.5),
+        (3.5, 3.5 + radius),
+        (3.5 - radius, 3.5),
+    ]
+}
+
+/// Points of a five-pointed star centered in the eye, pointing up
+fn star(outer: f32, inner: f32) -> Vec<Point> {
+    (0..10)
+        .map(|i| {
+            let radius = if i % 2 == 0 { outer } else { inner };
+            let angle = std::f32::consts::PI * (i as f32 / 5.0 - 0.5);
+            (3.5 + radius * angle.cos(), 3.5 + radius * angle.sin())
+        })
+        .collect()
+}
@@ -5,23 +5,30 @@
 //! eye patterns, logos, and background images.
 
 use image::{ImageBuffer, Rgba, RgbaImage, imageops};
-use qrcode::QrCode;
+use crate::qr::spec::QrSpec;
+use crate::qr::{drawing, encoding, images, swiss};
+use crate::qr::matrix::{ModuleKind, QrMatrix};
+use crate::types::{EyePosition, PatternStyle, SizingMode};
 
-use crate::app::QrCodeApp;
-use crate::qr::{drawing, images};
+/// Supersampling factor used to render fractional module sizes
+const SUPERSAMPLE: u32 = 4;
 
-/// Generate a fully styled QR code image based on application settings
+/// Largest intermediate image side (pixels) used for supersampling
+const MAX_SUPERSAMPLED_SIZE: u32 = 4096;
+
+/// Generate a fully styled QR code image based on a rendering specification
 ///
 /// This is the main entry point for QR code creation. It:
-/// 1. Generates the base QR code matrix
+/// 1. Generates the base QR code matrix (see `encoding::encode`)
 /// 2. Creates an appropriately sized image buffer
 /// 3. Applies background image (if present)
 /// 4. Draws all QR modules with the selected style
 /// 5. Applies logo overlay (if present)
 /// 6. Applies opacity settings
+/// 7. Resamples to the exact requested size (`SizingMode::Exact` only)
 ///
 /// # Arguments
-/// * `app` - Application state containing all QR code settings
+/// * `spec` - Rendering specification containing all QR code settings
 ///
 /// # Returns
 /// * `Ok(RgbaImage)` - Successfully generated QR code image
@@ -32,99 +39,351 @@ use crate::qr::{drawing, images};
 /// - QR code content is invalid or too long
 /// - Image operations fail
 /// - Logo overlay fails
-pub fn generate_qr_image(app: &QrCodeApp) -> Result<RgbaImage, String> {
+pub fn generate_qr_image(spec: &QrSpec) -> Result<RgbaImage, String> {
     // === Step 1: Generate QR Code Matrix ===
-    let code = QrCode::with_error_correction_level(
-        &app.qr_text, 
-        app.ec_level.to_ec_level()
-    ).map_err(|e| format!("Failed to create QR code: {}", e))?;
-
-    let matrix = code.to_colors();
-    let qr_width = code.width();
+    let matrix = encoding::encode(spec)?.matrix;
 
     // === Step 2: Calculate Dimensions ===
-    // Module size in pixels (how big each black/white square is)
-    let module_size = (app.size - 2 * app.border * (app.size / qr_width as u32)) / qr_width as u32;
-    let actual_qr_size = module_size * qr_width as u32;
-    let total_size = actual_qr_size + 2 * app.border * module_size;
+    let layout = Layout::new(spec, matrix.width(), matrix.height());
+
+    if layout.is_integral() {
+        let offset = (layout.offset_x as u32, layout.offset_y as u32);
+        return render_modules(
+            spec, &matrix, layout.module_size as u32, offset, (layout.width, layout.height)
+        );
+    }
+
+    // === Step 3: Fractional Modules ===
+    // Render whole-pixel modules at a higher resolution and resample to the
+    // exact size, which places module edges at fractional positions with
+    // anti-aliasing
+    let columns = matrix.width() as u32 + 2 * spec.border;
+    let rows = matrix.height() as u32 + 2 * spec.border;
+    let base = layout.module_size.ceil() as u32;
+    let factor = (MAX_SUPERSAMPLED_SIZE / (base * columns.max(rows))).clamp(1, SUPERSAMPLE);
+    let module_size = base * factor;
+    let border = spec.border * module_size;
+    let large = render_modules(
+        spec, &matrix, module_size, (border, border), (module_size * columns, module_size * rows)
+    )?;
+
+    Ok(imageops::resize(
+        &large,
+        layout.width,
+        layout.height,
+        imageops::FilterType::Triangle,
+    ))
+}
 
-    // === Step 3: Create Base Image ===
-    let mut image: RgbaImage = if let Some(bg_img) = &app.bg_image {
+/// Generate the QR code image with a transparent background
+///
+/// The code is rendered once on black and once on white. A pixel that
+/// looks the same on both is fully covered; the difference between the two
+/// gives the coverage of anti-aliased edges and translucent logo pixels, so
+/// they keep smooth alpha instead of a fringe in the background color.
+/// The overall opacity then scales the coverage, while the Swiss cross of
+/// a QR-bill stays opaque.
+///
+/// # Arguments
+/// * `spec` - Rendering specification (`bg_color` only colors nothing)
+///
+/// # Returns
+/// * `Ok(RgbaImage)` - QR code whose light areas are fully transparent
+/// * `Err(String)` - Error message if a background image is set or the QR code cannot be created
+pub fn generate_transparent_qr_image(spec: &QrSpec) -> Result<RgbaImage, String> {
+    if spec.bg_image.is_some() {
+        return Err("A transparent background cannot be combined with a background image".to_string());
+    }
+
+    // === Step 1: Render on Black and on White ===
+    let on = |bg_color| QrSpec { bg_color, qr_opacity: 1.0, ..spec.clone() };
+    let dark = generate_qr_image(&on([0, 0, 0]))?;
+    let light = generate_qr_image(&on([255, 255, 255]))?;
+
+    // === Step 2: Recover Coverage and Color ===
+    let opacity = spec.qr_opacity.clamp(0.0, 1.0);
+    let mut image = RgbaImage::new(dark.width(), dark.height());
+    for ((pixel, d), l) in image.pixels_mut().zip(dark.pixels()).zip(light.pixels()) {
+        let spread = (0..3).map(|c| l[c].saturating_sub(d[c])).max().unwrap_or(0);
+        let alpha = 255 - spread as u32;
+        if alpha == 0 {
+            continue;
+        }
+        // On black the pixel is its color premultiplied by its coverage
+        let color = |c: usize| ((d[c] as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+        let alpha = (alpha as f32 * opacity).round() as u8;
+        *pixel = Rgba([color(0), color(1), color(2), alpha]);
+    }
+
+    // === Step 3: Restore the Swiss Cross ===
+    if swiss::uses_cross(spec) && opacity < 1.0 {
+        let matrix = encoding::encode(spec)?.matrix;
+        let layout = Layout::new(spec, matrix.width(), matrix.height());
+        swiss::draw_cross(&mut image, &layout, (matrix.width(), matrix.height()));
+    }
+
+    Ok(image)
+}
+
+/// Draw background, modules, logo, opacity and Swiss cross with whole-pixel modules
+///
+/// # Arguments
+/// * `spec` - Rendering specification
+/// * `matrix` - Encoded QR matrix
+/// * `module_size` - Size of one module in pixels
+/// * `(offset_x, offset_y)` - Distance from the left and top image edges to the first module in pixels
+/// * `(width, height)` - Size of the image in pixels
+fn render_modules(
+    spec: &QrSpec,
+    matrix: &QrMatrix,
+    module_size: u32,
+    (offset_x, offset_y): (u32, u32),
+    (width, height): (u32, u32),
+) -> Result<RgbaImage, String> {
+    // === Step 1: Create Base Image ===
+    let mut image: RgbaImage = if let Some(bg_img) = &spec.bg_image {
         // Use background image if provided
-        create_background_with_image(bg_img, total_size, app)
+        create_background_with_image(bg_img, width, height, spec)
     } else {
         // Otherwise, solid color background
-        create_solid_background(total_size, app.bg_color)
+        create_solid_background(width, height, spec.bg_color)
     };
 
-    // === Step 4: Identify Eye (Finder Pattern) Positions ===
-    // Eyes are the three 7x7 squares in the corners
-    let eye_positions = vec![
-        (0, 0),                    // Top-left
-        (qr_width - 7, 0),         // Top-right
-        (0, qr_width - 7),         // Bottom-left
-    ];
-
-    // === Step 5: Draw All QR Modules ===
-    let offset = app.border * module_size;
-    
-    for y in 0..qr_width {
-        for x in 0..qr_width {
+    // === Step 2: Draw All Data Modules ===
+    let whole_alignment = spec.alignment_style != PatternStyle::MatchModules;
+    for y in 0..matrix.height() {
+        for x in 0..matrix.width() {
             // Only draw dark modules (white modules are already background)
-            let is_dark = matches!(matrix[y * qr_width + x], qrcode::Color::Dark);
-            
-            if is_dark {
-                let px = offset + x as u32 * module_size;
-                let py = offset + y as u32 * module_size;
-                
-                // Check if this module is part of a finder pattern
-                let is_eye = eye_positions.iter().any(|(ex, ey)| {
-                    x >= *ex && x < ex + 7 && y >= *ey && y < ey + 7
-                });
-                
-                if is_eye {
-                    // Use eye-specific drawing
-                    drawing::draw_eye_module(
-                        &mut image, app, x, y, px, py, 
-                        module_size, &eye_positions
-                    );
-                } else {
-                    // Use data module drawing
-                    drawing::draw_data_module(
-                        &mut image, app, x, y, px, py, 
-                        module_size
-                    );
+            if !matrix.is_dark(x as isize, y as isize) {
+                continue;
+            }
+            let px = offset_x + x as u32 * module_size;
+            let py = offset_y + y as u32 * module_size;
+
+            match matrix.kind(x, y) {
+                // Finder and alignment patterns are drawn as whole shapes below
+                ModuleKind::Finder => {}
+                ModuleKind::Alignment if whole_alignment => {}
+                ModuleKind::Timing if spec.timing_style != PatternStyle::MatchModules => {
+                    drawing::draw_timing_module(&mut image, spec, px, py, module_size);
                 }
+                // Connected styles look at the neighbors
+                _ => drawing::draw_data_module(
+                    &mut image, spec, matrix, x, y, px, py,
+                    module_size
+                ),
             }
         }
     }
 
-    // === Step 6: Apply Logo Overlay ===
-    if let Some(logo_img) = &app.logo_image {
+    if whole_alignment {
+        for &(ax, ay) in matrix.alignment_positions() {
+            let px = offset_x + ax as u32 * module_size;
+            let py = offset_y + ay as u32 * module_size;
+            drawing::draw_alignment(&mut image, spec, px, py, module_size);
+        }
+    }
+
+    // === Step 3: Draw Eyes (Finder Patterns) ===
+    for (&(ex, ey), position) in matrix.eye_positions().iter().zip(EyePosition::ALL) {
+        let px = offset_x + ex as u32 * module_size;
+        let py = offset_y + ey as u32 * module_size;
+        drawing::draw_eye(&mut image, spec, position, px, py, module_size);
+    }
+
+    // === Step 4: Apply Logo Overlay ===
+    if let Some(logo_img) = &spec.logo_image {
         images::apply_logo_overlay(
-            &mut image, logo_img, qr_width, 
-            module_size, offset, app.logo_size
+            &mut image, logo_img, (matrix.width(), matrix.height()),
+            module_size, (offset_x, offset_y), spec.logo_size
         )?;
     }
 
-    // === Step 7: Apply Overall Opacity ===
-    if app.qr_opacity < 1.0 {
-        apply_qr_opacity(&mut image, app.qr_opacity, app.bg_color);
+    // === Step 5: Apply Overall Opacity ===
+    if spec.qr_opacity < 1.0 {
+        apply_qr_opacity(&mut image, spec.qr_opacity, spec.bg_color);
+    }
+
+    // === Step 6: Draw the Swiss Cross ===
+    // Drawn last so no styling (colors, opacity) changes the mandated mark
+    if swiss::uses_cross(spec) {
+        let layout = Layout {
+            module_size: module_size as f32,
+            offset_x: offset_x as f32,
+            offset_y: offset_y as f32,
+            width,
+            height,
+        };
+        swiss::draw_cross(&mut image, &layout, (matrix.width(), matrix.height()));
     }
 
     Ok(image)
 }
 
+/// Pixel geometry of a rendered QR code
+///
+/// Shared by the raster and vector renderers (and the analyses that need
+/// to know where modules end up) so all of them agree. For rectangular
+/// symbols (rMQR) the requested size applies to the longer side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    /// Width and height of one module in pixels (fractional in `SizingMode::Exact`)
+    pub module_size: f32,
+    /// Distance from the left image edge to the first module (border plus any padding)
+    pub offset_x: f32,
+    /// Distance from the top image edge to the first module (border plus any padding)
+    pub offset_y: f32,
+    /// Width of the output image in pixels
+    pub width: u32,
+    /// Height of the output image in pixels
+    pub height: u32,
+}
+
+impl Layout {
+    /// Compute the layout of a QR matrix for the spec's size, border and sizing mode
+    ///
+    /// # Arguments
+    /// * `spec` - Rendering specification
+    /// * `columns` - Width of the QR matrix in modules
+    /// * `rows` - Height of the QR matrix in modules
+    pub fn new(spec: &QrSpec, columns: usize, rows: usize) -> Self {
+        let longest = columns.max(rows);
+        let modules = longest as u32 + 2 * spec.border;
+        let span = |count: usize, module_size: f32| (count as u32 + 2 * spec.border) as f32 * module_size;
+
+        // Size of each side; the longer one gets the requested size
+        let (module_size, width, height) = match spec.sizing_mode {
+            SizingMode::FitModules => {
+                let (module_size, _) = compute_layout(spec.size, spec.border, longest);
+                let module_size = module_size as f32;
+                (module_size, span(columns, module_size) as u32, span(rows, module_size) as u32)
+            }
+            SizingMode::PadToSize => {
+                let module_size = (spec.size / modules).max(1) as f32;
+                let side = |count: usize| match count == longest {
+                    true => spec.size.max(span(count, module_size) as u32),
+                    false => span(count, module_size) as u32,
+                };
+                (module_size, side(columns), side(rows))
+            }
+            SizingMode::Exact => {
+                let module_size = spec.size as f32 / modules as f32;
+                let side = |count: usize| match count == longest {
+                    true => spec.size,
+                    false => span(count, module_size).round() as u32,
+                };
+                (module_size, side(columns), side(rows))
+            }
+        };
+
+        // Center the modules on each side
+        let offset = |side: u32, count: usize| {
+            let offset = (side as f32 - count as f32 * module_size) / 2.0;
+            if module_size.fract() == 0.0 { offset.floor() } else { offset }
+        };
+        Self {
+            module_size,
+            offset_x: offset(width, columns),
+            offset_y: offset(height, rows),
+            width,
+            height,
+        }
+    }
+
+    /// Whether modules fall on whole pixels
+    pub fn is_integral(&self) -> bool {
+        self.module_size.fract() == 0.0 && self.offset_x.fract() == 0.0 && self.offset_y.fract() == 0.0
+    }
+
+    /// Top-left corner and size of the logo square in pixels
+    ///
+    /// Matches `images::apply_logo_overlay`, which rounds down to whole
+    /// pixels when modules are whole pixels. The logo is sized relative to
+    /// the shorter side of the symbol.
+    ///
+    /// # Arguments
+    /// * `columns` - Width of the QR matrix in modules
+    /// * `rows` - Height of the QR matrix in modules
+    /// * `ratio` - Logo size as fraction of the QR code
+    ///
+    /// # Returns
+    /// `(x, y, size)` - Position of the logo's top-left corner and its side length
+    pub fn logo_rect(&self, columns: usize, rows: usize, ratio: f32) -> (f32, f32, f32) {
+        let qr_width = self.module_size * columns as f32;
+        let qr_height = self.module_size * rows as f32;
+        let logo_size = qr_width.min(qr_height) * ratio;
+        if self.is_integral() {
+            let logo_size = logo_size.floor();
+            (
+                self.offset_x + ((qr_width - logo_size) / 2.0).floor(),
+                self.offset_y + ((qr_height - logo_size) / 2.0).floor(),
+                logo_size,
+            )
+        } else {
+            (
+                self.offset_x + (qr_width - logo_size) / 2.0,
+                self.offset_y + (qr_height - logo_size) / 2.0,
+                logo_size,
+            )
+        }
+    }
+}
+
+/// Calculate whole-pixel module and image dimensions for a QR matrix
+///
+/// Used by `SizingMode::FitModules`; the image may come out smaller than
+/// `size`. Modules are at least one pixel, so a border too wide for the
+/// size gives an image larger than requested rather than no image. See
+/// `Layout` for the other sizing modes.
+///
+/// # Arguments
+/// * `size` - Requested output size in pixels
+/// * `border` - Border (quiet zone) width in modules
+/// * `qr_width` - Width of the QR matrix in modules
+///
+/// # Returns
+/// `(module_size, total_size)` - Pixel size of one module and of the whole image
+pub fn compute_layout(size: u32, border: u32, qr_width: usize) -> (u32, u32) {
+    // Module size in pixels (how big each black/white square is); the
+    // border counts as modules, so it scales with them
+    let modules = qr_width as u32 + 2 * border;
+    let module_size = (size / modules).max(1);
+    (module_size, module_size * modules)
+}
+
+/// Calculate the output image size without rendering
+///
+/// # Returns
+/// * `Ok((width, height))` - Size of the generated image in pixels
+/// * `Err(String)` - Error message if the QR code cannot be created
+pub fn output_size(spec: &QrSpec) -> Result<(u32, u32), String> {
+    let matrix = encoding::encode(spec)?.matrix;
+    let layout = Layout::new(spec, matrix.width(), matrix.height());
+    Ok((layout.width, layout.height))
+}
+
+/// Top-left module coordinates of the three finder patterns (eyes)
+///
+/// Eyes are the three 7x7 squares in the corners, listed in the order of
+/// `EyePosition::ALL`.
+pub fn eye_positions(qr_width: usize) -> Vec<(usize, usize)> {
+    vec![
+        (0, 0),                    // Top-left
+        (qr_width - 7, 0),         // Top-right
+        (0, qr_width - 7),         // Bottom-left
+    ]
+}
+
 /// Create a solid color background image
 ///
 /// # Arguments
-/// * `size` - Image dimensions (square)
+/// * `width`, `height` - Image dimensions
 /// * `bg_color` - RGB color for background
 ///
 /// # Returns
 /// RGBA image filled with the specified color
-fn create_solid_background(size: u32, bg_color: [u8; 3]) -> RgbaImage {
-    let mut img = ImageBuffer::new(size, size);
+fn create_solid_background(width: u32, height: u32, bg_color: [u8; 3]) -> RgbaImage {
+    let mut img = ImageBuffer::new(width, height);
     for pixel in img.pixels_mut() {
         *pixel = Rgba([bg_color[0], bg_color[1], bg_color[2], 255]);
     }
@@ -135,31 +394,32 @@ fn create_solid_background(size: u32, bg_color: [u8; 3]) -> RgbaImage {
 ///
 /// # Arguments
 /// * `bg_img` - Background image to use
-/// * `size` - Target size for the background
-/// * `app` - Application state for opacity and background color
+/// * `width`, `height` - Target size for the background
+/// * `spec` - Rendering specification for opacity and background color
 ///
 /// # Returns
 /// RGBA image with blended background
 fn create_background_with_image(
     bg_img: &image::DynamicImage, 
-    size: u32, 
-    app: &QrCodeApp
+    width: u32,
+    height: u32,
+    spec: &QrSpec
 ) -> RgbaImage {
     // Resize background image to match QR code size
-    let resized = bg_img.resize_exact(size, size, imageops::FilterType::Lanczos3);
+    let resized = bg_img.resize_exact(width, height, imageops::FilterType::Lanczos3);
     let mut img_buffer = resized.to_rgba8();
     
     // Apply opacity to background image
-    if app.bg_image_opacity < 1.0 {
+    if spec.bg_image_opacity < 1.0 {
         for pixel in img_buffer.pixels_mut() {
-            pixel[3] = (pixel[3] as f32 * app.bg_image_opacity) as u8;
+            pixel[3] = (pixel[3] as f32 * spec.bg_image_opacity) as u8;
         }
     }
     
     // Create solid color base
-    let mut base = ImageBuffer::new(size, size);
+    let mut base = ImageBuffer::new(width, height);
     for pixel in base.pixels_mut() {
-        *pixel = Rgba([app.bg_color[0], app.bg_color[1], app.bg_color[2], 255]);
+        *pixel = Rgba([spec.bg_color[0], spec.bg_color[1], spec.bg_color[2], 255]);
     }
     
     // Blend background image over solid color
@@ -183,3 +443,115 @@ fn apply_qr_opacity(image: &mut RgbaImage, opacity: f32, bg_color: [u8; 3]) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SymbolType;
+
+    fn spec_with(sizing_mode: SizingMode) -> QrSpec {
+        QrSpec { size: 512, sizing_mode, ..Default::default() }
+    }
+
+    #[test]
+    fn test_sizing_modes_output_size() {
+        // Default content is version 3 (29 modules) plus a 2-module border
+        let fit = generate_qr_image(&spec_with(SizingMode::FitModules)).unwrap();
+        assert!(fit.width() < 512);
+
+        for mode in [SizingMode::PadToSize, SizingMode::Exact] {
+            let image = generate_qr_image(&spec_with(mode)).unwrap();
+            assert_eq!(image.dimensions(), (512, 512), "{:?}", mode);
+            assert_eq!(output_size(&spec_with(mode)), Ok((512, 512)));
+        }
+    }
+
+    #[test]
+    fn test_layout_geometry() {
+        let pad = Layout::new(&spec_with(SizingMode::PadToSize), 29, 29);
+        assert!(pad.is_integral());
+        assert_eq!(pad.module_size, 15.0);
+        // 29 modules of 15px centered on 512px
+        assert_eq!(pad.offset_x, ((512 - 29 * 15) / 2) as f32);
+        assert_eq!(pad.offset_y, pad.offset_x);
+
+        let exact = Layout::new(&spec_with(SizingMode::Exact), 29, 29);
+        assert!(!exact.is_integral());
+        assert!((exact.module_size * 33.0 - 512.0).abs() < 1e-3);
+        assert!((exact.offset_x - 2.0 * exact.module_size).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rectangular_output() {
+        // R11x27: the width gets the requested size, the height follows
+        let spec = QrSpec {
+            qr_text: "12345".to_string(),
+            symbol_type: SymbolType::Rectangular,
+            ..spec_with(SizingMode::PadToSize)
+        };
+        let image = generate_qr_image(&spec).unwrap();
+        assert_eq!(image.dimensions(), (512, 15 * 16));
+        assert_eq!(output_size(&spec), Ok(image.dimensions()));
+
+        let exact = generate_qr_image(&QrSpec { sizing_mode: SizingMode::Exact, ..spec }).unwrap();
+        assert_eq!(exact.width(), 512);
+        assert_eq!(exact.height(), (512.0_f32 * 15.0 / 31.0).round() as u32);
+    }
+
+    #[test]
+    fn test_transparent_background() {
+        let spec = QrSpec {
+            fg_color: [20, 60, 160],
+            bg_color: [250, 240, 200],
+            module_style: crate::types::ModuleStyle::Dots,
+            ..spec_with(SizingMode::Exact)
+        };
+        let opaque = generate_qr_image(&spec).unwrap();
+        let image = generate_transparent_qr_image(&spec).unwrap();
+        assert_eq!(image.dimensions(), opaque.dimensions());
+
+        // The border is empty and the top-left eye is solid module color
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0, 0]);
+        let eye = (2.5 * 512.0 / 33.0) as u32;
+        assert_eq!(image.get_pixel(eye, eye).0, [20, 60, 160, 255]);
+        // Anti-aliased dot edges are partially covered, not background colored
+        assert!(image.pixels().any(|p| p[3] > 0 && p[3] < 255 && p.0[..3] == [20, 60, 160]));
+
+        let faded = generate_transparent_qr_image(&QrSpec { qr_opacity: 0.5, ..spec.clone() }).unwrap();
+        assert_eq!(faded.get_pixel(eye, eye).0, [20, 60, 160, 128]);
+
+        let with_background = QrSpec { bg_image: Some(image::DynamicImage::new_rgb8(4, 4)), ..spec };
+        assert!(generate_transparent_qr_image(&with_background).is_err());
+    }
+
+    #[test]
+    fn test_wide_border_on_small_size() {
+        // 100px cannot hold 21 modules plus a 50-module border: one pixel per module
+        assert_eq!(compute_layout(100, 50, 21), (1, 121));
+        assert_eq!(compute_layout(512, 2, 29), (15, 495));
+
+        let spec = QrSpec { qr_text: "hi".to_string(), size: 100, border: 50, ..spec_with(SizingMode::FitModules) };
+        assert_eq!(generate_qr_image(&spec).unwrap().dimensions(), (121, 121));
+    }
+
+    #[test]
+    fn test_small_symbols_with_widest_border() {
+        // Smallest size and widest border of the GUI sliders
+        for symbol_type in [SymbolType::Micro, SymbolType::Rectangular] {
+            for sizing_mode in [SizingMode::FitModules, SizingMode::PadToSize, SizingMode::Exact] {
+                let spec = QrSpec {
+                    qr_text: "1".to_string(),
+                    symbol_type,
+                    size: 128,
+                    border: 10,
+                    ..spec_with(sizing_mode)
+                };
+                let image = generate_qr_image(&spec).unwrap();
+                // Whole-pixel modules may leave the longer side short of the
+                // requested size, but only by a fraction of a module each
+                let longest = image.width().max(image.height());
+                assert!(longest <= 128 && longest * 3 >= 128 * 2, "{:?} {:?}: {:?}", symbol_type, sizing_mode, image.dimensions());
+            }
+        }
+    }
+}
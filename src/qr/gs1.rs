@@ -0,0 +1,394 @@
+//! GS1 Application Identifier parsing and validation
+//!
+//! Supply-chain codes carry GS1 data as a list of Application Identifiers
+//! (AIs), each followed by its value. Two notations are accepted:
+//! - Element strings in human-readable form, e.g.
+//!   `(01)09501101530003(17)251231(10)LOT42`. They are encoded with FNC1
+//!   in the first position, with a field separator after every
+//!   variable-length value that is not last. Parentheses inside a value
+//!   are kept unless they enclose a known AI.
+//! - GS1 Digital Link URIs, e.g.
+//!   `https://id.gs1.org/01/09501101530003/10/LOT42?17=251231`. They are
+//!   plain URLs and are encoded as text.
+//!
+//! Values are checked against the format of their AI: length, digits,
+//! GS1 check digits, YYMMDD dates and the GS1 character set.
+
+use crate::qr::spec::QrSpec;
+use crate::types::ContentMode;
+
+/// Group separator that stands for FNC1 between element string fields
+pub const GROUP_SEPARATOR: char = '\u{1D}';
+
+/// Symbols allowed in values besides digits and letters (GS1 character set 82)
+const GS1_SYMBOLS: &str = "!\"%&'()*+,-./:;<=>?_";
+
+/// Two-digit AI prefixes whose values have a predefined length, so no
+/// separator follows them (GS1 General Specifications, Figure 7.8.4-2)
+const PREDEFINED_LENGTH_PREFIXES: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "31", "32",
+    "33", "34", "35", "36", "41",
+];
+
+/// Value format of an Application Identifier
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    /// Exactly this many digits
+    Digits(usize),
+    /// Exactly this many digits, the last being a GS1 check digit
+    CheckDigits(usize),
+    /// One to this many digits
+    DigitsUpTo(usize),
+    /// Date as YYMMDD (DD may be 00 for "end of month")
+    Date,
+    /// One to this many characters of the GS1 character set
+    Text(usize),
+}
+
+/// Application Identifiers with their titles and value formats
+///
+/// Measures (310n-369n, with n decimal places) and company internal AIs
+/// (91-99) are handled separately.
+const AIS: [(&str, &str, Format); 36] = [
+    ("00", "SSCC", Format::CheckDigits(18)),
+    ("01", "GTIN", Format::CheckDigits(14)),
+    ("02", "Content GTIN", Format::CheckDigits(14)),
+    ("10", "Batch/lot", Format::Text(20)),
+    ("11", "Production date", Format::Date),
+    ("12", "Due date", Format::Date),
+    ("13", "Packaging date", Format::Date),
+    ("15", "Best before", Format::Date),
+    ("16", "Sell by", Format::Date),
+    ("17", "Expiration date", Format::Date),
+    ("20", "Variant", Format::Digits(2)),
+    ("21", "Serial number", Format::Text(20)),
+    ("22", "Consumer product variant", Format::Text(20)),
+    ("240", "Additional product ID", Format::Text(30)),
+    ("241", "Customer part number", Format::Text(30)),
+    ("250", "Secondary serial number", Format::Text(30)),
+    ("251", "Reference to source entity", Format::Text(30)),
+    ("254", "GLN extension", Format::Text(20)),
+    ("30", "Variable count", Format::DigitsUpTo(8)),
+    ("37", "Count of trade items", Format::DigitsUpTo(8)),
+    ("400", "Customer order number", Format::Text(30)),
+    ("401", "Consignment number", Format::Text(30)),
+    ("402", "Shipment ID", Format::CheckDigits(17)),
+    ("403", "Routing code", Format::Text(30)),
+    ("410", "Ship to GLN", Format::CheckDigits(13)),
+    ("411", "Bill to GLN", Format::CheckDigits(13)),
+    ("412", "Purchased from GLN", Format::CheckDigits(13)),
+    ("413", "Ship for GLN", Format::CheckDigits(13)),
+    ("414", "Location GLN", Format::CheckDigits(13)),
+    ("415", "Invoicing party GLN", Format::CheckDigits(13)),
+    ("416", "Production location GLN", Format::CheckDigits(13)),
+    ("420", "Ship to postal code", Format::Text(20)),
+    ("422", "Country of origin", Format::Digits(3)),
+    ("8004", "GIAI", Format::Text(30)),
+    ("8200", "Product URL", Format::Text(70)),
+    ("90", "Mutually agreed information", Format::Text(30)),
+];
+
+/// One Application Identifier and its value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gs1Element {
+    /// Application Identifier, e.g. "01"
+    pub ai: String,
+    /// Name of the AI, e.g. "GTIN"
+    pub title: String,
+    /// Value without the AI
+    pub value: String,
+}
+
+impl Gs1Element {
+    /// Human-readable form, e.g. "(01) 09501101530003"
+    pub fn human_readable(&self) -> String {
+        format!("({}) {}", self.ai, self.value)
+    }
+}
+
+/// Whether GS1 content is a Digital Link URI rather than an element string
+pub fn is_digital_link(text: &str) -> bool {
+    let text = text.trim_start().to_ascii_lowercase();
+    text.starts_with("https://") || text.starts_with("http://")
+}
+
+/// Whether a specification is encoded with FNC1 in the first position
+pub fn uses_fnc1(spec: &QrSpec) -> bool {
+    spec.content_mode == ContentMode::Gs1 && !is_digital_link(&spec.qr_text)
+}
+
+/// Parse and validate GS1 content
+///
+/// # Arguments
+/// * `text` - Element string in parentheses notation or a Digital Link URI
+///
+/// # Returns
+/// * `Ok(Vec<Gs1Element>)` - Validated elements in the given order
+/// * `Err(String)` - Description of the first problem found
+pub fn parse(text: &str) -> Result<Vec<Gs1Element>, String> {
+    let pairs = if is_digital_link(text) {
+        digital_link_pairs(text.trim())?
+    } else {
+        element_string_pairs(text.trim())?
+    };
+    if pairs.is_empty() {
+        return Err("No GS1 Application Identifiers found".to_string());
+    }
+
+    let mut elements: Vec<Gs1Element> = Vec::new();
+    for (ai, value) in pairs {
+        if elements.iter().any(|e| e.ai == ai) {
+            return Err(format!("({}) appears more than once", ai));
+        }
+        let (title, format) = definition(&ai).ok_or_else(|| format!("Unknown Application Identifier ({})", ai))?;
+        validate(&ai, &title, format, &value)?;
+        elements.push(Gs1Element { ai, title, value });
+    }
+    Ok(elements)
+}
+
+/// Element string with separators, as encoded after FNC1
+///
+/// Values of variable length are followed by `GROUP_SEPARATOR` unless
+/// they come last.
+pub fn element_string(elements: &[Gs1Element]) -> String {
+    let mut data = String::new();
+    for (i, element) in elements.iter().enumerate() {
+        data.push_str(&element.ai);
+        data.push_str(&element.value);
+        let predefined = PREDEFINED_LENGTH_PREFIXES.contains(&&element.ai[..2]);
+        if !predefined && i + 1 < elements.len() {
+            data.push(GROUP_SEPARATOR);
+        }
+    }
+    data
+}
+
+/// AI/value pairs of an element string such as "(01)0950...(10)LOT42"
+fn element_string_pairs(text: &str) -> Result<Vec<(String, String)>, String> {
+    let Some(rest) = text.strip_prefix('(') else {
+        return Err("GS1 element strings start with an AI in parentheses, e.g. (01)".to_string());
+    };
+
+    let mut pairs = Vec::new();
+    let mut rest = rest;
+    loop {
+        let Some((ai, after)) = rest.split_once(')') else {
+            return Err(format!("Missing ')' after ({}", rest));
+        };
+        // The value runs up to the next known AI in parentheses; any other
+        // parentheses belong to the value, e.g. (10)LOT(A)
+        let end = after
+            .match_indices('(')
+            .map(|(i, _)| i)
+            .find(|&i| opens_known_ai(&after[i + 1..]))
+            .unwrap_or(after.len());
+        pairs.push((ai.trim().to_string(), after[..end].trim().to_string()));
+        if end == after.len() {
+            return Ok(pairs);
+        }
+        rest = &after[end + 1..];
+    }
+}
+
+/// Whether text following a '(' starts with a known AI and its ')'
+fn opens_known_ai(text: &str) -> bool {
+    text.split_once(')').is_some_and(|(ai, _)| definition(ai.trim()).is_some())
+}
+
+/// AI/value pairs of a Digital Link URI (path segments, then query parameters)
+fn digital_link_pairs(uri: &str) -> Result<Vec<(String, String)>, String> {
+    let after_scheme = uri.split_once("://").map_or(uri, |(_, rest)| rest);
+    let (path, query) = after_scheme.split_once('?').unwrap_or((after_scheme, ""));
+    let segments: Vec<&str> = path.split('/').skip(1).filter(|s| !s.is_empty()).collect();
+
+    // The AI path starts at the first segment that names a known AI; a
+    // domain may put its own prefix segments in front
+    let Some(start) = segments.iter().position(|s| definition(s).is_some()) else {
+        return Err("Digital Link URI has no GS1 Application Identifier in its path".to_string());
+    };
+    let path_pairs = &segments[start..];
+    if !path_pairs.len().is_multiple_of(2) {
+        return Err(format!("Digital Link path ends with AI ({}) but no value", path_pairs[path_pairs.len() - 1]));
+    }
+
+    let mut pairs: Vec<(String, String)> = path_pairs
+        .chunks(2)
+        .map(|pair| Ok((pair[0].to_string(), percent_decode(pair[1])?)))
+        .collect::<Result<_, String>>()?;
+    for parameter in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+        // Only numeric keys are AIs; other parameters are left to the resolver
+        if !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit()) {
+            pairs.push((key.to_string(), percent_decode(value)?));
+        }
+    }
+    Ok(pairs)
+}
+
+/// Decode %XX escapes in a URI component
+fn percent_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = text.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok());
+            decoded.push(hex.ok_or_else(|| format!("Invalid escape in '{}'", text))?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format!("Invalid escape in '{}'", text))
+}
+
+/// Title and format of an AI
+fn definition(ai: &str) -> Option<(String, Format)> {
+    if !ai.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if let Some((_, title, format)) = AIS.iter().find(|(code, _, _)| *code == ai) {
+        return Some((title.to_string(), *format));
+    }
+    if ai.len() == 2 && ("91"..="99").contains(&ai) {
+        return Some(("Company internal".to_string(), Format::Text(90)));
+    }
+
+    // Trade measures: 3-digit measure type plus the number of decimal places
+    let measure = match ai.get(..3)? {
+        "310" => "Net weight (kg)",
+        "311" => "Length (m)",
+        "320" => "Net weight (lb)",
+        "330" => "Gross weight (kg)",
+        prefix if ("310".."370").contains(&prefix) => "Measure",
+        _ => return None,
+    };
+    let decimals = ai[3..].parse::<u8>().ok().filter(|_| ai.len() == 4)?;
+    Some((format!("{}, {} decimals", measure, decimals), Format::Digits(6)))
+}
+
+/// Check a value against the format of its AI
+fn validate(ai: &str, title: &str, format: Format, value: &str) -> Result<(), String> {
+    let name = format!("({}) {}", ai, title);
+    if value.is_empty() {
+        return Err(format!("{} has no value", name));
+    }
+    let all_digits = value.bytes().all(|b| b.is_ascii_digit());
+
+    match format {
+        Format::Digits(length) | Format::CheckDigits(length) => {
+            if !all_digits || value.len() != length {
+                return Err(format!("{} must be {} digits (found '{}')", name, length, value));
+            }
+            if let Format::CheckDigits(_) = format {
+                let digits: Vec<u8> = value.bytes().map(|b| b - b'0').collect();
+                let (body, check) = digits.split_at(length - 1);
+                let expected = check_digit(body);
+                if check[0] != expected {
+                    return Err(format!("{} check digit should be {} (found {})", name, expected, check[0]));
+                }
+            }
+        }
+        Format::DigitsUpTo(length) => {
+            if !all_digits || value.len() > length {
+                return Err(format!("{} must be 1-{} digits (found '{}')", name, length, value));
+            }
+        }
+        Format::Date => {
+            if !all_digits || value.len() != 6 || !is_valid_date(value) {
+                return Err(format!("{} must be a YYMMDD date (found '{}')", name, value));
+            }
+        }
+        Format::Text(length) => {
+            if value.chars().count() > length {
+                return Err(format!("{} is longer than {} characters", name, length));
+            }
+            let allowed = |c: char| c.is_ascii_alphanumeric() || GS1_SYMBOLS.contains(c);
+            if let Some(c) = value.chars().find(|&c| !allowed(c)) {
+                return Err(format!("{} contains '{}', which is not in the GS1 character set", name, c));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// GS1 check digit (modulo 10, weights 3 and 1 from the right)
+pub fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| d as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Whether six digits form a valid YYMMDD date (day 00 is allowed)
+fn is_valid_date(value: &str) -> bool {
+    let field = |range: std::ops::Range<usize>| value[range].parse::<u32>().unwrap_or(0);
+    let (year, month, day) = (field(0..2), field(2..4), field(4..6));
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 => 29,
+        2 => 28,
+        _ => return false,
+    };
+    day <= days_in_month
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_digits() {
+        let digits = |s: &str| s.bytes().map(|b| b - b'0').collect::<Vec<u8>>();
+        assert_eq!(check_digit(&digits("0950110153000")), 3);
+        assert_eq!(check_digit(&digits("0950600013435")), 2);
+        assert_eq!(check_digit(&digits("00614141123456789")), 0);
+    }
+
+    #[test]
+    fn test_element_string() {
+        let elements = parse("(01)09501101530003(10)LOT42(17)251231(21)S/N-7").unwrap();
+        assert_eq!(elements.len(), 4);
+        assert_eq!(elements[0].title, "GTIN");
+        assert_eq!(elements[1].human_readable(), "(10) LOT42");
+
+        // Separators only follow variable-length values that are not last
+        assert_eq!(element_string(&elements), "010950110153000310LOT42\u{1D}1725123121S/N-7");
+
+        // Parentheses are valid in values; only a known AI starts a new element
+        let elements = parse("(10)LOT(A)(21)(7)").unwrap();
+        let pairs: Vec<(&str, &str)> = elements.iter().map(|e| (e.ai.as_str(), e.value.as_str())).collect();
+        assert_eq!(pairs, [("10", "LOT(A)"), ("21", "(7)")]);
+    }
+
+    #[test]
+    fn test_digital_link() {
+        let elements = parse("https://id.gs1.org/01/09506000134352/10/AB%2F12?17=280229&linkType=pip").unwrap();
+        let pairs: Vec<(&str, &str)> = elements.iter().map(|e| (e.ai.as_str(), e.value.as_str())).collect();
+        assert_eq!(pairs, [("01", "09506000134352"), ("10", "AB/12"), ("17", "280229")]);
+
+        assert!(parse("https://example.com/products/42").is_err());
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let error = |text: &str| parse(text).unwrap_err();
+        assert!(error("(01)09501101530004").contains("check digit should be 3"));
+        assert!(error("(01)9501101530003").contains("must be 14 digits"));
+        assert!(error("(17)250230").contains("YYMMDD"));
+        assert!(error("(10)LOT é").contains("GS1 character set"));
+        assert!(error("(10)A(10)B").contains("more than once"));
+        assert!(error("(09)X").contains("Unknown"));
+        assert!(error("01095011015300").contains("parentheses"));
+
+        // Measures carry the number of decimal places in the AI
+        let weight = parse("(3102)001250").unwrap();
+        assert_eq!(weight[0].title, "Net weight (kg), 2 decimals");
+    }
+}
@@ -6,7 +6,8 @@ use image::{DynamicImage, imageops, RgbaImage};
 
 /// Apply a logo overlay to the center of the QR code
 ///
-/// The logo is resized to the specified size and centered on the QR code.
+/// The logo is resized to the specified size (relative to the shorter side
+/// of rectangular symbols) and centered on the QR code.
 /// Uses alpha blending to preserve logo transparency.
 ///
 /// **Important**: Logos reduce scannability! Use high error correction
@@ -15,9 +16,9 @@ use image::{DynamicImage, imageops, RgbaImage};
 /// # Arguments
 /// * `image` - QR code image to overlay logo onto
 /// * `logo` - Logo image to overlay
-/// * `qr_width` - Width of QR matrix in modules
+/// * `(columns, rows)` - Width and height of QR matrix in modules
 /// * `module_size` - Size of each module in pixels
-/// * `offset` - Border offset in pixels
+/// * `(offset_x, offset_y)` - Border offset in pixels
 /// * `logo_size_ratio` - Logo size as fraction of QR code (0.05-0.35)
 ///
 /// # Returns
@@ -25,20 +26,22 @@ use image::{DynamicImage, imageops, RgbaImage};
 /// * `Err(String)` - Error message if overlay fails
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// // Add a logo that's 20% of the QR code size
-/// apply_logo_overlay(&mut qr_image, &logo, qr_width, module_size, offset, 0.2)?;
+/// apply_logo_overlay(&mut qr_image, &logo, (25, 25), module_size, (offset, offset), 0.2)?;
 /// ```
 pub fn apply_logo_overlay(
     image: &mut RgbaImage, 
     logo: &DynamicImage, 
-    qr_width: usize, 
+    (columns, rows): (usize, usize),
     module_size: u32, 
-    offset: u32,
+    (offset_x, offset_y): (u32, u32),
     logo_size_ratio: f32
 ) -> Result<(), String> {
     // === Step 1: Calculate Logo Dimensions ===
-    let qr_size = qr_width as u32 * module_size;
+    let qr_width = columns as u32 * module_size;
+    let qr_height = rows as u32 * module_size;
+    let qr_size = qr_width.min(qr_height);
     let logo_size = (qr_size as f32 * logo_size_ratio) as u32;
     
     // Validate logo size
@@ -60,8 +63,8 @@ pub fn apply_logo_overlay(
     
     // === Step 3: Calculate Center Position ===
     // Logo is centered within the QR code area (excluding border)
-    let center_x = offset + (qr_size - logo_size) / 2;
-    let center_y = offset + (qr_size - logo_size) / 2;
+    let center_x = offset_x + (qr_width - logo_size) / 2;
+    let center_y = offset_y + (qr_height - logo_size) / 2;
     
     // === Step 4: Overlay with Alpha Blending ===
     // This preserves logo transparency and blends nicely with QR modules
@@ -77,8 +80,6 @@ pub fn apply_logo_overlay(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_logo_size_calculation() {
         let qr_width = 25;
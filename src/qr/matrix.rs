@@ -0,0 +1,358 @@
+//! QR matrix with module classification and neighborhood queries
+//!
+//! Wraps the module colors of an encoded QR code together with the
+//! positions of its function patterns, so every module can be classified
+//! (finder, timing, alignment, ...) and styled by kind. Connected module
+//! styles (liquid, lines) use it to look at the modules around the one
+//! being drawn.
+
+use qrcode::{Color, QrCode, Version};
+
+use crate::qr::generator;
+
+/// Dark modules of the same kind (data or eye) directly next to a module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Neighbors {
+    /// Module above is dark
+    pub top: bool,
+    /// Module to the right is dark
+    pub right: bool,
+    /// Module below is dark
+    pub bottom: bool,
+    /// Module to the left is dark
+    pub left: bool,
+}
+
+/// Role of a module in the symbol (ISO/IEC 18004, Figure 2; ISO/IEC 23941 for rMQR)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleKind {
+    /// Part of a 7x7 finder pattern (eye)
+    Finder,
+    /// Light border around a finder pattern
+    Separator,
+    /// Alternating row or column between the finder patterns (rMQR: also
+    /// the corner patterns and the 3x3 alignment patterns on the edges)
+    Timing,
+    /// Part of a 5x5 alignment pattern (version 2 and up) or the rMQR sub-finder
+    Alignment,
+    /// Error correction level and mask (including the always-dark module)
+    FormatInfo,
+    /// Version number blocks (version 7 and up)
+    VersionInfo,
+    /// Data and error correction codewords
+    Data,
+}
+
+impl ModuleKind {
+    /// Kinds that are styled together (connected styles only join modules of one group)
+    fn group(self) -> ModuleKind {
+        match self {
+            ModuleKind::FormatInfo | ModuleKind::VersionInfo | ModuleKind::Data => ModuleKind::Data,
+            kind => kind,
+        }
+    }
+}
+
+/// Module colors and function pattern positions of an encoded QR code
+///
+/// Standard QR codes are square with three eyes; Micro QR has one eye and
+/// rMQR symbols are rectangular with one eye and a 5x5 sub-finder in the
+/// bottom-right corner (listed with the alignment patterns).
+#[derive(Debug, Clone)]
+pub struct QrMatrix {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+    kinds: Vec<ModuleKind>,
+    eye_positions: Vec<(usize, usize)>,
+    alignment_positions: Vec<(usize, usize)>,
+}
+
+impl QrMatrix {
+    /// Build the matrix for an encoded QR code
+    pub fn new(code: &QrCode) -> Self {
+        Self::from_colors(code.version(), &code.to_colors())
+    }
+
+    /// Build the matrix of a QR or Micro QR symbol from module colors in row-major order
+    pub fn from_colors(version: Version, colors: &[Color]) -> Self {
+        let width = version.width() as usize;
+        let dark = colors.iter().map(|&color| color == Color::Dark).collect();
+
+        let (kinds, eye_positions, alignment_positions) = match version {
+            Version::Normal(number) => {
+                let number = number as usize;
+                let alignment_positions = alignment_positions(number, width);
+                let kinds = (0..width * width)
+                    .map(|i| qr_module_kind(width, number >= 7, &alignment_positions, i % width, i / width))
+                    .collect();
+                (kinds, generator::eye_positions(width), alignment_positions)
+            }
+            Version::Micro(_) => {
+                let kinds = (0..width * width).map(|i| micro_module_kind(i % width, i / width)).collect();
+                (kinds, vec![(0, 0)], Vec::new())
+            }
+        };
+
+        Self { width, height: width, dark, kinds, eye_positions, alignment_positions }
+    }
+
+    /// Build a matrix from already classified modules (used by the rMQR encoder)
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Size of the symbol in modules
+    /// * `dark` - Module colors in row-major order
+    /// * `kinds` - Module roles in row-major order
+    /// * `eye_positions` - Top-left corners of the 7x7 finder patterns
+    /// * `alignment_positions` - Top-left corners of 5x5 alignment-like patterns
+    pub(crate) fn from_parts(
+        width: usize,
+        height: usize,
+        dark: Vec<bool>,
+        kinds: Vec<ModuleKind>,
+        eye_positions: Vec<(usize, usize)>,
+        alignment_positions: Vec<(usize, usize)>,
+    ) -> Self {
+        Self { width, height, dark, kinds, eye_positions, alignment_positions }
+    }
+
+    /// Width of the matrix in modules
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the matrix in modules (equal to the width except for rMQR)
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Top-left module coordinates of the eyes, in the order of `EyePosition::ALL`
+    ///
+    /// Three for standard QR codes, one (top-left) for Micro QR and rMQR.
+    pub fn eye_positions(&self) -> &[(usize, usize)] {
+        &self.eye_positions
+    }
+
+    /// Top-left module coordinates of the 5x5 alignment patterns
+    pub fn alignment_positions(&self) -> &[(usize, usize)] {
+        &self.alignment_positions
+    }
+
+    /// Role of the module at (x, y)
+    pub fn kind(&self, x: usize, y: usize) -> ModuleKind {
+        self.kinds[y * self.width + x]
+    }
+
+    /// Whether the module at (x, y) is dark (modules outside the matrix are light)
+    pub fn is_dark(&self, x: isize, y: isize) -> bool {
+        let inside = (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y);
+        inside && self.dark[y as usize * self.width + x as usize]
+    }
+
+    /// Top-left corner of the eye containing the module at (x, y), if any
+    pub fn eye_origin(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        self.eye_positions
+            .iter()
+            .copied()
+            .find(|&(ex, ey)| x >= ex && x < ex + 7 && y >= ey && y < ey + 7)
+    }
+
+    /// Whether the module at (x, y) belongs to an eye
+    pub fn is_eye(&self, x: usize, y: usize) -> bool {
+        self.eye_origin(x, y).is_some()
+    }
+
+    /// Dark neighbors of the module at (x, y)
+    ///
+    /// Only neighbors of the same kind count, so data modules never merge
+    /// into an eye, timing or alignment pattern (and vice versa). Format and
+    /// version info are styled like data.
+    pub fn neighbors(&self, x: usize, y: usize) -> Neighbors {
+        let group = self.kind(x, y).group();
+        let (x, y) = (x as isize, y as isize);
+        let joins = |nx: isize, ny: isize| {
+            self.is_dark(nx, ny) && self.kind(nx as usize, ny as usize).group() == group
+        };
+
+        Neighbors {
+            top: joins(x, y - 1),
+            right: joins(x + 1, y),
+            bottom: joins(x, y + 1),
+            left: joins(x - 1, y),
+        }
+    }
+}
+
+/// Role of the module at (x, y) in a standard QR code
+fn qr_module_kind(
+    w: usize,
+    has_version_info: bool,
+    alignment_positions: &[(usize, usize)],
+    x: usize,
+    y: usize,
+) -> ModuleKind {
+    let in_corner = |limit: usize| {
+        (y < limit && (x < limit || x >= w - limit)) || (x < limit && y >= w - limit)
+    };
+
+    if in_corner(7) {
+        ModuleKind::Finder
+    } else if in_corner(8) {
+        ModuleKind::Separator
+    } else if (x == 8 && (y < 9 || y >= w - 8)) || (y == 8 && (x < 9 || x >= w - 8)) {
+        ModuleKind::FormatInfo
+    } else if has_version_info
+        && ((x >= w - 11 && x < w - 8 && y < 6) || (y >= w - 11 && y < w - 8 && x < 6))
+    {
+        ModuleKind::VersionInfo
+    } else if alignment_positions
+        .iter()
+        .any(|&(ax, ay)| x >= ax && x < ax + 5 && y >= ay && y < ay + 5)
+    {
+        ModuleKind::Alignment
+    } else if x == 6 || y == 6 {
+        ModuleKind::Timing
+    } else {
+        ModuleKind::Data
+    }
+}
+
+/// Role of the module at (x, y) in a Micro QR symbol
+///
+/// The single finder sits in the top-left corner; timing runs along the
+/// top row and left column, and format information wraps around the
+/// separator (ISO/IEC 18004, Figure 3).
+fn micro_module_kind(x: usize, y: usize) -> ModuleKind {
+    if x < 7 && y < 7 {
+        ModuleKind::Finder
+    } else if x < 8 && y < 8 {
+        ModuleKind::Separator
+    } else if (x == 8 && (1..=8).contains(&y)) || (y == 8 && (1..=8).contains(&x)) {
+        ModuleKind::FormatInfo
+    } else if x == 0 || y == 0 {
+        ModuleKind::Timing
+    } else {
+        ModuleKind::Data
+    }
+}
+
+/// Top-left corners of the alignment patterns for a QR version
+///
+/// Pattern centers lie on a grid starting at row/column 6 and ending 7
+/// modules before the far edge, evenly spaced with an even step (ISO/IEC
+/// 18004, Annex E); centers that would overlap a finder pattern are skipped.
+fn alignment_positions(version: usize, width: usize) -> Vec<(usize, usize)> {
+    if version < 2 {
+        return Vec::new();
+    }
+
+    let count = version / 7 + 2;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+    let last = width - 7;
+    let mut centers: Vec<usize> = (0..count - 1).map(|i| last - i * step).collect();
+    centers.push(6);
+    centers.reverse();
+
+    let mut positions = Vec::new();
+    for &cy in &centers {
+        for &cx in &centers {
+            let in_finder = (cx == 6 && (cy == 6 || cy == last)) || (cx == last && cy == 6);
+            if !in_finder {
+                positions.push((cx - 2, cy - 2));
+            }
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbors_stay_within_kind() {
+        let code = QrCode::new("https://example.com").unwrap();
+        let matrix = QrMatrix::new(&code);
+
+        // Top-left eye corner: dark ring continues right and down, nothing outside
+        let corner = matrix.neighbors(0, 0);
+        assert!(corner.right && corner.bottom);
+        assert!(!corner.top && !corner.left);
+
+        // Data modules next to an eye never join it
+        for y in 0..matrix.width() {
+            if matrix.is_eye(6, y) && !matrix.is_eye(7, y) {
+                assert!(!matrix.neighbors(7, y).left, "module (7, {}) joined the eye", y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alignment_centers_match_spec() {
+        let centers = |version: usize| -> Vec<usize> {
+            let width = version * 4 + 17;
+            let mut columns: Vec<usize> =
+                alignment_positions(version, width).iter().map(|&(x, _)| x + 2).collect();
+            columns.sort();
+            columns.dedup();
+            columns
+        };
+
+        assert!(centers(1).is_empty());
+        assert_eq!(centers(2), vec![18]);
+        assert_eq!(centers(7), vec![6, 22, 38]);
+        assert_eq!(centers(32), vec![6, 34, 60, 86, 112, 138]);
+        assert_eq!(centers(40), vec![6, 30, 58, 86, 114, 142, 170]);
+    }
+
+    #[test]
+    fn test_module_kinds() {
+        let code = QrCode::with_version("https://example.com", Version::Normal(7), qrcode::EcLevel::L).unwrap();
+        let matrix = QrMatrix::new(&code);
+        let w = matrix.width();
+
+        assert_eq!(matrix.kind(3, 3), ModuleKind::Finder);
+        assert_eq!(matrix.kind(7, 2), ModuleKind::Separator);
+        assert_eq!(matrix.kind(10, 6), ModuleKind::Timing);
+        assert_eq!(matrix.kind(6, 10), ModuleKind::Timing);
+        assert_eq!(matrix.kind(22, 22), ModuleKind::Alignment);
+        assert_eq!(matrix.kind(22, 6), ModuleKind::Alignment);
+        assert_eq!(matrix.kind(8, w - 8), ModuleKind::FormatInfo);
+        assert_eq!(matrix.kind(w - 10, 2), ModuleKind::VersionInfo);
+        assert_eq!(matrix.kind(12, 12), ModuleKind::Data);
+
+        // Every function module agrees with the encoder's own bookkeeping
+        // (which leaves version info out)
+        for y in 0..w {
+            for x in 0..w {
+                if matrix.kind(x, y) == ModuleKind::VersionInfo {
+                    continue;
+                }
+                let functional = qrcode::canvas::is_functional(Version::Normal(7), w as i16, x as i16, y as i16);
+                assert_eq!(matrix.kind(x, y) != ModuleKind::Data, functional, "module ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_micro_module_kinds() {
+        let code = QrCode::with_version("12345", Version::Micro(2), qrcode::EcLevel::L).unwrap();
+        let matrix = QrMatrix::new(&code);
+        let w = matrix.width();
+
+        assert_eq!((w, matrix.height()), (13, 13));
+        assert_eq!(matrix.eye_positions(), &[(0, 0)]);
+        assert_eq!(matrix.kind(10, 0), ModuleKind::Timing);
+        assert_eq!(matrix.kind(8, 3), ModuleKind::FormatInfo);
+        for y in 0..w {
+            for x in 0..w {
+                let functional = qrcode::canvas::is_functional(Version::Micro(2), w as i16, x as i16, y as i16);
+                assert_eq!(matrix.kind(x, y) != ModuleKind::Data, functional, "module ({}, {})", x, y);
+            }
+        }
+    }
+}
@@ -1,12 +1,34 @@
 //! QR code generation and rendering
 //!
 //! This module is responsible for all QR code generation logic,
-//! including module drawing, color gradients, and image integration.
+//! including module drawing, color gradients, image integration,
+//! vector (SVG and PDF) export, ECI character sets, Structured Append
+//! sequences, the Swiss QR-bill cross and decode verification.
+//! Everything here works from a `QrSpec` and has no dependency on the GUI.
 
+pub mod spec;
 pub mod generator;
-pub mod drawing;
-pub mod colors;
-pub mod images;
+pub mod encoding;
+pub mod eci;
+pub mod gs1;
+pub mod matrix;
+pub mod rmqr;
+pub mod structured;
+pub(crate) mod swiss;
+pub(crate) mod drawing;
+pub mod eyes;
+pub mod path;
+pub(crate) mod colors;
+pub(crate) mod images;
+pub(crate) mod vector;
+pub mod svg;
+pub mod pdf;
+pub mod verify;
+pub mod scannability;
+pub mod occlusion;
 
-// Re-export main generation function for convenience
+// Re-export main generation functions for convenience
+pub use spec::QrSpec;
 pub use generator::generate_qr_image;
+pub use svg::generate_qr_svg;
+pub use pdf::generate_qr_pdf;
@@ -0,0 +1,464 @@
+//! Logo occlusion analysis
+//!
+//! A logo pasted over the center of the code destroys every codeword it
+//! touches; the code only scans while each Reed-Solomon block can still
+//! correct the damage. This module maps the logo area onto the module grid,
+//! traces which codeword (and block) every covered module belongs to, and
+//! compares the damage with the error correction budget of the chosen level.
+//! Function patterns (timing, alignment, version info) carry no codewords,
+//! so they are counted separately.
+
+use qrcode::canvas::is_functional;
+use qrcode::{EcLevel, Version};
+
+use crate::qr::spec::QrSpec;
+use crate::qr::{encoding, generator, structured};
+use crate::types::{ErrorCorrectionLevel, LogoFit, SymbolType};
+
+/// Smallest logo size (fraction of the code) the shrink strategy goes down to
+const MIN_LOGO_SIZE: f32 = 0.05;
+
+/// Logo size step used when shrinking
+const LOGO_SHRINK_STEP: f32 = 0.01;
+
+/// Error correction codewords per block, indexed by version - 1 and
+/// L/M/Q/H (ISO/IEC 18004, Table 9)
+const EC_CODEWORDS_PER_BLOCK: [[usize; 4]; 40] = [
+    [7, 10, 13, 17], [10, 16, 22, 28], [15, 26, 18, 22], [20, 18, 26, 16],
+    [26, 24, 18, 22], [18, 16, 24, 28], [20, 18, 18, 26], [24, 22, 22, 26],
+    [30, 22, 20, 24], [18, 26, 24, 28], [20, 30, 28, 24], [24, 22, 26, 28],
+    [26, 22, 24, 22], [30, 24, 20, 24], [22, 24, 30, 24], [24, 28, 24, 30],
+    [28, 28, 28, 28], [30, 26, 28, 28], [28, 26, 26, 26], [28, 26, 30, 28],
+    [28, 26, 28, 30], [28, 28, 30, 24], [30, 28, 30, 30], [30, 28, 30, 30],
+    [26, 28, 30, 30], [28, 28, 28, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+    [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+    [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+    [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+];
+
+/// Data codeword blocks per version and level: `(size, count, size, count)`
+/// for the two block groups (ISO/IEC 18004, Table 9)
+const DATA_BLOCKS: [[(usize, usize, usize, usize); 4]; 40] = [
+    [(19, 1, 0, 0), (16, 1, 0, 0), (13, 1, 0, 0), (9, 1, 0, 0)],
+    [(34, 1, 0, 0), (28, 1, 0, 0), (22, 1, 0, 0), (16, 1, 0, 0)],
+    [(55, 1, 0, 0), (44, 1, 0, 0), (17, 2, 0, 0), (13, 2, 0, 0)],
+    [(80, 1, 0, 0), (32, 2, 0, 0), (24, 2, 0, 0), (9, 4, 0, 0)],
+    [(108, 1, 0, 0), (43, 2, 0, 0), (15, 2, 16, 2), (11, 2, 12, 2)],
+    [(68, 2, 0, 0), (27, 4, 0, 0), (19, 4, 0, 0), (15, 4, 0, 0)],
+    [(78, 2, 0, 0), (31, 4, 0, 0), (14, 2, 15, 4), (13, 4, 14, 1)],
+    [(97, 2, 0, 0), (38, 2, 39, 2), (18, 4, 19, 2), (14, 4, 15, 2)],
+    [(116, 2, 0, 0), (36, 3, 37, 2), (16, 4, 17, 4), (12, 4, 13, 4)],
+    [(68, 2, 69, 2), (43, 4, 44, 1), (19, 6, 20, 2), (15, 6, 16, 2)],
+    [(81, 4, 0, 0), (50, 1, 51, 4), (22, 4, 23, 4), (12, 3, 13, 8)],
+    [(92, 2, 93, 2), (36, 6, 37, 2), (20, 4, 21, 6), (14, 7, 15, 4)],
+    [(107, 4, 0, 0), (37, 8, 38, 1), (20, 8, 21, 4), (11, 12, 12, 4)],
+    [(115, 3, 116, 1), (40, 4, 41, 5), (16, 11, 17, 5), (12, 11, 13, 5)],
+    [(87, 5, 88, 1), (41, 5, 42, 5), (24, 5, 25, 7), (12, 11, 13, 7)],
+    [(98, 5, 99, 1), (45, 7, 46, 3), (19, 15, 20, 2), (15, 3, 16, 13)],
+    [(107, 1, 108, 5), (46, 10, 47, 1), (22, 1, 23, 15), (14, 2, 15, 17)],
+    [(120, 5, 121, 1), (43, 9, 44, 4), (22, 17, 23, 1), (14, 2, 15, 19)],
+    [(113, 3, 114, 4), (44, 3, 45, 11), (21, 17, 22, 4), (13, 9, 14, 16)],
+    [(107, 3, 108, 5), (41, 3, 42, 13), (24, 15, 25, 5), (15, 15, 16, 10)],
+    [(116, 4, 117, 4), (42, 17, 0, 0), (22, 17, 23, 6), (16, 19, 17, 6)],
+    [(111, 2, 112, 7), (46, 17, 0, 0), (24, 7, 25, 16), (13, 34, 0, 0)],
+    [(121, 4, 122, 5), (47, 4, 48, 14), (24, 11, 25, 14), (15, 16, 16, 14)],
+    [(117, 6, 118, 4), (45, 6, 46, 14), (24, 11, 25, 16), (16, 30, 17, 2)],
+    [(106, 8, 107, 4), (47, 8, 48, 13), (24, 7, 25, 22), (15, 22, 16, 13)],
+    [(114, 10, 115, 2), (46, 19, 47, 4), (22, 28, 23, 6), (16, 33, 17, 4)],
+    [(122, 8, 123, 4), (45, 22, 46, 3), (23, 8, 24, 26), (15, 12, 16, 28)],
+    [(117, 3, 118, 10), (45, 3, 46, 23), (24, 4, 25, 31), (15, 11, 16, 31)],
+    [(116, 7, 117, 7), (45, 21, 46, 7), (23, 1, 24, 37), (15, 19, 16, 26)],
+    [(115, 5, 116, 10), (47, 19, 48, 10), (24, 15, 25, 25), (15, 23, 16, 25)],
+    [(115, 13, 116, 3), (46, 2, 47, 29), (24, 42, 25, 1), (15, 23, 16, 28)],
+    [(115, 17, 0, 0), (46, 10, 47, 23), (24, 10, 25, 35), (15, 19, 16, 35)],
+    [(115, 17, 116, 1), (46, 14, 47, 21), (24, 29, 25, 19), (15, 11, 16, 46)],
+    [(115, 13, 116, 6), (46, 14, 47, 23), (24, 44, 25, 7), (16, 59, 17, 1)],
+    [(121, 12, 122, 7), (47, 12, 48, 26), (24, 39, 25, 14), (15, 22, 16, 41)],
+    [(121, 6, 122, 14), (47, 6, 48, 34), (24, 46, 25, 10), (15, 2, 16, 64)],
+    [(122, 17, 123, 4), (46, 29, 47, 14), (24, 49, 25, 10), (15, 24, 16, 46)],
+    [(122, 4, 123, 18), (46, 13, 47, 32), (24, 48, 25, 14), (15, 42, 16, 32)],
+    [(117, 20, 118, 4), (47, 40, 48, 7), (24, 43, 25, 22), (15, 10, 16, 67)],
+    [(118, 19, 119, 6), (47, 18, 48, 31), (24, 34, 25, 34), (15, 20, 16, 61)],
+];
+
+/// Damage to one Reed-Solomon block
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockOcclusion {
+    /// Data plus error correction codewords in the block
+    pub codewords: usize,
+    /// Codewords with at least one module under the logo
+    pub obscured: usize,
+    /// Codeword errors the block can correct
+    pub correctable: usize,
+}
+
+impl BlockOcclusion {
+    /// Share of the block's correction capacity used by the logo
+    pub fn budget_used(&self) -> f32 {
+        match (self.obscured, self.correctable) {
+            (0, _) => 0.0,
+            (_, 0) => f32::INFINITY,
+            (obscured, correctable) => obscured as f32 / correctable as f32,
+        }
+    }
+}
+
+/// How much of the error correction budget a logo consumes
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcclusionReport {
+    /// QR version (1-40) of the encoded content
+    pub version: i16,
+    /// Error correction level the report was computed for
+    pub ec_level: ErrorCorrectionLevel,
+    /// Data and error correction modules under the logo
+    pub obscured_modules: usize,
+    /// Timing, alignment and version info modules under the logo
+    pub obscured_function_modules: usize,
+    /// Damage per Reed-Solomon block
+    pub blocks: Vec<BlockOcclusion>,
+}
+
+impl OcclusionReport {
+    /// Share of the correction budget used in the worst block
+    ///
+    /// Decoding fails once any single block exceeds its budget, so the
+    /// worst block decides. Values above 1.0 mean the code cannot decode.
+    pub fn budget_used(&self) -> f32 {
+        self.blocks
+            .iter()
+            .map(BlockOcclusion::budget_used)
+            .fold(0.0, f32::max)
+    }
+
+    /// Total codewords touched by the logo
+    pub fn obscured_codewords(&self) -> usize {
+        self.blocks.iter().map(|b| b.obscured).sum()
+    }
+
+    /// Whether the logo leaves `safety_margin` (0.0-0.9) of every block's budget free
+    pub fn fits(&self, safety_margin: f32) -> bool {
+        self.budget_used() <= 1.0 - safety_margin.clamp(0.0, 0.9)
+    }
+
+    /// Short human-readable description for status messages
+    pub fn summary(&self) -> String {
+        let used = self.budget_used();
+        let percent = if used.is_finite() {
+            format!("{:.0}%", used * 100.0)
+        } else {
+            "all".to_string()
+        };
+        let mut text = format!(
+            "Logo covers {} codewords, using {} of the error correction budget",
+            self.obscured_codewords(),
+            percent
+        );
+        if self.obscured_function_modules > 0 {
+            text.push_str(&format!(
+                " ({} timing/alignment modules hidden)",
+                self.obscured_function_modules
+            ));
+        }
+        text
+    }
+}
+
+/// Compute which codewords the logo obscures
+///
+/// A module counts as obscured when its center lies under the logo square
+/// (transparent logo pixels are treated as covering too, which errs on the
+/// safe side). Uses the same geometry as the renderer.
+///
+/// # Returns
+/// * `Ok(Some(report))` - Occlusion of the loaded logo
+/// * `Ok(None)` - No logo is loaded, or the symbol is not a standard QR
+///   code (the block tables below only cover versions 1-40)
+/// * `Err(String)` - Error message if the QR code cannot be created
+pub fn analyze(spec: &QrSpec) -> Result<Option<OcclusionReport>, String> {
+    if spec.logo_image.is_none() || spec.symbol_type != SymbolType::Qr {
+        return Ok(None);
+    }
+    if spec.structured_append.is_some() {
+        // The codes of a sequence share one version, so the logo covers
+        // the same codewords in each of them
+        return analyze(&structured::split(spec)?[0]);
+    }
+
+    // === Step 1: Encode and Look Up Block Structure ===
+    let encoded = encoding::encode(spec)?;
+    let version = encoded.info.version;
+    let (owners, mut blocks) = block_structure(version, spec.ec_level);
+
+    // === Step 2: Locate the Logo on the Module Grid ===
+    let qr_width = encoded.matrix.width();
+    let layout = generator::Layout::new(spec, qr_width, qr_width);
+    let (logo_x, logo_y, logo_size) = layout.logo_rect(qr_width, qr_width, spec.logo_size);
+    let covered = |x: i16, y: i16| {
+        let center = |offset: f32, i: i16| offset + (i as f32 + 0.5) * layout.module_size;
+        let inside = |c: f32, start: f32| c >= start && c < start + logo_size;
+        inside(center(layout.offset_x, x), logo_x) && inside(center(layout.offset_y, y), logo_y)
+    };
+
+    // === Step 3: Trace Covered Modules to Codewords ===
+    let width = qr_width as i16;
+    let mut obscured_codeword = vec![false; owners.len()];
+    let mut obscured_modules = 0;
+    for (bit, (x, y)) in data_module_order(version, width).enumerate() {
+        let codeword = bit / 8;
+        if codeword < owners.len() && covered(x, y) {
+            obscured_codeword[codeword] = true;
+            obscured_modules += 1;
+        }
+    }
+
+    let mut obscured_function_modules = 0;
+    for y in 0..width {
+        for x in 0..width {
+            if is_function_module(version, width, x, y) && covered(x, y) {
+                obscured_function_modules += 1;
+            }
+        }
+    }
+
+    // === Step 4: Compare With Each Block's Budget ===
+    for (codeword, &block) in owners.iter().enumerate() {
+        if obscured_codeword[codeword] {
+            blocks[block].obscured += 1;
+        }
+    }
+
+    Ok(Some(OcclusionReport {
+        version,
+        ec_level: spec.ec_level,
+        obscured_modules,
+        obscured_function_modules,
+        blocks,
+    }))
+}
+
+/// Adjust the specification so the logo stays within its budget
+///
+/// Applies the strategy in `spec.logo_fit` when the logo uses more than
+/// `1 - spec.logo_safety_margin` of the error correction budget. Raising
+/// the error correction stops at High (or earlier if the content no longer
+/// fits); shrinking stops at 5% of the code size.
+///
+/// # Returns
+/// * `Ok(Some(message))` - Description of the change that was made
+/// * `Ok(None)` - Nothing to do (no logo, fitting disabled, or already fits)
+/// * `Err(String)` - Error message if the QR code cannot be created
+pub fn fit_logo(spec: &mut QrSpec) -> Result<Option<String>, String> {
+    if spec.logo_fit == LogoFit::Off {
+        return Ok(None);
+    }
+    let margin = spec.logo_safety_margin;
+    match analyze(spec)? {
+        Some(report) if !report.fits(margin) => {}
+        _ => return Ok(None),
+    }
+
+    match spec.logo_fit {
+        LogoFit::Off => Ok(None),
+        LogoFit::RaiseErrorCorrection => {
+            let original = spec.ec_level;
+            while let Some(next) = spec.ec_level.stronger() {
+                let previous = spec.ec_level;
+                spec.ec_level = next;
+                match analyze(spec) {
+                    Ok(Some(report)) if report.fits(margin) => {
+                        return Ok(Some(format!(
+                            "Raised error correction from {:?} to {:?} so the logo fits",
+                            original, next
+                        )));
+                    }
+                    Ok(_) => {}
+                    Err(_) => {
+                        // Content too long for a stronger level
+                        spec.ec_level = previous;
+                        break;
+                    }
+                }
+            }
+            if spec.ec_level == original {
+                return Ok(None);
+            }
+            Ok(Some(format!(
+                "Raised error correction from {:?} to {:?}, but the logo still exceeds the safety margin",
+                original, spec.ec_level
+            )))
+        }
+        LogoFit::ShrinkLogo => {
+            let original = spec.logo_size;
+            while spec.logo_size - LOGO_SHRINK_STEP >= MIN_LOGO_SIZE {
+                spec.logo_size -= LOGO_SHRINK_STEP;
+                if analyze(spec)?.is_some_and(|report| report.fits(margin)) {
+                    return Ok(Some(format!(
+                        "Shrunk logo from {:.0}% to {:.0}% so it fits",
+                        original * 100.0,
+                        spec.logo_size * 100.0
+                    )));
+                }
+            }
+            Ok(Some(format!(
+                "Shrunk logo to {:.0}%, but it still exceeds the safety margin",
+                spec.logo_size * 100.0
+            )))
+        }
+    }
+}
+
+/// Reed-Solomon blocks of a standard QR version and level
+///
+/// # Returns
+/// The block of every codeword in placement order, and the blocks with
+/// nothing obscured yet
+pub(crate) fn block_structure(version: i16, ec_level: ErrorCorrectionLevel) -> (Vec<usize>, Vec<BlockOcclusion>) {
+    let level = level_index(ec_level.to_ec_level());
+    let (size_1, count_1, size_2, count_2) = DATA_BLOCKS[version as usize - 1][level];
+    let ec_per_block = EC_CODEWORDS_PER_BLOCK[version as usize - 1][level];
+
+    let data_sizes: Vec<usize> = std::iter::repeat_n(size_1, count_1)
+        .chain(std::iter::repeat_n(size_2, count_2))
+        .collect();
+    let reserved = misdecode_reserve(version, ec_level);
+    let blocks = data_sizes
+        .iter()
+        .map(|&size| BlockOcclusion {
+            codewords: size + ec_per_block,
+            obscured: 0,
+            correctable: ec_per_block.saturating_sub(reserved) / 2,
+        })
+        .collect();
+
+    (interleaved_block_owners(&data_sizes, ec_per_block), blocks)
+}
+
+/// Column index of an error correction level in the block tables
+fn level_index(level: EcLevel) -> usize {
+    match level {
+        EcLevel::L => 0,
+        EcLevel::M => 1,
+        EcLevel::Q => 2,
+        EcLevel::H => 3,
+    }
+}
+
+/// Codewords per block held back against misdecoding in small versions
+fn misdecode_reserve(version: i16, level: ErrorCorrectionLevel) -> usize {
+    use ErrorCorrectionLevel::{Low, Medium};
+    match (version, level) {
+        (1, Low) => 3,
+        (2, Low) | (1, Medium) => 2,
+        (1, _) | (3, Low) => 1,
+        _ => 0,
+    }
+}
+
+/// Block index of every codeword in placement order
+///
+/// Data codewords are interleaved block by block (shorter blocks drop out
+/// at the end), followed by the interleaved error correction codewords.
+fn interleaved_block_owners(data_sizes: &[usize], ec_per_block: usize) -> Vec<usize> {
+    let longest = data_sizes.iter().copied().max().unwrap_or(0);
+    let mut owners = Vec::new();
+    for i in 0..longest {
+        for (block, &size) in data_sizes.iter().enumerate() {
+            if i < size {
+                owners.push(block);
+            }
+        }
+    }
+    for _ in 0..ec_per_block {
+        owners.extend(0..data_sizes.len());
+    }
+    owners
+}
+
+/// Whether a module belongs to a function pattern or reserved area
+fn is_function_module(version: i16, width: i16, x: i16, y: i16) -> bool {
+    // Version information blocks (6x3) next to the top-right and bottom-left finders
+    let version_info = version >= 7
+        && ((x >= width - 11 && x < width - 8 && y < 6)
+            || (y >= width - 11 && y < width - 8 && x < 6));
+    version_info || is_functional(Version::Normal(version), width, x, y)
+}
+
+/// Data module coordinates in bit placement order
+///
+/// Walks two-module-wide columns from the right edge, alternating upwards
+/// and downwards and skipping the vertical timing pattern.
+pub(crate) fn data_module_order(version: i16, width: i16) -> impl Iterator<Item = (i16, i16)> {
+    let mut order = Vec::new();
+    let mut right = width - 1;
+    let mut upward = true;
+
+    while right > 0 {
+        if right == 6 {
+            right -= 1;
+        }
+        for i in 0..width {
+            let y = if upward { width - 1 - i } else { i };
+            for x in [right, right - 1] {
+                if !is_function_module(version, width, x, y) {
+                    order.push((x, y));
+                }
+            }
+        }
+        upward = !upward;
+        right -= 2;
+    }
+
+    order.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::DynamicImage;
+
+    fn spec_with_logo(size: f32, level: ErrorCorrectionLevel) -> QrSpec {
+        QrSpec {
+            logo_image: Some(DynamicImage::new_rgba8(16, 16)),
+            logo_size: size,
+            ec_level: level,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_module_order_covers_all_codewords() {
+        // Version 7 has 196 codewords plus 0 remainder bits; version 2 has 7 remainder bits
+        for (version, codewords, remainder) in [(2, 44, 7), (7, 196, 0), (14, 581, 3)] {
+            let width = 17 + 4 * version;
+            let modules = data_module_order(version, width).count();
+            assert_eq!(modules, codewords * 8 + remainder, "version {}", version);
+        }
+    }
+
+    #[test]
+    fn test_no_logo_has_no_report() {
+        assert_eq!(analyze(&QrSpec::default()), Ok(None));
+    }
+
+    #[test]
+    fn test_higher_level_uses_less_budget() {
+        let low = analyze(&spec_with_logo(0.3, ErrorCorrectionLevel::Low)).unwrap().unwrap();
+        let high = analyze(&spec_with_logo(0.3, ErrorCorrectionLevel::High)).unwrap().unwrap();
+        assert!(low.budget_used() > 1.0);
+        assert!(high.budget_used() < low.budget_used());
+    }
+
+    #[test]
+    fn test_fit_logo_strategies() {
+        let mut spec = spec_with_logo(0.3, ErrorCorrectionLevel::Low);
+        spec.logo_fit = LogoFit::RaiseErrorCorrection;
+        assert!(fit_logo(&mut spec).unwrap().is_some());
+        assert_ne!(spec.ec_level, ErrorCorrectionLevel::Low);
+
+        let mut spec = spec_with_logo(0.3, ErrorCorrectionLevel::Low);
+        spec.logo_fit = LogoFit::ShrinkLogo;
+        fit_logo(&mut spec).unwrap();
+        assert!(spec.logo_size < 0.3);
+        assert!(analyze(&spec).unwrap().unwrap().fits(spec.logo_safety_margin));
+    }
+}
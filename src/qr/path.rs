@@ -0,0 +1,271 @@
+//! Vector paths for whole-shape rendering
+//!
+//! Shapes that span several modules (eye frames and pupils) are described
+//! once as a `Path` and then either rasterized (`drawing::fill_path`) or
+//! written to SVG and PDF, so every output format draws the same outline.
+//!
+//! Paths are filled with the nonzero winding rule. Contours are built
+//! clockwise (in image coordinates, y down); holes are added with
+//! `Path::reversed` so they wind the other way.
+
+/// Bezier handle length for approximating a quarter circle with a cubic curve
+pub const KAPPA: f32 = 0.552_284_8;
+
+/// Line segments used to flatten one cubic curve for rasterization
+const CURVE_SEGMENTS: usize = 16;
+
+/// A point in pixel (or module) coordinates
+pub type Point = (f32, f32);
+
+/// One segment of a contour, starting where the previous one ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// Straight line to a point
+    Line(Point),
+    /// Cubic Bezier curve with two control points and an end point
+    Cubic(Point, Point, Point),
+}
+
+impl Segment {
+    /// End point of the segment
+    pub fn end(&self) -> Point {
+        match *self {
+            Segment::Line(end) | Segment::Cubic(_, _, end) => end,
+        }
+    }
+}
+
+/// A closed outline: a start point and segments that end back at the start
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    /// First point of the outline
+    pub start: Point,
+    /// Segments in drawing order
+    pub segments: Vec<Segment>,
+}
+
+/// A filled shape made of one or more closed contours
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    /// Outlines making up the shape (holes wind counter-clockwise)
+    pub contours: Vec<Contour>,
+}
+
+impl Path {
+    /// Create an empty path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a closed polygon through the given points
+    pub fn polygon(mut self, points: &[Point]) -> Self {
+        if let Some(&start) = points.first() {
+            let segments = points[1..]
+                .iter()
+                .chain(std::iter::once(&start))
+                .map(|&p| Segment::Line(p))
+                .collect();
+            self.contours.push(Contour { start, segments });
+        }
+        self
+    }
+
+    /// Add an axis-aligned rectangle
+    pub fn rect(self, x: f32, y: f32, width: f32, height: f32) -> Self {
+        self.polygon(&[(x, y), (x + width, y), (x + width, y + height), (x, y + height)])
+    }
+
+    /// Add a circle given by center and radius
+    pub fn circle(self, cx: f32, cy: f32, r: f32) -> Self {
+        self.rounded_rect(cx - r, cy - r, 2.0 * r, 2.0 * r, [r; 4])
+    }
+
+    /// Add a rectangle with individually rounded corners
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left corner
+    /// * `width`, `height` - Size
+    /// * `radii` - Corner radii: top-left, top-right, bottom-right, bottom-left
+    ///   (limited to half the shorter side)
+    pub fn rounded_rect(mut self, x: f32, y: f32, width: f32, height: f32, radii: [f32; 4]) -> Self {
+        let max_radius = width.min(height) / 2.0;
+        let [tl, tr, br, bl] = radii.map(|r| r.clamp(0.0, max_radius));
+        let (right, bottom) = (x + width, y + height);
+
+        let mut segments = Vec::new();
+        let mut corner = |from: Point, to: Point, corner: Point, r: f32| {
+            segments.push(Segment::Line(from));
+            if r > 0.0 {
+                // Handles point from each end of the arc towards the square corner
+                let c1 = (from.0 + (corner.0 - from.0) * KAPPA, from.1 + (corner.1 - from.1) * KAPPA);
+                let c2 = (to.0 + (corner.0 - to.0) * KAPPA, to.1 + (corner.1 - to.1) * KAPPA);
+                segments.push(Segment::Cubic(c1, c2, to));
+            }
+        };
+        corner((right - tr, y), (right, y + tr), (right, y), tr);
+        corner((right, bottom - br), (right - br, bottom), (right, bottom), br);
+        corner((x + bl, bottom), (x, bottom - bl), (x, bottom), bl);
+        corner((x, y + tl), (x + tl, y), (x, y), tl);
+
+        self.contours.push(Contour { start: (x + tl, y), segments });
+        self
+    }
+
+    /// Append all contours of another path
+    pub fn append(mut self, other: Path) -> Self {
+        self.contours.extend(other.contours);
+        self
+    }
+
+    /// The same outlines traversed in the opposite direction (turns a shape into a hole)
+    pub fn reversed(self) -> Self {
+        let contours = self
+            .contours
+            .into_iter()
+            .map(|contour| {
+                let mut segments = Vec::with_capacity(contour.segments.len());
+                let mut end = contour.start;
+                for (i, segment) in contour.segments.iter().enumerate().rev() {
+                    let from = if i == 0 { contour.start } else { contour.segments[i - 1].end() };
+                    segments.push(match *segment {
+                        Segment::Line(_) => Segment::Line(from),
+                        Segment::Cubic(c1, c2, _) => Segment::Cubic(c2, c1, from),
+                    });
+                    end = from;
+                }
+                Contour { start: end, segments }
+            })
+            .collect();
+        Self { contours }
+    }
+
+    /// Scale every point by `scale`, then move it by (`dx`, `dy`)
+    pub fn transformed(self, scale: f32, dx: f32, dy: f32) -> Self {
+        self.map_points(|(x, y)| (x * scale + dx, y * scale + dy))
+    }
+
+    /// Rotate clockwise (in image coordinates) by quarter turns around a center
+    pub fn rotated(self, quarter_turns: u8, center: Point) -> Self {
+        let (cx, cy) = center;
+        self.map_points(|(x, y)| match quarter_turns % 4 {
+            0 => (x, y),
+            1 => (cx - (y - cy), cy + (x - cx)),
+            2 => (2.0 * cx - x, 2.0 * cy - y),
+            _ => (cx + (y - cy), cy - (x - cx)),
+        })
+    }
+
+    /// Apply a point mapping to every point (including control points)
+    fn map_points(self, map: impl Fn(Point) -> Point) -> Self {
+        let contours = self
+            .contours
+            .into_iter()
+            .map(|contour| Contour {
+                start: map(contour.start),
+                segments: contour
+                    .segments
+                    .into_iter()
+                    .map(|segment| match segment {
+                        Segment::Line(p) => Segment::Line(map(p)),
+                        Segment::Cubic(c1, c2, p) => Segment::Cubic(map(c1), map(c2), map(p)),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self { contours }
+    }
+
+    /// Approximate every contour by a closed polygon
+    pub fn flatten(&self) -> Vec<Vec<Point>> {
+        self.contours
+            .iter()
+            .map(|contour| {
+                let mut points = vec![contour.start];
+                let mut current = contour.start;
+                for segment in &contour.segments {
+                    match *segment {
+                        Segment::Line(p) => points.push(p),
+                        Segment::Cubic(c1, c2, p) => {
+                            for i in 1..=CURVE_SEGMENTS {
+                                let t = i as f32 / CURVE_SEGMENTS as f32;
+                                points.push(cubic_point(current, c1, c2, p, t));
+                            }
+                        }
+                    }
+                    current = segment.end();
+                }
+                points
+            })
+            .collect()
+    }
+
+    /// Bounding box of all points (including control points) as (min, max)
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let points = self.contours.iter().flat_map(|contour| {
+            std::iter::once(contour.start).chain(contour.segments.iter().flat_map(|segment| {
+                match *segment {
+                    Segment::Line(p) => vec![p],
+                    Segment::Cubic(c1, c2, p) => vec![c1, c2, p],
+                }
+            }))
+        });
+
+        points.fold(None, |acc, (x, y)| match acc {
+            None => Some(((x, y), (x, y))),
+            Some(((x0, y0), (x1, y1))) => Some(((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y)))),
+        })
+    }
+}
+
+/// Point on a cubic Bezier curve at parameter `t` (0.0-1.0)
+fn cubic_point(p0: Point, c1: Point, c2: Point, p1: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    (
+        a * p0.0 + b * c1.0 + c * c2.0 + d * p1.0,
+        a * p0.1 + b * c1.1 + c * c2.1 + d * p1.1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Signed area of a polygon (positive for clockwise in y-down coordinates)
+    fn area(points: &[Point]) -> f32 {
+        points
+            .windows(2)
+            .map(|w| w[0].0 * w[1].1 - w[1].0 * w[0].1)
+            .sum::<f32>()
+            / 2.0
+    }
+
+    #[test]
+    fn test_reversed_hole_cancels_area() {
+        let ring = Path::new()
+            .circle(5.0, 5.0, 5.0)
+            .append(Path::new().circle(5.0, 5.0, 3.0).reversed());
+        let areas: Vec<f32> = ring.flatten().iter().map(|p| area(p)).collect();
+
+        // Outer circle clockwise, inner circle counter-clockwise
+        assert!((areas[0] - std::f32::consts::PI * 25.0).abs() < 0.5);
+        assert!((areas[1] + std::f32::consts::PI * 9.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_rotation_keeps_winding() {
+        let path = Path::new().rect(0.0, 0.0, 2.0, 1.0);
+        let rotated = path.clone().rotated(1, (1.0, 1.0));
+        assert_eq!(rotated.bounds(), Some(((1.0, 0.0), (2.0, 2.0))));
+        assert!((area(&rotated.flatten()[0]) - area(&path.flatten()[0])).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_contours_are_closed() {
+        let path = Path::new().rounded_rect(0.0, 0.0, 4.0, 2.0, [1.0, 0.0, 1.0, 0.0]);
+        for polygon in path.flatten() {
+            assert_eq!(polygon.first(), polygon.last());
+        }
+        assert_eq!(path.bounds(), Some(((0.0, 0.0), (4.0, 2.0))));
+    }
+}
@@ -0,0 +1,766 @@
+//! Print-ready PDF export
+//!
+//! Places the QR code on a page of a chosen physical size using vector
+//! shapes (not an embedded bitmap), with optional bleed and crop marks for
+//! commercial printing:
+//! - Page, QR code and bleed sizes in millimeters or inches
+//! - Modules drawn as filled paths from `vector::build_vector_qr`
+//! - Gradients as native PDF shading patterns
+//! - Logo and background images embedded as compressed image XObjects
+//!
+//! The writer is self-contained and deterministic (no timestamps).
+
+use std::fmt::Write;
+
+use image::DynamicImage;
+use serde::{Serialize, Deserialize};
+
+use crate::qr::spec::QrSpec;
+use crate::types::GradientType;
+use crate::qr::path::{Segment, KAPPA};
+use crate::qr::vector::{self, format_number as num, Shape};
+
+/// PostScript points per millimeter
+const POINTS_PER_MM: f32 = 72.0 / 25.4;
+
+/// Millimeters per inch
+const MM_PER_INCH: f32 = 25.4;
+
+/// Space reserved outside the bleed for crop marks (mm)
+const CROP_MARK_MARGIN_MM: f32 = 12.0;
+
+/// Length of each crop mark line (mm)
+const CROP_MARK_LENGTH_MM: f32 = 8.0;
+
+/// Gap between the bleed edge and the start of a crop mark (mm)
+const CROP_MARK_GAP_MM: f32 = 2.0;
+
+/// Unit used to display and enter physical lengths
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LengthUnit {
+    /// Millimeters
+    Millimeters,
+    /// Inches
+    Inches,
+}
+
+impl LengthUnit {
+    /// Convert a length in this unit to millimeters
+    pub fn unit_to_mm(self, value: f32) -> f32 {
+        match self {
+            LengthUnit::Millimeters => value,
+            LengthUnit::Inches => value * MM_PER_INCH,
+        }
+    }
+
+    /// Convert a length in millimeters to this unit
+    pub fn mm_to_unit(self, mm: f32) -> f32 {
+        match self {
+            LengthUnit::Millimeters => mm,
+            LengthUnit::Inches => mm / MM_PER_INCH,
+        }
+    }
+
+    /// Short suffix for display ("mm" or "in")
+    pub fn suffix(self) -> &'static str {
+        match self {
+            LengthUnit::Millimeters => "mm",
+            LengthUnit::Inches => "in",
+        }
+    }
+}
+
+/// Common page sizes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PageSize {
+    /// ISO A4 (210 × 297 mm)
+    A4,
+    /// ISO A5 (148 × 210 mm)
+    A5,
+    /// US Letter (8.5 × 11 in)
+    Letter,
+    /// Business card (85 × 55 mm)
+    BusinessCard,
+    /// Square sticker (100 × 100 mm)
+    Sticker,
+}
+
+impl PageSize {
+    /// All page size presets in display order
+    pub const ALL: [PageSize; 5] = [
+        PageSize::A4,
+        PageSize::A5,
+        PageSize::Letter,
+        PageSize::BusinessCard,
+        PageSize::Sticker,
+    ];
+
+    /// Page width and height in millimeters
+    pub fn dimensions_mm(self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (210.0, 297.0),
+            PageSize::A5 => (148.0, 210.0),
+            PageSize::Letter => (8.5 * MM_PER_INCH, 11.0 * MM_PER_INCH),
+            PageSize::BusinessCard => (85.0, 55.0),
+            PageSize::Sticker => (100.0, 100.0),
+        }
+    }
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            PageSize::A4 => "A4",
+            PageSize::A5 => "A5",
+            PageSize::Letter => "US Letter",
+            PageSize::BusinessCard => "Business Card",
+            PageSize::Sticker => "Sticker (100mm)",
+        }
+    }
+}
+
+impl std::str::FromStr for PageSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "a4" => Ok(PageSize::A4),
+            "a5" => Ok(PageSize::A5),
+            "letter" | "usletter" => Ok(PageSize::Letter),
+            "businesscard" | "card" => Ok(PageSize::BusinessCard),
+            "sticker" => Ok(PageSize::Sticker),
+            _ => Err(format!("Unknown page size '{}' (expected a4, a5, letter, business-card or sticker)", s)),
+        }
+    }
+}
+
+/// Physical layout options for PDF export (all lengths in millimeters)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfOptions {
+    /// Trimmed page width
+    pub page_width_mm: f32,
+
+    /// Trimmed page height
+    pub page_height_mm: f32,
+
+    /// Printed width/height of the QR code image (including its border)
+    pub qr_size_mm: f32,
+
+    /// Extra area printed beyond the trim edge on every side
+    pub bleed_mm: f32,
+
+    /// Draw crop marks at the trim corners (adds a margin outside the bleed)
+    pub crop_marks: bool,
+
+    /// Fill the whole page including bleed with the QR background color
+    pub fill_page_background: bool,
+
+    /// Unit used to display lengths in the UI (storage is always mm)
+    pub unit: LengthUnit,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        let (width, height) = PageSize::A4.dimensions_mm();
+        Self {
+            page_width_mm: width,
+            page_height_mm: height,
+            qr_size_mm: 50.0,
+            bleed_mm: 0.0,
+            crop_marks: false,
+            fill_page_background: false,
+            unit: LengthUnit::Millimeters,
+        }
+    }
+}
+
+impl PdfOptions {
+    /// Set the trimmed page size from a preset
+    pub fn set_page_size(&mut self, page: PageSize) {
+        let (width, height) = page.dimensions_mm();
+        self.page_width_mm = width;
+        self.page_height_mm = height;
+    }
+
+    /// Check that the layout is physically possible
+    ///
+    /// # Returns
+    /// * `Ok(())` - Layout is valid
+    /// * `Err(String)` - Description of the first problem found
+    pub fn validate(&self) -> Result<(), String> {
+        if self.page_width_mm <= 0.0 || self.page_height_mm <= 0.0 {
+            return Err("Page size must be greater than zero".to_string());
+        }
+        if self.qr_size_mm <= 0.0 {
+            return Err("QR code size must be greater than zero".to_string());
+        }
+        if self.qr_size_mm > self.page_width_mm.min(self.page_height_mm) {
+            return Err(format!(
+                "QR code ({:.1} mm) does not fit on a {:.1} × {:.1} mm page",
+                self.qr_size_mm, self.page_width_mm, self.page_height_mm
+            ));
+        }
+        if self.bleed_mm < 0.0 {
+            return Err("Bleed cannot be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parse a physical length such as `50`, `50mm`, `2in` or `2.5 in`
+///
+/// Plain numbers are interpreted as millimeters.
+///
+/// # Returns
+/// * `Ok(f32)` - Length in millimeters
+/// * `Err(String)` - Error message if the value cannot be parsed
+pub fn parse_length_mm(s: &str) -> Result<f32, String> {
+    let text = s.trim().to_ascii_lowercase();
+    let (number, unit) = if let Some(n) = text.strip_suffix("mm") {
+        (n, LengthUnit::Millimeters)
+    } else if let Some(n) = text.strip_suffix("in") {
+        (n, LengthUnit::Inches)
+    } else {
+        (text.as_str(), LengthUnit::Millimeters)
+    };
+
+    number
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(|v| unit.unit_to_mm(v))
+        .ok_or_else(|| format!("Invalid length '{}' (expected e.g. 50mm or 2in)", s))
+}
+
+/// Generate a PDF document for the current QR code design
+///
+/// # Arguments
+/// * `spec` - Rendering specification containing all QR code settings
+/// * `options` - Physical page layout
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Complete PDF file contents
+/// * `Err(String)` - Error message if the layout is invalid or the QR code
+///   cannot be created
+pub fn generate_qr_pdf(spec: &QrSpec, options: &PdfOptions) -> Result<Vec<u8>, String> {
+    options.validate()?;
+
+    // === Step 1: Build Vector Geometry ===
+    let qr = vector::build_vector_qr(spec)?;
+
+    // === Step 2: Page Geometry (points, y grows upwards) ===
+    let pt = |mm: f32| mm * POINTS_PER_MM;
+    let slug = if options.crop_marks { pt(CROP_MARK_MARGIN_MM) } else { 0.0 };
+    let bleed = pt(options.bleed_mm);
+    let trim_w = pt(options.page_width_mm);
+    let trim_h = pt(options.page_height_mm);
+    let media_w = trim_w + 2.0 * (bleed + slug);
+    let media_h = trim_h + 2.0 * (bleed + slug);
+    let trim_x = slug + bleed;
+    let trim_y = slug + bleed;
+
+    // QR code centered on the trimmed page; the size applies to the
+    // longer side (the width of rMQR symbols)
+    let (width, height) = (qr.width as f32, qr.height as f32);
+    let scale = pt(options.qr_size_mm) / width.max(height);
+    let qr_left = trim_x + (trim_w - width * scale) / 2.0;
+    let qr_top = trim_y + (trim_h + height * scale) / 2.0;
+
+    // === Step 3: Resources ===
+    let mut pdf = PdfWriter::new();
+    let mut resources = Resources::default();
+
+    if spec.use_gradient {
+        let id = pdf.reserve();
+        pdf.set(id, gradient_pattern(spec, (width, height), scale, qr_left, qr_top));
+        resources.patterns.push(("P0".to_string(), id));
+    }
+    if let Some(img) = &spec.bg_image {
+        resources.add_image(&mut pdf, "Im0", img);
+    }
+    if let Some(img) = &spec.logo_image {
+        resources.add_image(&mut pdf, "Im1", img);
+    }
+
+    // === Step 4: Page Content ===
+    let mut content = String::new();
+
+    if options.fill_page_background {
+        let _ = writeln!(content, "{} rg", rgb(spec.bg_color));
+        let _ = writeln!(
+            content,
+            "{} {} {} {} re f",
+            num(slug), num(slug), num(trim_w + 2.0 * bleed), num(trim_h + 2.0 * bleed)
+        );
+    }
+
+    // Switch to image pixel coordinates (origin top-left, y down)
+    content.push_str("q\n");
+    let _ = writeln!(content, "{} 0 0 {} {} {} cm", num(scale), num(-scale), num(qr_left), num(qr_top));
+
+    let _ = writeln!(content, "{} rg", rgb(spec.bg_color));
+    let _ = writeln!(content, "0 0 {} {} re f", num(width), num(height));
+
+    if spec.bg_image.is_some() {
+        content.push_str("q\n");
+        if spec.bg_image_opacity < 1.0 {
+            let gs = resources.add_opacity(spec.bg_image_opacity);
+            let _ = writeln!(content, "/{} gs", gs);
+        }
+        draw_image(&mut content, "Im0", 0.0, 0.0, width, height);
+        content.push_str("Q\n");
+    }
+
+    if spec.qr_opacity < 1.0 {
+        let gs = resources.add_opacity(spec.qr_opacity);
+        let _ = writeln!(content, "/{} gs", gs);
+    }
+
+    let data_fill = if spec.use_gradient {
+        "/Pattern cs /P0 scn".to_string()
+    } else {
+        format!("{} rg", rgb(spec.fg_color))
+    };
+    let eye_fill = |color: Option<[u8; 3]>| match color {
+        Some(color) => format!("{} rg", rgb(color)),
+        None => data_fill.clone(),
+    };
+
+    fill_shapes(&mut content, &data_fill, &qr.data_shapes);
+    for eye in &qr.eyes {
+        fill_shapes(&mut content, &eye_fill(eye.frame_color), std::slice::from_ref(&eye.frame));
+        fill_shapes(&mut content, &eye_fill(eye.pupil_color), std::slice::from_ref(&eye.pupil));
+    }
+
+    if let (Some(_), Some(rect)) = (&spec.logo_image, qr.logo_rect) {
+        draw_image(&mut content, "Im1", rect.x, rect.y, rect.size, rect.size);
+    }
+
+    content.push_str("Q\n");
+
+    // The Swiss cross stays solid, so it is drawn after the opacity state is dropped
+    if !qr.swiss_cross.is_empty() {
+        content.push_str("q\n");
+        let _ = writeln!(content, "{} 0 0 {} {} {} cm", num(scale), num(-scale), num(qr_left), num(qr_top));
+        for (shape, color) in &qr.swiss_cross {
+            fill_shapes(&mut content, &format!("{} rg", rgb(*color)), std::slice::from_ref(shape));
+        }
+        content.push_str("Q\n");
+    }
+
+    // === Step 5: Crop Marks ===
+    if options.crop_marks {
+        crop_marks(&mut content, trim_x, trim_y, trim_w, trim_h, bleed);
+    }
+
+    // === Step 6: Assemble Document ===
+    let content_id = pdf.reserve();
+    pdf.set(content_id, PdfWriter::stream("", content.as_bytes()));
+
+    let pages_id = pdf.reserve();
+    let page_id = pdf.reserve();
+    let mut page = String::new();
+    let _ = write!(
+        page,
+        "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /BleedBox [{} {} {} {}] /TrimBox [{} {} {} {}] /Resources {} /Contents {} 0 R >>",
+        pages_id,
+        num(media_w), num(media_h),
+        num(slug), num(slug), num(media_w - slug), num(media_h - slug),
+        num(trim_x), num(trim_y), num(trim_x + trim_w), num(trim_y + trim_h),
+        resources.dictionary(),
+        content_id
+    );
+    pdf.set(page_id, page.into_bytes());
+    pdf.set(
+        pages_id,
+        format!("<< /Type /Pages /Kids [{} 0 R] /Count 1 >>", page_id).into_bytes(),
+    );
+
+    let catalog_id = pdf.reserve();
+    pdf.set(catalog_id, format!("<< /Type /Catalog /Pages {} 0 R >>", pages_id).into_bytes());
+
+    let info_id = pdf.reserve();
+    pdf.set(info_id, b"<< /Producer (QRtistry) /Title (QR Code) >>".to_vec());
+
+    Ok(pdf.finish(catalog_id, info_id))
+}
+
+/// Build the shading pattern equivalent of `colors::get_gradient_color`
+///
+/// Pattern coordinates are in default page space, so the gradient geometry
+/// is transformed from image pixels to page points here.
+fn gradient_pattern(spec: &QrSpec, (width, height): (f32, f32), scale: f32, left: f32, top: f32) -> Vec<u8> {
+    let to_page = |x: f32, y: f32| (left + x * scale, top - y * scale);
+    let function = format!(
+        "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+        rgb(spec.fg_color),
+        rgb(spec.gradient_color)
+    );
+
+    let shading = match spec.gradient_type {
+        GradientType::Radial => {
+            let (cx, cy) = to_page(width / 2.0, height / 2.0);
+            let radius = (width / 2.0).hypot(height / 2.0) * scale;
+            format!(
+                "<< /ShadingType 3 /ColorSpace /DeviceRGB /Coords [{0} {1} 0 {0} {1} {2}] /Function {3} /Extend [true true] >>",
+                num(cx), num(cy), num(radius), function
+            )
+        }
+        linear => {
+            let end = match linear {
+                GradientType::Horizontal => (width, 0.0),
+                GradientType::Vertical => (0.0, height),
+                // Diagonal: reaches the end color where x + y = width + height
+                _ => ((width + height) / 2.0, (width + height) / 2.0),
+            };
+            let (x0, y0) = to_page(0.0, 0.0);
+            let (x1, y1) = to_page(end.0, end.1);
+            format!(
+                "<< /ShadingType 2 /ColorSpace /DeviceRGB /Coords [{} {} {} {}] /Function {} /Extend [true true] >>",
+                num(x0), num(y0), num(x1), num(y1), function
+            )
+        }
+    };
+
+    format!("<< /Type /Pattern /PatternType 2 /Shading {} >>", shading).into_bytes()
+}
+
+/// Append path operators for a set of shapes and fill them
+fn fill_shapes(content: &mut String, fill: &str, shapes: &[Shape]) {
+    if shapes.is_empty() {
+        return;
+    }
+    content.push_str(fill);
+    content.push('\n');
+    for shape in shapes {
+        shape_path(content, shape);
+    }
+    content.push_str("f\n");
+}
+
+/// Append the path for one shape (in the current coordinate system)
+fn shape_path(content: &mut String, shape: &Shape) {
+    match *shape {
+        Shape::Rect { x, y, width, height } => {
+            let _ = writeln!(content, "{} {} {} {} re", num(x), num(y), num(width), num(height));
+        }
+        Shape::RoundedRect { x, y, width, height, radius } => {
+            let r = radius.min(width / 2.0).min(height / 2.0);
+            let k = r * KAPPA;
+            let (right, bottom) = (x + width, y + height);
+            let _ = writeln!(content, "{} {} m", num(x + r), num(y));
+            let _ = writeln!(content, "{} {} l", num(right - r), num(y));
+            curve(content, (right - r + k, y), (right, y + r - k), (right, y + r));
+            let _ = writeln!(content, "{} {} l", num(right), num(bottom - r));
+            curve(content, (right, bottom - r + k), (right - r + k, bottom), (right - r, bottom));
+            let _ = writeln!(content, "{} {} l", num(x + r), num(bottom));
+            curve(content, (x + r - k, bottom), (x, bottom - r + k), (x, bottom - r));
+            let _ = writeln!(content, "{} {} l", num(x), num(y + r));
+            curve(content, (x, y + r - k), (x + r - k, y), (x + r, y));
+            content.push_str("h\n");
+        }
+        Shape::Circle { cx, cy, r } => {
+            let k = r * KAPPA;
+            let _ = writeln!(content, "{} {} m", num(cx + r), num(cy));
+            curve(content, (cx + r, cy + k), (cx + k, cy + r), (cx, cy + r));
+            curve(content, (cx - k, cy + r), (cx - r, cy + k), (cx - r, cy));
+            curve(content, (cx - r, cy - k), (cx - k, cy - r), (cx, cy - r));
+            curve(content, (cx + k, cy - r), (cx + r, cy - k), (cx + r, cy));
+            content.push_str("h\n");
+        }
+        Shape::RoundedCorners { x, y, width, height, radii } => {
+            let [tl, tr, br, bl] = radii;
+            let (right, bottom) = (x + width, y + height);
+            let _ = writeln!(content, "{} {} m", num(x + tl), num(y));
+            let _ = writeln!(content, "{} {} l", num(right - tr), num(y));
+            if tr > 0.0 {
+                let k = tr * KAPPA;
+                curve(content, (right - tr + k, y), (right, y + tr - k), (right, y + tr));
+            }
+            let _ = writeln!(content, "{} {} l", num(right), num(bottom - br));
+            if br > 0.0 {
+                let k = br * KAPPA;
+                curve(content, (right, bottom - br + k), (right - br + k, bottom), (right - br, bottom));
+            }
+            let _ = writeln!(content, "{} {} l", num(x + bl), num(bottom));
+            if bl > 0.0 {
+                let k = bl * KAPPA;
+                curve(content, (x + bl - k, bottom), (x, bottom - bl + k), (x, bottom - bl));
+            }
+            let _ = writeln!(content, "{} {} l", num(x), num(y + tl));
+            if tl > 0.0 {
+                let k = tl * KAPPA;
+                curve(content, (x, y + tl - k), (x + tl - k, y), (x + tl, y));
+            }
+            content.push_str("h\n");
+        }
+        Shape::Path(ref path) => {
+            for contour in &path.contours {
+                let _ = writeln!(content, "{} {} m", num(contour.start.0), num(contour.start.1));
+                for segment in &contour.segments {
+                    match *segment {
+                        Segment::Line((x, y)) => {
+                            let _ = writeln!(content, "{} {} l", num(x), num(y));
+                        }
+                        Segment::Cubic(c1, c2, end) => curve(content, c1, c2, end),
+                    }
+                }
+                content.push_str("h\n");
+            }
+        }
+    }
+}
+
+/// Append a cubic Bezier curve operator
+fn curve(content: &mut String, c1: (f32, f32), c2: (f32, f32), end: (f32, f32)) {
+    let _ = writeln!(
+        content,
+        "{} {} {} {} {} {} c",
+        num(c1.0), num(c1.1), num(c2.0), num(c2.1), num(end.0), num(end.1)
+    );
+}
+
+/// Draw an image XObject into a rectangle in image pixel coordinates
+///
+/// The outer transform flips the y axis, so the image is flipped back here.
+fn draw_image(content: &mut String, name: &str, x: f32, y: f32, width: f32, height: f32) {
+    let _ = writeln!(
+        content,
+        "q {} 0 0 -{} {} {} cm /{} Do Q",
+        num(width),
+        num(height),
+        num(x),
+        num(y + height),
+        name
+    );
+}
+
+/// Draw crop marks at the four trim corners, outside the bleed area
+fn crop_marks(content: &mut String, trim_x: f32, trim_y: f32, trim_w: f32, trim_h: f32, bleed: f32) {
+    let gap = CROP_MARK_GAP_MM * POINTS_PER_MM + bleed;
+    let length = CROP_MARK_LENGTH_MM * POINTS_PER_MM;
+    let right = trim_x + trim_w;
+    let top = trim_y + trim_h;
+
+    content.push_str("q 0 G 0.25 w\n");
+    for (x, dir_x) in [(trim_x, -1.0), (right, 1.0)] {
+        for (y, dir_y) in [(trim_y, -1.0), (top, 1.0)] {
+            // Horizontal mark in line with the trim edge
+            let _ = writeln!(
+                content,
+                "{} {} m {} {} l S",
+                num(x + dir_x * gap), num(y), num(x + dir_x * (gap + length)), num(y)
+            );
+            // Vertical mark in line with the trim edge
+            let _ = writeln!(
+                content,
+                "{} {} m {} {} l S",
+                num(x), num(y + dir_y * gap), num(x), num(y + dir_y * (gap + length))
+            );
+        }
+    }
+    content.push_str("Q\n");
+}
+
+/// Format an RGB color as PDF color operands (0.0-1.0)
+fn rgb(color: [u8; 3]) -> String {
+    format!(
+        "{} {} {}",
+        num(color[0] as f32 / 255.0),
+        num(color[1] as f32 / 255.0),
+        num(color[2] as f32 / 255.0)
+    )
+}
+
+/// Named page resources collected while building the content stream
+#[derive(Default)]
+struct Resources {
+    /// Pattern name → object id
+    patterns: Vec<(String, usize)>,
+    /// Image XObject name → object id
+    images: Vec<(String, usize)>,
+    /// Graphics state name → fill opacity
+    opacities: Vec<(String, f32)>,
+}
+
+impl Resources {
+    /// Embed an image (with soft mask for transparency) and register it
+    fn add_image(&mut self, pdf: &mut PdfWriter, name: &str, img: &DynamicImage) {
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+
+        let mut color = Vec::with_capacity((width * height * 3) as usize);
+        let mut alpha = Vec::with_capacity((width * height) as usize);
+        for pixel in rgba.pixels() {
+            color.extend_from_slice(&pixel.0[..3]);
+            alpha.push(pixel[3]);
+        }
+
+        let mask = if alpha.iter().any(|a| *a < 255) {
+            let id = pdf.reserve();
+            let dict = format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8",
+                width, height
+            );
+            pdf.set(id, PdfWriter::stream(&dict, &alpha));
+            format!(" /SMask {} 0 R", id)
+        } else {
+            String::new()
+        };
+
+        let id = pdf.reserve();
+        let dict = format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8{}",
+            width, height, mask
+        );
+        pdf.set(id, PdfWriter::stream(&dict, &color));
+        self.images.push((name.to_string(), id));
+    }
+
+    /// Register a graphics state with the given fill opacity
+    ///
+    /// # Returns
+    /// Resource name of the graphics state
+    fn add_opacity(&mut self, opacity: f32) -> String {
+        let name = format!("GS{}", self.opacities.len());
+        self.opacities.push((name.clone(), opacity.clamp(0.0, 1.0)));
+        name
+    }
+
+    /// Serialize as a PDF resource dictionary
+    fn dictionary(&self) -> String {
+        let mut dict = String::from("<<");
+        if !self.patterns.is_empty() {
+            dict.push_str(" /Pattern <<");
+            for (name, id) in &self.patterns {
+                let _ = write!(dict, " /{} {} 0 R", name, id);
+            }
+            dict.push_str(" >>");
+        }
+        if !self.images.is_empty() {
+            dict.push_str(" /XObject <<");
+            for (name, id) in &self.images {
+                let _ = write!(dict, " /{} {} 0 R", name, id);
+            }
+            dict.push_str(" >>");
+        }
+        if !self.opacities.is_empty() {
+            dict.push_str(" /ExtGState <<");
+            for (name, opacity) in &self.opacities {
+                let _ = write!(dict, " /{} << /Type /ExtGState /ca {} >>", name, num(*opacity));
+            }
+            dict.push_str(" >>");
+        }
+        dict.push_str(" >>");
+        dict
+    }
+}
+
+/// Minimal PDF object writer with cross-reference table
+struct PdfWriter {
+    /// Object bodies indexed by object number - 1
+    objects: Vec<Vec<u8>>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        Self { objects: Vec::new() }
+    }
+
+    /// Reserve an object number so it can be referenced before it is written
+    fn reserve(&mut self) -> usize {
+        self.objects.push(Vec::new());
+        self.objects.len()
+    }
+
+    /// Set the body of a reserved object
+    fn set(&mut self, id: usize, body: Vec<u8>) {
+        self.objects[id - 1] = body;
+    }
+
+    /// Build a Flate-compressed stream object body
+    ///
+    /// # Arguments
+    /// * `dict_entries` - Extra dictionary entries (without `<<`/`>>`)
+    /// * `data` - Uncompressed stream data
+    fn stream(dict_entries: &str, data: &[u8]) -> Vec<u8> {
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(data, 6);
+        let mut body = format!(
+            "<< {} /Filter /FlateDecode /Length {} >>\nstream\n",
+            dict_entries,
+            compressed.len()
+        ).into_bytes();
+        body.extend_from_slice(&compressed);
+        body.extend_from_slice(b"\nendstream");
+        body
+    }
+
+    /// Serialize all objects, the cross-reference table and the trailer
+    fn finish(self, root_id: usize, info_id: usize) -> Vec<u8> {
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+
+        for (index, body) in self.objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.objects.len() + 1,
+            root_id,
+            info_id,
+            xref_offset
+        );
+        out.extend_from_slice(xref.as_bytes());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_length_mm() {
+        assert_eq!(parse_length_mm("50"), Ok(50.0));
+        assert_eq!(parse_length_mm("50mm"), Ok(50.0));
+        assert_eq!(parse_length_mm("2in"), Ok(50.8));
+        assert!(parse_length_mm("-3mm").is_err());
+        assert!(parse_length_mm("abc").is_err());
+    }
+
+    #[test]
+    fn test_options_validation() {
+        let mut options = PdfOptions::default();
+        assert!(options.validate().is_ok());
+
+        options.set_page_size(PageSize::BusinessCard);
+        options.qr_size_mm = 60.0;
+        assert!(options.validate().is_err());
+    }
+
+    #[test]
+    fn test_pdf_structure() {
+        let options = PdfOptions { bleed_mm: 3.0, crop_marks: true, ..Default::default() };
+        let pdf = generate_qr_pdf(&QrSpec::default(), &options).unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/TrimBox"));
+        assert!(text.contains("/BleedBox"));
+
+        // Output is deterministic
+        assert_eq!(pdf, generate_qr_pdf(&QrSpec::default(), &options).unwrap());
+    }
+}
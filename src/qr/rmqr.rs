@@ -0,0 +1,511 @@
+//! Rectangular Micro QR (rMQR) encoder
+//!
+//! The `qrcode` crate only knows square symbols, so rMQR (ISO/IEC 23941)
+//! is encoded here: segment bits, Reed-Solomon blocks (using the crate's
+//! GF(256) arithmetic), function patterns, codeword placement, the single
+//! fixed mask and both copies of the format information. The result is a
+//! `QrMatrix`, so the styling pipeline renders it like any other symbol.
+//!
+//! rMQR only defines Medium and High error correction and encodes the
+//! content as a single segment.
+
+use std::ops::RangeInclusive;
+
+use qrcode::ec;
+
+use crate::qr::encoding::{self, push_bits};
+use crate::qr::matrix::{ModuleKind, QrMatrix};
+use crate::types::EncodingMode;
+
+/// Symbol sizes as `(height, width)` in modules, indexed by version - 1
+pub const SIZES: [(usize, usize); 32] = [
+    (7, 43), (7, 59), (7, 77), (7, 99), (7, 139),
+    (9, 43), (9, 59), (9, 77), (9, 99), (9, 139),
+    (11, 27), (11, 43), (11, 59), (11, 77), (11, 99), (11, 139),
+    (13, 27), (13, 43), (13, 59), (13, 77), (13, 99), (13, 139),
+    (15, 43), (15, 59), (15, 77), (15, 99), (15, 139),
+    (17, 43), (17, 59), (17, 77), (17, 99), (17, 139),
+];
+
+/// Reed-Solomon blocks at Medium level: `(count, total codewords, data codewords)`
+const BLOCKS_M: [&[(usize, usize, usize)]; 32] = [
+    &[(1, 13, 6)], &[(1, 21, 12)], &[(1, 32, 20)], &[(1, 44, 28)], &[(1, 68, 44)],
+    &[(1, 21, 12)], &[(1, 33, 21)], &[(1, 49, 31)], &[(1, 66, 42)], &[(1, 49, 31), (1, 50, 32)],
+    &[(1, 15, 7)], &[(1, 31, 19)], &[(1, 47, 31)], &[(1, 67, 43)], &[(1, 44, 28), (1, 45, 29)], &[(2, 66, 42)],
+    &[(1, 21, 12)], &[(1, 41, 27)], &[(1, 60, 38)], &[(1, 42, 26), (1, 43, 27)], &[(1, 56, 36), (1, 57, 37)],
+    &[(2, 55, 35), (1, 56, 36)],
+    &[(1, 51, 33)], &[(1, 74, 48)], &[(1, 51, 33), (1, 52, 34)], &[(2, 68, 44)], &[(2, 66, 42), (1, 67, 43)],
+    &[(1, 61, 39)], &[(2, 44, 28)], &[(2, 61, 39)], &[(2, 53, 33), (1, 54, 34)], &[(4, 58, 38)],
+];
+
+/// Reed-Solomon blocks at High level: `(count, total codewords, data codewords)`
+const BLOCKS_H: [&[(usize, usize, usize)]; 32] = [
+    &[(1, 13, 3)], &[(1, 21, 7)], &[(1, 32, 10)], &[(1, 44, 14)], &[(2, 34, 12)],
+    &[(1, 21, 7)], &[(1, 33, 11)], &[(1, 24, 8), (1, 25, 9)], &[(2, 33, 11)], &[(3, 33, 11)],
+    &[(1, 15, 5)], &[(1, 31, 11)], &[(1, 23, 7), (1, 24, 8)], &[(1, 33, 11), (1, 34, 12)],
+    &[(1, 44, 14), (1, 45, 15)], &[(3, 44, 14)],
+    &[(1, 21, 7)], &[(1, 41, 13)], &[(2, 30, 10)], &[(1, 42, 14), (1, 43, 15)], &[(1, 37, 11), (2, 38, 12)],
+    &[(2, 41, 13), (2, 42, 14)],
+    &[(1, 25, 7), (1, 26, 8)], &[(2, 37, 13)], &[(2, 34, 10), (1, 35, 11)], &[(4, 34, 12)],
+    &[(1, 39, 13), (4, 40, 14)],
+    &[(1, 30, 10), (1, 31, 11)], &[(2, 44, 14)], &[(1, 40, 12), (2, 41, 13)], &[(4, 40, 14)],
+    &[(2, 38, 12), (4, 39, 13)],
+];
+
+/// Character count indicator length for numeric, alphanumeric, byte and kanji mode
+const CHAR_COUNT_BITS: [[usize; 4]; 32] = [
+    [4, 3, 3, 2], [5, 5, 4, 3], [6, 5, 5, 4], [7, 6, 5, 5], [7, 6, 6, 5],
+    [5, 5, 4, 3], [6, 5, 5, 4], [7, 6, 5, 5], [7, 6, 6, 5], [8, 7, 6, 6],
+    [4, 4, 3, 2], [6, 5, 5, 4], [7, 6, 5, 5], [7, 6, 6, 5], [8, 7, 6, 6], [8, 7, 7, 6],
+    [5, 5, 4, 3], [6, 6, 5, 5], [7, 6, 6, 5], [7, 7, 6, 5], [8, 7, 7, 6], [8, 8, 7, 7],
+    [7, 6, 6, 5], [7, 7, 6, 5], [8, 7, 7, 6], [8, 7, 7, 6], [9, 8, 7, 7],
+    [7, 6, 6, 5], [8, 7, 6, 6], [8, 7, 7, 6], [8, 8, 7, 6], [9, 8, 8, 7],
+];
+
+/// Characters of alphanumeric mode, in value order
+const ALPHANUMERIC_TABLE: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Generator polynomial of the 18-bit format information BCH code
+const FORMAT_GENERATOR: u32 = 0x1F25;
+
+/// Format information masks next to the finder and the sub-finder
+const FORMAT_MASK_FINDER: u32 = 0b011111101010110010;
+const FORMAT_MASK_SUB_FINDER: u32 = 0b100000101001111011;
+
+/// Encoded rMQR symbol
+#[derive(Debug, Clone)]
+pub struct RmqrSymbol {
+    /// Module colors and roles
+    pub matrix: QrMatrix,
+    /// Version (1-32, see `SIZES`)
+    pub version: u8,
+    /// Bits used by the content (mode indicator, count and data)
+    pub data_bits: usize,
+    /// Data bits the symbol holds at the chosen level
+    pub capacity_bits: usize,
+}
+
+/// Version number (1-32) of an rMQR size
+///
+/// # Arguments
+/// * `height` - Symbol height in modules (7-17)
+/// * `width` - Symbol width in modules (27-139)
+pub fn size_index(height: usize, width: usize) -> Option<u8> {
+    SIZES
+        .iter()
+        .position(|&size| size == (height, width))
+        .map(|i| i as u8 + 1)
+}
+
+/// Name of an rMQR version, e.g. "R11x43"
+pub fn size_name(version: u8) -> String {
+    let (height, width) = SIZES[version as usize - 1];
+    format!("R{}x{}", height, width)
+}
+
+/// Encode content into the smallest fitting rMQR symbol
+///
+/// Without a version constraint the symbol with the smallest area wins,
+/// so short content gets a short, narrow code.
+///
+/// # Arguments
+/// * `data` - Content bytes (Shift JIS pairs for kanji mode)
+/// * `mode` - Segment mode (automatic picks the densest mode for the whole content)
+/// * `versions` - Versions (1-32) to try
+/// * `high` - High error correction instead of Medium
+///
+/// # Returns
+/// * `Ok(RmqrSymbol)` - Encoded symbol
+/// * `Err(String)` - Error message if the content does not fit any allowed version
+pub fn encode(
+    data: &[u8],
+    mode: EncodingMode,
+    versions: RangeInclusive<u8>,
+    high: bool,
+) -> Result<RmqrSymbol, String> {
+    let mode = match mode {
+        EncodingMode::Auto if data.iter().all(u8::is_ascii_digit) => EncodingMode::Numeric,
+        EncodingMode::Auto if data.iter().all(|c| ALPHANUMERIC_TABLE.contains(c)) => EncodingMode::Alphanumeric,
+        EncodingMode::Auto => EncodingMode::Byte,
+        mode => mode,
+    };
+
+    // === Step 1: Pick the Smallest Version That Fits ===
+    let mut candidates: Vec<u8> = versions.collect();
+    candidates.sort_by_key(|&v| {
+        let (height, width) = SIZES[v as usize - 1];
+        (height * width, v)
+    });
+    let fitted = candidates.into_iter().find_map(|version| {
+        let blocks = blocks(version, high);
+        let capacity_bits = 8 * blocks.iter().map(|&(count, _, data)| count * data).sum::<usize>();
+        let bits = segment_bits(data, mode, version)?;
+        (bits.len() <= capacity_bits).then_some((version, bits, capacity_bits))
+    });
+    let Some((version, bits, capacity_bits)) = fitted else {
+        return Err("Content does not fit in any allowed rMQR size".to_string());
+    };
+    let data_bits = bits.len();
+
+    // === Step 2: Terminate and Pad ===
+    let codewords = encoding::terminate_and_pad(bits, capacity_bits, 3);
+
+    // === Step 3: Error Correction and Interleaving ===
+    let codewords = interleave(&codewords, blocks(version, high));
+
+    // === Step 4: Draw the Symbol ===
+    let (height, width) = SIZES[version as usize - 1];
+    let mut grid = Grid::new(width, height);
+    grid.draw_function_patterns();
+    grid.draw_codewords(&codewords);
+    grid.draw_format_info(version, high);
+
+    Ok(RmqrSymbol { matrix: grid.into_matrix(), version, data_bits, capacity_bits })
+}
+
+/// Reed-Solomon blocks of a version and level
+fn blocks(version: u8, high: bool) -> &'static [(usize, usize, usize)] {
+    let table = if high { &BLOCKS_H } else { &BLOCKS_M };
+    table[version as usize - 1]
+}
+
+/// Mode indicator, character count and data bits of a single segment
+///
+/// # Returns
+/// `None` if the character count does not fit the count indicator
+fn segment_bits(data: &[u8], mode: EncodingMode, version: u8) -> Option<Vec<bool>> {
+    let (indicator, column, count) = match mode {
+        EncodingMode::Numeric => (0b001, 0, data.len()),
+        EncodingMode::Alphanumeric => (0b010, 1, data.len()),
+        EncodingMode::Auto | EncodingMode::Byte => (0b011, 2, data.len()),
+        EncodingMode::Kanji => (0b100, 3, data.len() / 2),
+    };
+    let count_bits = CHAR_COUNT_BITS[version as usize - 1][column];
+    if count >= 1 << count_bits {
+        return None;
+    }
+
+    let mut bits = Vec::new();
+    push_bits(&mut bits, indicator, 3);
+    push_bits(&mut bits, count as u32, count_bits);
+    match mode {
+        EncodingMode::Numeric => {
+            for group in data.chunks(3) {
+                let value = group.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u32);
+                push_bits(&mut bits, value, [0, 4, 7, 10][group.len()]);
+            }
+        }
+        EncodingMode::Alphanumeric => {
+            let value = |c: &u8| ALPHANUMERIC_TABLE.iter().position(|t| t == c).unwrap_or(0) as u32;
+            for pair in data.chunks(2) {
+                match pair {
+                    [a, b] => push_bits(&mut bits, value(a) * 45 + value(b), 11),
+                    [a] => push_bits(&mut bits, value(a), 6),
+                    _ => unreachable!(),
+                }
+            }
+        }
+        EncodingMode::Auto | EncodingMode::Byte => {
+            for &byte in data {
+                push_bits(&mut bits, byte as u32, 8);
+            }
+        }
+        EncodingMode::Kanji => {
+            for pair in data.chunks(2) {
+                let code = u16::from_be_bytes([pair[0], pair[1]]) as u32;
+                let offset = if code <= 0x9FFC { code - 0x8140 } else { code - 0xC140 };
+                push_bits(&mut bits, (offset >> 8) * 0xC0 + (offset & 0xFF), 13);
+            }
+        }
+    }
+    Some(bits)
+}
+
+/// Split data codewords into blocks, add error correction and interleave
+///
+/// Data codewords are read column by column across the blocks, followed
+/// by the error correction codewords in the same order.
+fn interleave(data: &[u8], blocks: &[(usize, usize, usize)]) -> Vec<u8> {
+    let mut data_blocks = Vec::new();
+    let mut ec_blocks = Vec::new();
+    let mut rest = data;
+    for &(count, total, size) in blocks {
+        for _ in 0..count {
+            let (block, tail) = rest.split_at(size);
+            ec_blocks.push(ec::create_error_correction_code(block, total - size));
+            data_blocks.push(block);
+            rest = tail;
+        }
+    }
+
+    let mut codewords = Vec::new();
+    let longest = data_blocks.iter().map(|b| b.len()).max().unwrap_or(0);
+    for i in 0..longest {
+        codewords.extend(data_blocks.iter().filter_map(|b| b.get(i)));
+    }
+    let ec_len = ec_blocks.first().map_or(0, Vec::len);
+    for i in 0..ec_len {
+        codewords.extend(ec_blocks.iter().map(|b| b[i]));
+    }
+    codewords
+}
+
+/// BCH-protected format information before masking
+///
+/// Six data bits (level and version) followed by 12 check bits.
+fn format_bits(version: u8, high: bool) -> u32 {
+    let data = (high as u32) << 5 | (version as u32 - 1);
+    let mut remainder = data << 12;
+    for bit in (12..18).rev() {
+        if remainder & (1 << bit) != 0 {
+            remainder ^= FORMAT_GENERATOR << (bit - 12);
+        }
+    }
+    data << 12 | remainder
+}
+
+/// Symbol under construction: colors of set modules and their roles
+struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<bool>>,
+    kinds: Vec<ModuleKind>,
+}
+
+impl Grid {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![None; width * height],
+            kinds: vec![ModuleKind::Data; width * height],
+        }
+    }
+
+    /// Set a function module unless an earlier pattern already claimed it
+    fn set(&mut self, x: usize, y: usize, dark: bool, kind: ModuleKind) {
+        if x < self.width && y < self.height && self.cells[y * self.width + x].is_none() {
+            self.cells[y * self.width + x] = Some(dark);
+            self.kinds[y * self.width + x] = kind;
+        }
+    }
+
+    /// Alignment pattern columns (centers) for the symbol width
+    fn alignment_columns(&self) -> &'static [usize] {
+        match self.width {
+            43 => &[21],
+            59 => &[19, 39],
+            77 => &[25, 51],
+            99 => &[23, 49, 75],
+            139 => &[27, 55, 83, 111],
+            _ => &[],
+        }
+    }
+
+    /// Draw finder, sub-finder, corner, alignment and timing patterns and reserve format areas
+    ///
+    /// Patterns are drawn in priority order; later ones never overwrite
+    /// modules set by earlier ones.
+    fn draw_function_patterns(&mut self) {
+        let (w, h) = (self.width, self.height);
+
+        // === Finder Pattern and Separator ===
+        for y in 0..7 {
+            for x in 0..7 {
+                let ring = x.min(y).min(6 - x).min(6 - y);
+                self.set(x, y, ring != 1, ModuleKind::Finder);
+            }
+        }
+        for i in 0..8 {
+            self.set(7, i, false, ModuleKind::Separator);
+            if h >= 9 {
+                self.set(i, 7, false, ModuleKind::Separator);
+            }
+        }
+
+        // === Sub-finder (5x5, Bottom-Right) ===
+        for y in 0..5 {
+            for x in 0..5 {
+                let ring = x.min(y).min(4 - x).min(4 - y);
+                self.set(w - 5 + x, h - 5 + y, ring != 1, ModuleKind::Alignment);
+            }
+        }
+
+        // === Corner Patterns ===
+        for x in 0..3 {
+            self.set(x, h - 1, true, ModuleKind::Timing);
+        }
+        if h >= 11 {
+            self.set(0, h - 2, true, ModuleKind::Timing);
+            self.set(1, h - 2, false, ModuleKind::Timing);
+        }
+        for (x, y, dark) in [(w - 1, 0, true), (w - 2, 0, true), (w - 1, 1, true), (w - 2, 1, false)] {
+            self.set(x, y, dark, ModuleKind::Timing);
+        }
+
+        // === Alignment Patterns (3x3, Top and Bottom Edge) ===
+        for &cx in self.alignment_columns() {
+            for dy in 0..3 {
+                for dx in 0..3 {
+                    let dark = dx != 1 || dy != 1;
+                    self.set(cx + dx - 1, dy, dark, ModuleKind::Timing);
+                    self.set(cx + dx - 1, h - 3 + dy, dark, ModuleKind::Timing);
+                }
+            }
+        }
+
+        // === Timing Patterns ===
+        for x in 0..w {
+            self.set(x, 0, x % 2 == 0, ModuleKind::Timing);
+            self.set(x, h - 1, x % 2 == 0, ModuleKind::Timing);
+        }
+        for &x in [0, w - 1].iter().chain(self.alignment_columns()) {
+            for y in 0..h {
+                self.set(x, y, y % 2 == 0, ModuleKind::Timing);
+            }
+        }
+
+        // === Format Information Areas (Filled Later) ===
+        for (x, y) in format_positions(w, h).into_iter().flatten() {
+            self.set(x, y, false, ModuleKind::FormatInfo);
+        }
+    }
+
+    /// Place codewords in two-module columns from the bottom-right, then mask
+    ///
+    /// Modules left after the last codeword (remainder bits) are light
+    /// before masking.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let (w, h) = (self.width, self.height);
+        let mut bits = codewords
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |i| byte >> i & 1 == 1));
+
+        let mut column = w - 2;
+        let mut upward = true;
+        loop {
+            let rows: Vec<usize> = if upward { (1..h - 1).rev().collect() } else { (1..h - 1).collect() };
+            for y in rows {
+                for x in [column, column - 1] {
+                    let index = y * w + x;
+                    if self.cells[index].is_none() {
+                        let masked = (y / 2 + x / 3) % 2 == 0;
+                        self.cells[index] = Some(bits.next().unwrap_or(false) != masked);
+                    }
+                }
+            }
+            if column < 3 {
+                break;
+            }
+            column -= 2;
+            upward = !upward;
+        }
+    }
+
+    /// Fill both copies of the format information
+    fn draw_format_info(&mut self, version: u8, high: bool) {
+        let bits = format_bits(version, high);
+        let [finder_side, sub_side] = format_positions(self.width, self.height);
+        for (mask, positions) in [(FORMAT_MASK_FINDER, finder_side), (FORMAT_MASK_SUB_FINDER, sub_side)] {
+            let value = bits ^ mask;
+            for (n, (x, y)) in positions.into_iter().enumerate() {
+                self.cells[y * self.width + x] = Some(value >> n & 1 == 1);
+            }
+        }
+    }
+
+    fn into_matrix(self) -> QrMatrix {
+        let dark = self.cells.into_iter().map(|cell| cell.unwrap_or(false)).collect();
+        QrMatrix::from_parts(
+            self.width,
+            self.height,
+            dark,
+            self.kinds,
+            vec![(0, 0)],
+            vec![(self.width - 5, self.height - 5)],
+        )
+    }
+}
+
+/// Module positions of the format information bits (least significant first)
+///
+/// One copy sits right of the finder pattern, the other left of the
+/// sub-finder.
+fn format_positions(w: usize, h: usize) -> [[(usize, usize); 18]; 2] {
+    let mut finder_side = [(0, 0); 18];
+    let mut sub_side = [(0, 0); 18];
+    for n in 0..15 {
+        finder_side[n] = (8 + n / 5, 1 + n % 5);
+        sub_side[n] = (w - 8 + n / 5, h - 6 + n % 5);
+    }
+    for n in 15..18 {
+        finder_side[n] = (11, n - 14);
+        sub_side[n] = (w - 20 + n, h - 6);
+    }
+    [finder_side, sub_side]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_tables_fill_each_symbol() {
+        for (i, &(height, width)) in SIZES.iter().enumerate() {
+            let mut grid = Grid::new(width, height);
+            grid.draw_function_patterns();
+            let data_modules = grid.cells.iter().filter(|c| c.is_none()).count();
+
+            for high in [false, true] {
+                let blocks = blocks(i as u8 + 1, high);
+                let total: usize = blocks.iter().map(|&(count, total, _)| count * total).sum();
+                assert_eq!(total, data_modules / 8, "R{}x{} high={}", height, width, high);
+
+                // Every block of a symbol has the same number of EC codewords
+                let ec = blocks[0].1 - blocks[0].2;
+                assert!(blocks.iter().all(|&(_, total, data)| total - data == ec));
+            }
+        }
+    }
+
+    #[test]
+    fn test_smallest_fitting_symbol() {
+        // R11x27 has fewer modules than R7x43
+        let symbol = encode(b"12345", EncodingMode::Auto, 1..=32, false).unwrap();
+        assert_eq!(size_name(symbol.version), "R11x27");
+        assert_eq!(symbol.data_bits, 3 + 4 + 10 + 7);
+        assert_eq!((symbol.matrix.width(), symbol.matrix.height()), (27, 11));
+
+        // Limited to tall symbols
+        let symbol = encode(b"PART-0042", EncodingMode::Auto, 23..=32, true).unwrap();
+        assert_eq!(SIZES[symbol.version as usize - 1].0, 15);
+
+        assert!(encode(&[b'x'; 200], EncodingMode::Byte, 1..=32, true).is_err());
+    }
+
+    #[test]
+    fn test_function_patterns() {
+        let symbol = encode(b"HELLO", EncodingMode::Auto, 12..=12, false).unwrap();
+        let matrix = &symbol.matrix;
+
+        assert_eq!(matrix.kind(3, 3), ModuleKind::Finder);
+        assert_eq!(matrix.kind(7, 3), ModuleKind::Separator);
+        assert_eq!(matrix.kind(40, 8), ModuleKind::Alignment);
+        assert_eq!(matrix.kind(21, 5), ModuleKind::Timing);
+        assert_eq!(matrix.kind(9, 2), ModuleKind::FormatInfo);
+        assert_eq!(matrix.alignment_positions(), &[(38, 6)]);
+
+        // Sub-finder: dark ring, light ring, dark center
+        assert!(matrix.is_dark(38, 6) && !matrix.is_dark(39, 7) && matrix.is_dark(40, 8));
+        // Edge alignment pattern: 3x3 with a light center
+        assert!(matrix.is_dark(20, 1) && !matrix.is_dark(21, 1) && matrix.is_dark(22, 1));
+    }
+
+    #[test]
+    fn test_format_bits() {
+        // Known BCH(18, 6) code word for data 000111 (same code as QR version info)
+        assert_eq!(format_bits(8, false), 0x07C94);
+        assert_eq!(format_bits(1, false) >> 12, 0);
+        assert_eq!(format_bits(32, true) >> 12, 0b111111);
+    }
+}
@@ -0,0 +1,457 @@
+//! Scannability scoring
+//!
+//! Measures how much abuse a rendered design survives before it stops
+//! decoding. The code is normalized to a fixed resolution and re-decoded
+//! under increasingly severe simulated degradations (small print, camera
+//! blur, JPEG compression, keystone skew, sensor noise and faded contrast).
+//! The last level each degradation survives is its margin; the margins
+//! together form a 0-100 score.
+
+use image::{imageops, GrayImage, ImageFormat, Luma, RgbaImage};
+
+use crate::qr::spec::QrSpec;
+use crate::qr::{encoding, generator, structured};
+use crate::qr::verify::{self, Expected, Verification};
+
+/// Pixels per module of the normalized image that degradations start from
+const WORK_PIXELS_PER_MODULE: f32 = 6.0;
+
+/// Fixed seed so noise results are reproducible between runs
+const NOISE_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A simulated real-world degradation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Degradation {
+    /// Printed or displayed with fewer pixels (or dots) per module
+    Downscale,
+    /// Out-of-focus or motion-blurred camera
+    Blur,
+    /// Lossy JPEG re-compression (messengers, social media)
+    Jpeg,
+    /// Code photographed at an angle (keystone distortion)
+    Skew,
+    /// Sensor noise in low light
+    Noise,
+    /// Faded print or washed-out screen
+    Contrast,
+}
+
+impl Degradation {
+    /// All degradations, in report order
+    pub const ALL: [Degradation; 6] = [
+        Degradation::Downscale,
+        Degradation::Blur,
+        Degradation::Jpeg,
+        Degradation::Skew,
+        Degradation::Noise,
+        Degradation::Contrast,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Degradation::Downscale => "Downscaling",
+            Degradation::Blur => "Blur",
+            Degradation::Jpeg => "JPEG compression",
+            Degradation::Skew => "Perspective skew",
+            Degradation::Noise => "Noise",
+            Degradation::Contrast => "Reduced contrast",
+        }
+    }
+
+    /// Severity ladder, from mild to extreme
+    fn levels(self) -> &'static [f32] {
+        match self {
+            // Pixels per module
+            Degradation::Downscale => &[5.0, 4.0, 3.5, 3.0, 2.5, 2.0],
+            // Blur radius (sigma) in modules
+            Degradation::Blur => &[0.1, 0.15, 0.2, 0.25, 0.3, 0.4],
+            // JPEG quality
+            Degradation::Jpeg => &[50.0, 30.0, 20.0, 10.0, 5.0, 2.0],
+            // Fraction by which the far edge is narrowed
+            Degradation::Skew => &[0.15, 0.3, 0.45, 0.6, 0.7, 0.8],
+            // Standard deviation in grey levels
+            Degradation::Noise => &[10.0, 20.0, 30.0, 40.0, 55.0, 70.0],
+            // Remaining fraction of the original contrast
+            Degradation::Contrast => &[0.6, 0.45, 0.35, 0.25, 0.2, 0.15],
+        }
+    }
+
+    /// Describe one severity level (e.g. "quality 30")
+    fn describe(self, level: f32) -> String {
+        match self {
+            Degradation::Downscale => format!("{} px/module", level),
+            Degradation::Blur => format!("blur {} modules", level),
+            Degradation::Jpeg => format!("quality {}", level),
+            Degradation::Skew => format!("{:.0}% keystone", level * 100.0),
+            Degradation::Noise => format!("noise σ{}", level),
+            Degradation::Contrast => format!("{:.0}% contrast", level * 100.0),
+        }
+    }
+
+    /// Apply this degradation at `level` to a normalized image
+    fn apply(self, image: &GrayImage, level: f32) -> GrayImage {
+        match self {
+            Degradation::Downscale => {
+                let scale = level / WORK_PIXELS_PER_MODULE;
+                let width = ((image.width() as f32 * scale).round() as u32).max(1);
+                let height = ((image.height() as f32 * scale).round() as u32).max(1);
+                imageops::resize(image, width, height, imageops::FilterType::Triangle)
+            }
+            Degradation::Blur => imageops::blur(image, level * WORK_PIXELS_PER_MODULE),
+            Degradation::Jpeg => recompress_jpeg(image, level as u8),
+            Degradation::Skew => keystone(image, level),
+            Degradation::Noise => add_noise(image, level),
+            Degradation::Contrast => {
+                // Pull dark pixels towards white, as with faded ink
+                let mut faded = image.clone();
+                for pixel in faded.pixels_mut() {
+                    let value = 255.0 - (255.0 - pixel[0] as f32) * level;
+                    pixel[0] = value.round() as u8;
+                }
+                faded
+            }
+        }
+    }
+}
+
+/// Margin measured for one degradation
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegradationResult {
+    /// Degradation that was simulated
+    pub degradation: Degradation,
+    /// Number of consecutive severity levels that still decoded
+    pub levels_passed: usize,
+    /// Number of severity levels tried in total
+    pub levels_total: usize,
+    /// Most severe level that still decoded (e.g. "quality 20")
+    pub last_passing: Option<String>,
+    /// First level at which decoding failed
+    pub first_failing: Option<String>,
+}
+
+impl DegradationResult {
+    /// Fraction of the severity ladder survived (0.0-1.0)
+    pub fn margin(&self) -> f32 {
+        if self.levels_total == 0 {
+            return 0.0;
+        }
+        self.levels_passed as f32 / self.levels_total as f32
+    }
+
+    /// One-line description of the margin
+    pub fn summary(&self) -> String {
+        match (&self.last_passing, &self.first_failing) {
+            (Some(pass), Some(fail)) => format!("survives {}, fails at {}", pass, fail),
+            (Some(pass), None) => format!("survives every level (up to {})", pass),
+            (None, Some(fail)) => format!("fails already at {}", fail),
+            (None, None) => "not tested".to_string(),
+        }
+    }
+}
+
+/// Scannability of a rendered design
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScannabilityReport {
+    /// Decode check of the undegraded image
+    pub baseline: Verification,
+    /// Margin for each degradation (empty if the baseline fails)
+    pub results: Vec<DegradationResult>,
+}
+
+impl ScannabilityReport {
+    /// Overall score from 0 (does not scan) to 100 (survives everything)
+    pub fn score(&self) -> u32 {
+        if !self.baseline.passed() || self.results.is_empty() {
+            return 0;
+        }
+        let total: f32 = self.results.iter().map(|r| r.margin()).sum();
+        (total / self.results.len() as f32 * 100.0).round() as u32
+    }
+
+    /// Rating label for the score
+    pub fn rating(&self) -> &'static str {
+        match self.score() {
+            80.. => "Excellent",
+            60..=79 => "Good",
+            40..=59 => "Fair",
+            1..=39 => "Poor",
+            0 => "Unscannable",
+        }
+    }
+
+    /// Degradation the design tolerates least
+    pub fn weakest(&self) -> Option<&DegradationResult> {
+        self.results
+            .iter()
+            .min_by(|a, b| a.margin().total_cmp(&b.margin()))
+    }
+
+    /// Whether the degradations have been measured (always true if the
+    /// baseline check did not pass, as there is nothing to degrade)
+    pub fn is_complete(&self) -> bool {
+        !self.baseline.passed() || !self.results.is_empty()
+    }
+
+    /// Short human-readable description for status messages
+    pub fn summary(&self) -> String {
+        if !self.baseline.passed() || !self.is_complete() {
+            return self.baseline.summary();
+        }
+        let mut text = format!("Scannability {}/100 ({})", self.score(), self.rating());
+        if let Some(weakest) = self.weakest().filter(|w| w.margin() < 1.0) {
+            text.push_str(&format!(", weakest: {}", weakest.degradation.name().to_lowercase()));
+        }
+        text
+    }
+}
+
+/// Render a specification and measure its scannability
+///
+/// # Returns
+/// * `Ok(ScannabilityReport)` - Baseline check and margin per degradation
+/// * `Err(String)` - Error message if the code could not be rendered
+pub fn analyze(spec: &QrSpec) -> Result<ScannabilityReport, String> {
+    if spec.structured_append.is_some() {
+        return analyze_sequence(spec);
+    }
+    let image = generator::generate_qr_image(spec)?;
+    analyze_image(&image, spec)
+}
+
+/// Decode check of an image rendered from `spec`, without the degradations
+///
+/// A single decode, cheap enough to run on every preview; `analyze_image`
+/// adds the margins. For a Structured Append sequence every code is checked.
+///
+/// # Arguments
+/// * `image` - QR code rendered from `spec`
+/// * `spec` - Specification the image was rendered from
+///
+/// # Returns
+/// * `Ok(ScannabilityReport)` - Report holding only the baseline check
+///   (incomplete if it passed)
+/// * `Err(String)` - Error message if the QR matrix cannot be created
+pub fn check_baseline(image: &RgbaImage, spec: &QrSpec) -> Result<ScannabilityReport, String> {
+    let baseline = if spec.structured_append.is_some() {
+        verify::verify_spec(spec)?
+    } else {
+        match verify::unsupported_symbol(spec) {
+            Some(skipped) => skipped,
+            None => verify::verify_image(image, &Expected::for_spec(spec)?),
+        }
+    };
+    Ok(ScannabilityReport { baseline, results: Vec::new() })
+}
+
+/// Measure the scannability of an image already rendered from `spec`
+///
+/// For a Structured Append sequence the image is the combined sheet and
+/// the codes are measured individually (see `analyze_sequence`).
+///
+/// # Arguments
+/// * `image` - QR code rendered from `spec`
+/// * `spec` - Specification the image was rendered from
+///
+/// # Returns
+/// * `Ok(ScannabilityReport)` - Baseline check and margin per degradation
+/// * `Err(String)` - Error message if the QR matrix cannot be created
+pub fn analyze_image(image: &RgbaImage, spec: &QrSpec) -> Result<ScannabilityReport, String> {
+    if spec.structured_append.is_some() {
+        return analyze_sequence(spec);
+    }
+
+    let expected = Expected::for_spec(spec)?;
+    let matrix = encoding::encode(spec)?.matrix;
+    let layout = generator::Layout::new(spec, matrix.width(), matrix.height());
+
+    // === Step 1: Baseline Check ===
+    let baseline = match verify::unsupported_symbol(spec) {
+        Some(skipped) => skipped,
+        None => verify::verify_image(image, &expected),
+    };
+    if !baseline.passed() {
+        return Ok(ScannabilityReport { baseline, results: Vec::new() });
+    }
+
+    // === Step 2: Normalize Resolution ===
+    // Every design is degraded from the same pixels-per-module, so scores
+    // do not depend on the export size
+    let scale = WORK_PIXELS_PER_MODULE / layout.module_size;
+    let width = (layout.width as f32 * scale).round() as u32;
+    let height = (layout.height as f32 * scale).round() as u32;
+    let gray = verify::to_gray_over_white(image);
+    let work = imageops::resize(&gray, width, height, imageops::FilterType::Gaussian);
+
+    // === Step 3: Walk Each Severity Ladder ===
+    let results = Degradation::ALL
+        .iter()
+        .map(|&degradation| measure(&work, &expected, degradation))
+        .collect();
+
+    Ok(ScannabilityReport { baseline, results })
+}
+
+/// Measure a Structured Append sequence
+///
+/// Every code gets the baseline check; the codes share one style and
+/// version, so the first stands in for the set under the degradations.
+fn analyze_sequence(spec: &QrSpec) -> Result<ScannabilityReport, String> {
+    let baseline = verify::verify_spec(spec)?;
+    if !baseline.passed() {
+        return Ok(ScannabilityReport { baseline, results: Vec::new() });
+    }
+    analyze(&structured::split(spec)?[0])
+}
+
+/// Apply increasing levels of one degradation until decoding fails
+fn measure(work: &GrayImage, expected: &Expected, degradation: Degradation) -> DegradationResult {
+    let levels = degradation.levels();
+    let mut result = DegradationResult {
+        degradation,
+        levels_passed: 0,
+        levels_total: levels.len(),
+        last_passing: None,
+        first_failing: None,
+    };
+
+    for &level in levels {
+        let degraded = degradation.apply(work, level);
+        if verify::decode_gray(&degraded, expected).passed() {
+            result.levels_passed += 1;
+            result.last_passing = Some(degradation.describe(level));
+        } else {
+            result.first_failing = Some(degradation.describe(level));
+            break;
+        }
+    }
+
+    result
+}
+
+/// Encode and decode as JPEG at the given quality
+fn recompress_jpeg(image: &GrayImage, quality: u8) -> GrayImage {
+    let mut bytes = Vec::new();
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, quality);
+    if image.write_with_encoder(encoder).is_err() {
+        return image.clone();
+    }
+    image::load_from_memory_with_format(&bytes, ImageFormat::Jpeg)
+        .map(|decoded| decoded.to_luma8())
+        .unwrap_or_else(|_| image.clone())
+}
+
+/// Tilt the image away from the viewer so its top edge narrows
+///
+/// Maps the image onto a symmetric trapezoid with a true perspective
+/// transform (rows further away are also compressed), using inverse mapping
+/// with bilinear sampling. Uncovered areas are white.
+fn keystone(image: &GrayImage, amount: f32) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let k = amount.clamp(0.0, 0.9);
+
+    GrayImage::from_fn(width, height, |x, y| {
+        // Normalized destination coordinates
+        let dst_x = (x as f32 + 0.5) / width as f32;
+        let dst_y = (y as f32 + 0.5) / height as f32;
+
+        // Inverse of the unit square -> trapezoid homography
+        let v = dst_y / (1.0 - k + k * dst_y);
+        let u = (dst_x * (1.0 - k * v) - k / 2.0 * (1.0 - v)) / (1.0 - k);
+
+        let src_x = u * width as f32 - 0.5;
+        let src_y = v * height as f32 - 0.5;
+        Luma([sample_bilinear(image, src_x, src_y)])
+    })
+}
+
+/// Sample a greyscale image at fractional coordinates (white outside)
+fn sample_bilinear(image: &GrayImage, x: f32, y: f32) -> u8 {
+    let (width, height) = image.dimensions();
+    if x < 0.0 || y < 0.0 || x > (width - 1) as f32 || y > (height - 1) as f32 {
+        return 255;
+    }
+
+    let x0 = x.floor() as u32;
+    let y0 = y.floor() as u32;
+    let x1 = (x0 + 1).min(width - 1);
+    let y1 = (y0 + 1).min(height - 1);
+    let fx = x - x0 as f32;
+    let fy = y - y0 as f32;
+
+    let p = |px: u32, py: u32| image.get_pixel(px, py)[0] as f32;
+    let top = p(x0, y0) * (1.0 - fx) + p(x1, y0) * fx;
+    let bottom = p(x0, y1) * (1.0 - fx) + p(x1, y1) * fx;
+    (top * (1.0 - fy) + bottom * fy).round() as u8
+}
+
+/// Add deterministic Gaussian noise with the given standard deviation
+fn add_noise(image: &GrayImage, std_dev: f32) -> GrayImage {
+    // xorshift64 keeps results identical across runs and platforms
+    let mut state = NOISE_SEED;
+    let mut next_uniform = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        ((state >> 11) as f64 / (1u64 << 53) as f64) as f32
+    };
+
+    let mut noisy = image.clone();
+    for pixel in noisy.pixels_mut() {
+        // Box-Muller transform
+        let u1 = next_uniform().max(f32::MIN_POSITIVE);
+        let u2 = next_uniform();
+        let gaussian = (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos();
+        let value = pixel[0] as f32 + gaussian * std_dev;
+        pixel[0] = value.round().clamp(0.0, 255.0) as u8;
+    }
+    noisy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EyeStyle, ErrorCorrectionLevel};
+
+    #[test]
+    fn test_default_design_scores_well() {
+        let report = analyze(&QrSpec::default()).unwrap();
+        assert!(report.baseline.passed());
+        assert_eq!(report.results.len(), Degradation::ALL.len());
+        assert!(report.score() >= 60, "score {}", report.score());
+        assert!(report.is_complete());
+
+        // The baseline alone is incomplete and reports just the decode check
+        let image = generator::generate_qr_image(&QrSpec::default()).unwrap();
+        let baseline = check_baseline(&image, &QrSpec::default()).unwrap();
+        assert_eq!(baseline.baseline, report.baseline);
+        assert!(!baseline.is_complete());
+        assert_eq!(baseline.summary(), report.baseline.summary());
+    }
+
+    #[test]
+    fn test_unscannable_design_scores_zero() {
+        let spec = QrSpec { eye_style: EyeStyle::DIAMOND, ..Default::default() };
+        let report = analyze(&spec).unwrap();
+        assert_eq!(report.score(), 0);
+        assert_eq!(report.rating(), "Unscannable");
+        assert!(report.results.is_empty());
+    }
+
+    #[test]
+    fn test_low_opacity_reduces_contrast_margin() {
+        let contrast = |opacity: f32| {
+            let spec = QrSpec {
+                qr_opacity: opacity,
+                ec_level: ErrorCorrectionLevel::High,
+                ..Default::default()
+            };
+            let report = analyze(&spec).unwrap();
+            report.results.iter()
+                .find(|r| r.degradation == Degradation::Contrast)
+                .map(|r| r.levels_passed)
+                .unwrap_or(0)
+        };
+        assert!(contrast(0.3) < contrast(1.0));
+    }
+}
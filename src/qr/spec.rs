@@ -0,0 +1,468 @@
+//! Rendering specification
+//!
+//! `QrSpec` holds everything needed to render a QR code (content, size,
+//! colors, styles, images, print layout and export settings) and nothing
+//! else. Renderers take a `&QrSpec`, so they can be driven from the GUI,
+//! the command line, tests or other code without any UI state.
+
+use std::ops::RangeInclusive;
+
+use image::DynamicImage;
+use serde::{Serialize, Serializer, Deserialize};
+
+use crate::types::*;
+use crate::payload::{self, Payload};
+use crate::export::ExportOptions;
+use crate::qr::pdf::PdfOptions;
+use crate::qr::structured::SequencePosition;
+
+/// Supported output sizes in pixels
+pub const SIZE_RANGE: RangeInclusive<u32> = 128..=2048;
+
+/// Supported border widths in modules
+pub const BORDER_RANGE: RangeInclusive<u32> = 0..=10;
+
+/// Complete description of a QR code design
+///
+/// Serialized as the body of preset files (the GUI adds its own UI fields
+/// alongside). Loaded images are runtime data and never serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QrSpec {
+    // === Content Settings ===
+    /// Text content to encode in the QR code (saved without authenticator secrets)
+    #[serde(serialize_with = "serialize_text")]
+    pub qr_text: String,
+
+    /// How `qr_text` is interpreted: plain text or GS1 data (older presets are plain text)
+    #[serde(default)]
+    pub content_mode: ContentMode,
+
+    /// Structured payload `qr_text` is built from (WiFi, contact, event, ...);
+    /// `None` when the text is entered directly
+    #[serde(default)]
+    pub payload: Option<Payload>,
+
+    /// Output size of the QR code image in pixels (128-2048; the longer side for rMQR)
+    pub size: u32,
+
+    /// Border width in modules (quiet zone around QR code)
+    pub border: u32,
+
+    /// How `size` maps to the output image (older presets fit whole modules)
+    #[serde(default)]
+    pub sizing_mode: SizingMode,
+
+    /// Error correction level (affects reliability and capacity)
+    pub ec_level: ErrorCorrectionLevel,
+
+    /// Standard QR, Micro QR or rMQR (older presets are standard QR codes)
+    #[serde(default)]
+    pub symbol_type: SymbolType,
+
+    /// QR version selection (older presets pick the smallest version)
+    #[serde(default)]
+    pub version: QrVersion,
+
+    /// Mask pattern 0-7 (Micro QR 0-3); `None` picks the pattern scanners read best
+    #[serde(default)]
+    pub mask_pattern: Option<u8>,
+
+    /// Segment mode for the content (older presets choose automatically)
+    #[serde(default)]
+    pub encoding_mode: EncodingMode,
+
+    /// Character set the content is transcoded to, announced with an ECI
+    /// designator (older presets use UTF-8 without one)
+    #[serde(default)]
+    pub character_set: CharacterSet,
+
+    /// Split the content across this many linked codes (Structured Append, 2-16);
+    /// `None` renders a single code
+    #[serde(default)]
+    pub structured_append: Option<u8>,
+
+    /// Position of this code in a Structured Append sequence (set by
+    /// `structured::split`, not serialized)
+    #[serde(skip)]
+    pub sequence_position: Option<SequencePosition>,
+
+    // === Color Settings ===
+    /// Foreground color for dark modules (RGB 0-255)
+    pub fg_color: [u8; 3],
+
+    /// Background color for light areas (RGB 0-255)
+    pub bg_color: [u8; 3],
+
+    /// Enable gradient color blending
+    pub use_gradient: bool,
+
+    /// Type of gradient to apply (horizontal, vertical, etc.)
+    pub gradient_type: GradientType,
+
+    /// Second color for gradient blending (RGB 0-255)
+    pub gradient_color: [u8; 3],
+
+    // === Module Styling ===
+    /// Visual style for data modules (square, circle, etc.)
+    pub module_style: ModuleStyle,
+
+    /// Enable extra rounding on rounded modules
+    pub use_rounded_corners: bool,
+
+    /// Corner radius for rounded modules (0.0-1.0)
+    pub corner_radius: f32,
+
+    /// Style for the alignment patterns (older presets get solid squares)
+    #[serde(default)]
+    pub alignment_style: PatternStyle,
+
+    /// Style for the timing patterns (older presets get solid squares)
+    #[serde(default)]
+    pub timing_style: PatternStyle,
+
+    // === Eye (Finder Pattern) Styling ===
+    /// Frame and pupil shapes for the three corner finder patterns
+    /// (older presets store a single style name)
+    pub eye_style: EyeStyle,
+
+    /// Use custom colors for eye patterns
+    pub use_custom_eye_color: bool,
+
+    /// Custom color for eye frames (and pupils, unless set separately) if enabled (RGB 0-255)
+    pub eye_color: [u8; 3],
+
+    /// Custom color for eye pupils; `None` uses the frame color
+    #[serde(default)]
+    pub eye_pupil_color: Option<[u8; 3]>,
+
+    /// Styling for individual eyes, replacing the shared settings above
+    #[serde(default)]
+    pub eye_overrides: Vec<EyeOverride>,
+
+    // === Image Integration ===
+    /// Loaded logo image data (not serialized)
+    #[serde(skip)]
+    pub logo_image: Option<DynamicImage>,
+
+    /// Logo size as fraction of QR code (0.05-0.35)
+    pub logo_size: f32,
+
+    /// Automatic adjustment when the logo exceeds the error correction budget
+    #[serde(default)]
+    pub logo_fit: LogoFit,
+
+    /// Share of the error correction budget kept free of the logo (0.0-0.9)
+    #[serde(default = "default_logo_safety_margin")]
+    pub logo_safety_margin: f32,
+
+    /// Loaded background image data (not serialized)
+    #[serde(skip)]
+    pub bg_image: Option<DynamicImage>,
+
+    /// Background image opacity (0.0-1.0)
+    pub bg_image_opacity: f32,
+
+    // === Advanced Settings ===
+    /// Overall QR code opacity (0.0-1.0) for watermark effects
+    pub qr_opacity: f32,
+
+    /// Edge smoothing for raster output (older presets get the default)
+    #[serde(default)]
+    pub antialiasing: Antialiasing,
+
+    /// Page layout used for PDF export (older presets fall back to defaults)
+    #[serde(default)]
+    pub pdf_options: PdfOptions,
+
+    /// Raster format settings used when saving (older presets fall back to defaults)
+    #[serde(default)]
+    pub export_options: ExportOptions,
+}
+
+impl Default for QrSpec {
+    /// Create a specification with sensible default settings
+    fn default() -> Self {
+        Self {
+            // Default content
+            qr_text: String::from("https://oliverbonhamcarter.com"),
+            content_mode: ContentMode::Text,
+            payload: None,
+
+            // Default dimensions
+            size: 512,
+            border: 2,
+            sizing_mode: SizingMode::FitModules,
+            ec_level: ErrorCorrectionLevel::Medium,
+            symbol_type: SymbolType::Qr,
+            version: QrVersion::Auto,
+            mask_pattern: None,
+            encoding_mode: EncodingMode::Auto,
+            character_set: CharacterSet::Auto,
+            structured_append: None,
+            sequence_position: None,
+
+            // Default colors (classic black on white)
+            fg_color: [0, 0, 0],
+            bg_color: [255, 255, 255],
+            use_gradient: false,
+            gradient_type: GradientType::Horizontal,
+            gradient_color: [100, 100, 255],
+
+            // Default module style (classic square)
+            module_style: ModuleStyle::Square,
+            use_rounded_corners: false,
+            corner_radius: 0.3,
+
+            // Function patterns stay plain for reliable scanning
+            alignment_style: PatternStyle::Solid,
+            timing_style: PatternStyle::Solid,
+
+            // Default eye style (standard)
+            eye_style: EyeStyle::STANDARD,
+            use_custom_eye_color: false,
+            eye_color: [255, 0, 0],
+            eye_pupil_color: None,
+            eye_overrides: Vec::new(),
+
+            // No images by default
+            logo_image: None,
+            logo_size: 0.2,
+            logo_fit: LogoFit::Off,
+            logo_safety_margin: default_logo_safety_margin(),
+            bg_image: None,
+            bg_image_opacity: 0.3,
+
+            // Default opacity (fully opaque)
+            qr_opacity: 1.0,
+            antialiasing: Antialiasing::Standard,
+
+            // Default print layout (50mm code on A4)
+            pdf_options: PdfOptions::default(),
+
+            // Default raster export (opaque, JPEG quality 90, lossless WebP)
+            export_options: ExportOptions::default(),
+        }
+    }
+}
+
+/// Resolved styling of one eye (shared settings plus its override)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EyeDesign {
+    /// Frame and pupil shapes
+    pub style: EyeStyle,
+    /// Frame color, or `None` for the module color or gradient
+    pub frame_color: Option<[u8; 3]>,
+    /// Pupil color, or `None` for the module color or gradient
+    pub pupil_color: Option<[u8; 3]>,
+    /// Clockwise rotation in quarter turns (0-3)
+    pub quarter_turns: u8,
+}
+
+impl QrSpec {
+    /// Use a structured payload as the content
+    ///
+    /// `qr_text` is rebuilt from the payload, and formats that mandate an
+    /// error correction level (Swiss QR-bills) switch to it. If the payload
+    /// is invalid the specification is left unchanged.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Payload and text updated
+    /// * `Err(String)` - Error message naming the invalid field
+    pub fn set_payload(&mut self, payload: Payload) -> Result<(), String> {
+        self.qr_text = payload.to_text()?;
+        self.content_mode = ContentMode::Text;
+        if let Some(level) = payload.required_ec_level() {
+            self.ec_level = level;
+        }
+        self.payload = Some(payload);
+        Ok(())
+    }
+
+    /// Check that the output size and border are within the supported ranges
+    ///
+    /// # Returns
+    /// * `Ok(())` - Size is within `SIZE_RANGE` and border within `BORDER_RANGE`
+    /// * `Err(String)` - Error message naming the value out of range
+    pub fn check_dimensions(&self) -> Result<(), String> {
+        if !SIZE_RANGE.contains(&self.size) {
+            return Err(format!(
+                "Invalid size {} px (expected {}-{})",
+                self.size, SIZE_RANGE.start(), SIZE_RANGE.end()
+            ));
+        }
+        if !BORDER_RANGE.contains(&self.border) {
+            return Err(format!(
+                "Invalid border of {} modules (expected {}-{})",
+                self.border, BORDER_RANGE.start(), BORDER_RANGE.end()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Override for one eye, if any (the last one wins if several match)
+    pub fn eye_override(&self, position: EyePosition) -> Option<&EyeOverride> {
+        self.eye_overrides.iter().rev().find(|o| o.position == position)
+    }
+
+    /// Mutable override for one eye, created empty if missing
+    pub fn eye_override_mut(&mut self, position: EyePosition) -> &mut EyeOverride {
+        match self.eye_overrides.iter().rposition(|o| o.position == position) {
+            Some(index) => &mut self.eye_overrides[index],
+            None => {
+                self.eye_overrides.push(EyeOverride::new(position));
+                self.eye_overrides.last_mut().expect("override was just added")
+            }
+        }
+    }
+
+    /// Styling of one eye after applying its override
+    pub fn eye_design(&self, position: EyePosition) -> EyeDesign {
+        let (mut frame_color, mut pupil_color) = if self.use_custom_eye_color {
+            (Some(self.eye_color), Some(self.eye_pupil_color.unwrap_or(self.eye_color)))
+        } else {
+            (None, None)
+        };
+        let mut design = EyeDesign { style: self.eye_style, frame_color, pupil_color, quarter_turns: 0 };
+
+        if let Some(eye) = self.eye_override(position) {
+            if let Some(style) = eye.style {
+                design.style = style;
+            }
+            if eye.frame_color.is_some() {
+                frame_color = eye.frame_color;
+                pupil_color = eye.frame_color;
+            }
+            design.frame_color = frame_color;
+            design.pupil_color = eye.pupil_color.or(pupil_color);
+            // Round to the nearest quarter turn (45 becomes 90, 44 becomes 0)
+            design.quarter_turns = ((eye.rotation as u32 + 45) / 90 % 4) as u8;
+        }
+
+        design
+    }
+}
+
+/// Default safety margin for logos: leave a quarter of the error
+/// correction for real-world damage (older presets fall back to this)
+fn default_logo_safety_margin() -> f32 {
+    0.25
+}
+
+/// Write the content with any authenticator secret removed, so presets
+/// never hold one in plain text
+fn serialize_text<S: Serializer>(text: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&payload::redact_otp_secret(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_reads_gui_preset() {
+        // Presets saved by the GUI contain extra UI fields such as the tab
+        let json = r#"{
+            "qr_text": "hello", "size": 300, "border": 4, "ec_level": "High",
+            "fg_color": [1, 2, 3], "bg_color": [255, 255, 255],
+            "use_gradient": false, "gradient_type": "Radial", "gradient_color": [0, 0, 0],
+            "module_style": "Dots", "use_rounded_corners": false, "corner_radius": 0.3,
+            "eye_style": "Flower", "use_custom_eye_color": false, "eye_color": [0, 0, 0],
+            "logo_size": 0.2, "bg_image_opacity": 0.3, "qr_opacity": 1.0,
+            "selected_tab": "Style"
+        }"#;
+
+        let spec: QrSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.qr_text, "hello");
+        assert_eq!(spec.module_style, ModuleStyle::Dots);
+        assert_eq!(spec.eye_style, EyeStyle::FLOWER);
+        assert_eq!(spec.pdf_options, PdfOptions::default());
+        assert_eq!(spec.export_options, ExportOptions::default());
+    }
+
+    #[test]
+    fn test_payload_is_saved_with_its_fields() {
+        let mut spec = QrSpec::default();
+        let event = Payload::Event {
+            summary: "Open day".to_string(),
+            location: String::new(),
+            description: String::new(),
+            start: "2026-11-05".to_string(),
+            end: String::new(),
+            all_day: true,
+        };
+        spec.set_payload(event.clone()).unwrap();
+        assert!(spec.qr_text.starts_with("BEGIN:VEVENT\r\nSUMMARY:Open day\r\n"));
+
+        let loaded: QrSpec = serde_json::from_str(&serde_json::to_string(&spec).unwrap()).unwrap();
+        assert_eq!(loaded.payload, Some(event));
+        assert_eq!(loaded.qr_text, spec.qr_text);
+
+        // An invalid payload leaves the content alone
+        assert!(spec.set_payload(Payload::Phone { number: String::new() }).is_err());
+        assert!(matches!(spec.payload, Some(Payload::Event { .. })));
+    }
+
+    #[test]
+    fn test_presets_never_hold_authenticator_secrets() {
+        let mut otp = Payload::new(crate::payload::PayloadKind::Otp);
+        if let Payload::Otp { issuer, account, secret, .. } = &mut otp {
+            *issuer = "Acme".to_string();
+            *account = "ada".to_string();
+            *secret = "JBSWY3DPEHPK3PXP".to_string();
+        }
+        let mut spec = QrSpec::default();
+        spec.set_payload(otp).unwrap();
+        assert!(spec.qr_text.contains("secret=JBSWY3DPEHPK3PXP"));
+
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(!json.contains("JBSWY3DP"));
+        let loaded: QrSpec = serde_json::from_str(&json).unwrap();
+        assert!(loaded.qr_text.starts_with("otpauth://totp/Acme:ada?issuer=Acme&"));
+        // The secret has to be entered again before anything is encoded
+        assert!(crate::qr::generate_qr_image(&loaded).unwrap_err().contains("Secret is required"));
+    }
+
+    #[test]
+    fn test_eye_override_replaces_shared_settings() {
+        let mut spec = QrSpec {
+            use_custom_eye_color: true,
+            eye_color: [1, 1, 1],
+            ..Default::default()
+        };
+        let accent = spec.eye_override_mut(EyePosition::TopRight);
+        accent.style = Some(EyeStyle::CIRCLE);
+        accent.pupil_color = Some([9, 9, 9]);
+        accent.rotation = 450;
+
+        let plain = spec.eye_design(EyePosition::TopLeft);
+        assert_eq!(plain.style, EyeStyle::STANDARD);
+        assert_eq!((plain.frame_color, plain.pupil_color), (Some([1, 1, 1]), Some([1, 1, 1])));
+
+        let accented = spec.eye_design(EyePosition::TopRight);
+        assert_eq!(accented.style, EyeStyle::CIRCLE);
+        assert_eq!((accented.frame_color, accented.pupil_color), (Some([1, 1, 1]), Some([9, 9, 9])));
+        assert_eq!(accented.quarter_turns, 1);
+
+        // Preset rotations between quarter turns snap to the nearest one
+        for (degrees, quarter_turns) in [(44, 0), (45, 1), (200, 2), (315, 0), (359, 0)] {
+            spec.eye_override_mut(EyePosition::TopRight).rotation = degrees;
+            assert_eq!(spec.eye_design(EyePosition::TopRight).quarter_turns, quarter_turns, "{}", degrees);
+        }
+    }
+
+    #[test]
+    fn test_eye_style_round_trips_as_frame_and_pupil() {
+        let spec = QrSpec {
+            eye_style: EyeStyle::new(EyeFrameStyle::Dotted, EyePupilStyle::Star),
+            eye_pupil_color: Some([1, 2, 3]),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(json.contains(r#""eye_style":{"frame":"Dotted","pupil":"Star"}"#));
+
+        let loaded: QrSpec = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.eye_style, spec.eye_style);
+        assert_eq!(loaded.eye_pupil_color, Some([1, 2, 3]));
+    }
+}
@@ -0,0 +1,240 @@
+//! Structured Append sequences
+//!
+//! Content that is too long for one comfortable symbol can be split across
+//! up to 16 linked QR codes. Every symbol starts with a header holding its
+//! position, the number of symbols and a parity byte over the whole
+//! message, so scanners that support Structured Append can join the parts
+//! in order (ISO/IEC 18004, section 7.4.2). Only standard QR codes define
+//! this header.
+//!
+//! `split` turns a specification into one specification per symbol; every
+//! other renderer works on those unchanged.
+
+use image::{imageops, Rgba, RgbaImage};
+
+use crate::qr::spec::QrSpec;
+use crate::qr::{encoding, generator, gs1, swiss};
+use crate::types::{ContentMode, QrVersion, SymbolType};
+
+/// Largest number of symbols in a sequence
+pub const MAX_SYMBOLS: u8 = 16;
+
+/// Gap between symbols on a combined sheet, as a fraction of the symbol size
+const SHEET_GAP_RATIO: f32 = 0.1;
+
+/// Position of one symbol in a Structured Append sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencePosition {
+    /// Zero-based position of this symbol
+    pub index: u8,
+    /// Number of symbols in the sequence (2-16)
+    pub total: u8,
+    /// XOR of every content byte of the whole message
+    pub parity: u8,
+}
+
+/// Split a specification into one specification per linked symbol
+///
+/// The content is cut at character boundaries into `spec.structured_append`
+/// parts of nearly equal length. Unless the version is fixed, every symbol
+/// gets the version of the largest part so the set looks uniform.
+///
+/// # Returns
+/// * `Ok(Vec<QrSpec>)` - Specifications in sequence order, each carrying its `sequence_position`
+/// * `Err(String)` - Error message if splitting is off, the count is out of
+///   range, the symbol type has no Structured Append or a part does not fit
+pub fn split(spec: &QrSpec) -> Result<Vec<QrSpec>, String> {
+    // === Step 1: Validate Settings ===
+    let Some(total) = spec.structured_append else {
+        return Err("Structured Append is not enabled".to_string());
+    };
+    if !(2..=MAX_SYMBOLS).contains(&total) {
+        return Err(format!("Invalid Structured Append count {} (expected 2-{})", total, MAX_SYMBOLS));
+    }
+    if spec.symbol_type != SymbolType::Qr {
+        return Err(format!("Structured Append is not available for {}", spec.symbol_type.name()));
+    }
+    if gs1::uses_fnc1(spec) {
+        return Err("Structured Append is not available for GS1 element strings".to_string());
+    }
+    if swiss::uses_cross(spec) {
+        return Err("Structured Append is not available for Swiss QR-bills".to_string());
+    }
+    if spec.content_mode == ContentMode::Gs1 {
+        gs1::parse(&spec.qr_text)?;
+    }
+    let text = &spec.qr_text;
+    if text.chars().count() < total as usize {
+        return Err(format!("Content is too short to split into {} codes", total));
+    }
+    let parity = encoding::mode_data(text, spec.encoding_mode, spec.character_set)?
+        .iter()
+        .fold(0, |acc, &byte| acc ^ byte);
+
+    // === Step 2: Cut the Content ===
+    let mut bounds = vec![0];
+    for i in 1..total as usize {
+        let previous = bounds[bounds.len() - 1];
+        let target = (text.len() * i / total as usize).max(previous + 1);
+        let bound = (target..text.len()).find(|&b| text.is_char_boundary(b)).unwrap_or(text.len());
+        bounds.push(bound);
+    }
+    bounds.push(text.len());
+    if bounds.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(format!("Content is too short to split into {} codes", total));
+    }
+
+    let mut parts: Vec<QrSpec> = bounds
+        .windows(2)
+        .enumerate()
+        .map(|(index, pair)| QrSpec {
+            qr_text: text[pair[0]..pair[1]].to_string(),
+            // A fragment of a Digital Link URI is not a URI of its own
+            content_mode: ContentMode::Text,
+            structured_append: None,
+            sequence_position: Some(SequencePosition { index: index as u8, total, parity }),
+            ..spec.clone()
+        })
+        .collect();
+
+    // === Step 3: Match Versions ===
+    let mut largest = 0;
+    for (index, part) in parts.iter().enumerate() {
+        let encoded = encoding::encode(part)
+            .map_err(|e| format!("Code {} of {}: {}", index + 1, total, e))?;
+        largest = largest.max(encoded.info.version as u8);
+    }
+    if !matches!(spec.version, QrVersion::Exact(_)) {
+        for part in &mut parts {
+            part.version = QrVersion::Minimum(largest);
+        }
+    }
+
+    Ok(parts)
+}
+
+/// Render every symbol of a sequence onto one sheet
+///
+/// Symbols are laid out in reading order on a near-square grid, separated
+/// by gaps in the background color.
+///
+/// # Returns
+/// * `Ok(RgbaImage)` - Combined sheet
+/// * `Err(String)` - Error message if the content cannot be split or rendered
+pub fn render_sheet(spec: &QrSpec) -> Result<RgbaImage, String> {
+    let [r, g, b] = spec.bg_color;
+    compose_sheet(spec, generator::generate_qr_image, Rgba([r, g, b, 255]))
+}
+
+/// Render every symbol of a sequence onto one sheet with a transparent background
+///
+/// Like `render_sheet`, with each symbol rendered by
+/// `generator::generate_transparent_qr_image` and transparent gaps.
+///
+/// # Returns
+/// * `Ok(RgbaImage)` - Combined sheet
+/// * `Err(String)` - Error message if the content cannot be split or
+///   rendered, or a background image is set
+pub fn render_transparent_sheet(spec: &QrSpec) -> Result<RgbaImage, String> {
+    compose_sheet(spec, generator::generate_transparent_qr_image, Rgba([0, 0, 0, 0]))
+}
+
+/// Lay out the symbols of a sequence on a near-square grid
+///
+/// # Arguments
+/// * `spec` - Specification to split
+/// * `render` - Renderer for each symbol
+/// * `background` - Fill of the gaps between symbols
+fn compose_sheet(
+    spec: &QrSpec,
+    render: fn(&QrSpec) -> Result<RgbaImage, String>,
+    background: Rgba<u8>,
+) -> Result<RgbaImage, String> {
+    let images = split(spec)?
+        .iter()
+        .map(render)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let columns = (images.len() as f32).sqrt().ceil() as u32;
+    let rows = (images.len() as u32).div_ceil(columns);
+    let cell_width = images.iter().map(|i| i.width()).max().unwrap_or(0);
+    let cell_height = images.iter().map(|i| i.height()).max().unwrap_or(0);
+    let gap = (cell_width.max(cell_height) as f32 * SHEET_GAP_RATIO).round() as u32;
+
+    let mut sheet = RgbaImage::from_pixel(
+        columns * cell_width + (columns - 1) * gap,
+        rows * cell_height + (rows - 1) * gap,
+        background,
+    );
+    for (i, image) in images.iter().enumerate() {
+        let x = (i as u32 % columns) * (cell_width + gap);
+        let y = (i as u32 / columns) * (cell_height + gap);
+        imageops::overlay(&mut sheet, image, x as i64, y as i64);
+    }
+
+    Ok(sheet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn long_spec(total: u8) -> QrSpec {
+        QrSpec {
+            qr_text: "Ünïcödé and ASCII ".repeat(30),
+            structured_append: Some(total),
+            ..QrSpec::default()
+        }
+    }
+
+    #[test]
+    fn test_split_keeps_content_and_parity() {
+        let spec = long_spec(4);
+        let parts = split(&spec).unwrap();
+        assert_eq!(parts.len(), 4);
+
+        let joined: String = parts.iter().map(|p| p.qr_text.as_str()).collect();
+        assert_eq!(joined, spec.qr_text);
+
+        let parity = spec.qr_text.bytes().fold(0, |acc, b| acc ^ b);
+        for (index, part) in parts.iter().enumerate() {
+            assert_eq!(part.sequence_position, Some(SequencePosition { index: index as u8, total: 4, parity }));
+            assert_eq!(part.structured_append, None);
+        }
+        let versions: Vec<i16> = parts.iter().map(|p| encoding::encode(p).unwrap().info.version).collect();
+        assert!(versions.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    #[test]
+    fn test_header_adds_twenty_bits() {
+        let spec = QrSpec { qr_text: "hello".to_string(), ..QrSpec::default() };
+        let plain = encoding::encode(&spec).unwrap();
+        let part = QrSpec {
+            sequence_position: Some(SequencePosition { index: 2, total: 3, parity: 0x5A }),
+            ..spec
+        };
+        let linked = encoding::encode(&part).unwrap();
+        assert_eq!(linked.info.data_bits, plain.info.data_bits + 20);
+
+        // Mode 0011, index 0010, count - 1 0010, parity 01011010, then byte mode 0100
+        assert_eq!(&linked.codewords[..3], &[0x32, 0x25, 0xA4]);
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        assert!(split(&long_spec(1)).is_err());
+        assert!(split(&long_spec(17)).is_err());
+        assert!(split(&QrSpec { qr_text: "ab".to_string(), ..long_spec(3) }).is_err());
+        assert!(split(&QrSpec { symbol_type: SymbolType::Micro, ..long_spec(2) }).is_err());
+        assert!(split(&QrSpec::default()).is_err());
+    }
+
+    #[test]
+    fn test_sheet_holds_every_symbol() {
+        let spec = long_spec(3);
+        let single = generator::generate_qr_image(&split(&spec).unwrap()[0]).unwrap();
+        let sheet = render_sheet(&spec).unwrap();
+        // Three symbols on a 2x2 grid
+        assert!(sheet.width() > 2 * single.width() && sheet.height() > 2 * single.height());
+    }
+}
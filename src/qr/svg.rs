@@ -0,0 +1,286 @@
+//! Vector (SVG) export
+//!
+//! Produces resolution-independent SVG output that mirrors the raster
+//! renderer in `generator`:
+//! - Real vector geometry for every module and eye style
+//! - Gradients as native SVG linear/radial gradients
+//! - Logo and background images embedded as base64 PNG `<image>` elements
+//!
+//! The output contains no timestamps or random identifiers, so the same
+//! settings always produce byte-identical files.
+
+use std::fmt::Write;
+
+use base64::Engine;
+
+use crate::qr::spec::QrSpec;
+use crate::types::GradientType;
+use crate::qr::path::{Path, Segment};
+use crate::qr::vector::{self, format_number as num, Shape};
+
+/// Element id of the foreground gradient definition
+const GRADIENT_ID: &str = "qr-gradient";
+
+/// Generate an SVG document for the current QR code design
+///
+/// Geometry comes from `vector::build_vector_qr`, so the SVG lines up with
+/// the PNG export pixel for pixel.
+///
+/// # Arguments
+/// * `spec` - Rendering specification containing all QR code settings
+///
+/// # Returns
+/// * `Ok(String)` - Complete SVG document
+/// * `Err(String)` - Error message if the QR code or an embedded image
+///   could not be encoded
+pub fn generate_qr_svg(spec: &QrSpec) -> Result<String, String> {
+    // === Step 1: Build Vector Geometry ===
+    let qr = vector::build_vector_qr(spec)?;
+    let (width, height) = (qr.width, qr.height);
+
+    // === Step 2: Document Header and Definitions ===
+    let mut svg = String::new();
+    svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+        width, height
+    );
+
+    if spec.use_gradient {
+        svg.push_str("<defs>\n");
+        svg.push_str(&gradient_definition(spec, width, height));
+        svg.push_str("</defs>\n");
+    }
+
+    // === Step 3: Background ===
+    let _ = writeln!(
+        svg,
+        "<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        width,
+        height,
+        hex(spec.bg_color)
+    );
+
+    if let Some(bg_img) = &spec.bg_image {
+        let _ = writeln!(
+            svg,
+            "<image x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" opacity=\"{}\" preserveAspectRatio=\"none\" href=\"{}\"/>",
+            width,
+            height,
+            num(spec.bg_image_opacity),
+            png_data_uri(bg_img)?
+        );
+    }
+
+    // === Step 4: QR Modules ===
+    let data_fill = if spec.use_gradient {
+        format!("url(#{})", GRADIENT_ID)
+    } else {
+        hex(spec.fg_color)
+    };
+    let eye_fill = |color: Option<[u8; 3]>| color.map(hex).unwrap_or_else(|| data_fill.clone());
+
+    if spec.qr_opacity < 1.0 {
+        let _ = writeln!(svg, "<g opacity=\"{}\">", num(spec.qr_opacity));
+    } else {
+        svg.push_str("<g>\n");
+    }
+    write_shape_group(&mut svg, &data_fill, &qr.data_shapes);
+    for eye in &qr.eyes {
+        write_shape_group(&mut svg, &eye_fill(eye.frame_color), std::slice::from_ref(&eye.frame));
+        write_shape_group(&mut svg, &eye_fill(eye.pupil_color), std::slice::from_ref(&eye.pupil));
+    }
+
+    // === Step 5: Logo Overlay ===
+    if let (Some(logo_img), Some(rect)) = (&spec.logo_image, qr.logo_rect) {
+        let _ = writeln!(
+            svg,
+            "<image x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" preserveAspectRatio=\"none\" href=\"{3}\"/>",
+            num(rect.x),
+            num(rect.y),
+            num(rect.size),
+            png_data_uri(logo_img)?
+        );
+    }
+
+    svg.push_str("</g>\n");
+
+    // === Step 6: Swiss Cross ===
+    // Outside the opacity group so the mandated mark stays solid
+    for (shape, color) in &qr.swiss_cross {
+        write_shape_group(&mut svg, &hex(*color), std::slice::from_ref(shape));
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Write a group of shapes sharing one fill
+fn write_shape_group(svg: &mut String, fill: &str, shapes: &[Shape]) {
+    let _ = writeln!(svg, "<g fill=\"{}\">", fill);
+    for shape in shapes {
+        let _ = match *shape {
+            Shape::Rect { x, y, width, height } => writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                num(x), num(y), num(width), num(height)
+            ),
+            Shape::RoundedRect { x, y, width, height, radius } => writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{4}\" ry=\"{4}\"/>",
+                num(x), num(y), num(width), num(height), num(radius)
+            ),
+            Shape::Circle { cx, cy, r } => writeln!(
+                svg,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>",
+                num(cx), num(cy), num(r)
+            ),
+            Shape::RoundedCorners { x, y, width, height, radii } => {
+                writeln!(svg, "<path d=\"{}\"/>", rounded_corners_path(x, y, width, height, radii))
+            }
+            Shape::Path(ref path) => writeln!(svg, "<path d=\"{}\"/>", path_data(path)),
+        };
+    }
+    svg.push_str("</g>\n");
+}
+
+/// SVG path data for a rectangle with individually rounded corners
+///
+/// Corners with a zero radius are drawn square.
+fn rounded_corners_path(x: f32, y: f32, width: f32, height: f32, radii: [f32; 4]) -> String {
+    let [tl, tr, br, bl] = radii;
+    let (right, bottom) = (x + width, y + height);
+    let arc = |r: f32, end_x: f32, end_y: f32| {
+        if r > 0.0 {
+            format!("A{0} {0} 0 0 1 {1} {2}", num(r), num(end_x), num(end_y))
+        } else {
+            String::new()
+        }
+    };
+
+    format!(
+        "M{} {}H{}{}V{}{}H{}{}V{}{}Z",
+        num(x + tl), num(y),
+        num(right - tr), arc(tr, right, y + tr),
+        num(bottom - br), arc(br, right - br, bottom),
+        num(x + bl), arc(bl, x, bottom - bl),
+        num(y + tl), arc(tl, x + tl, y),
+    )
+}
+
+/// SVG path data for a `Path`
+fn path_data(path: &Path) -> String {
+    let mut data = String::new();
+    for contour in &path.contours {
+        let _ = write!(data, "M{} {}", num(contour.start.0), num(contour.start.1));
+        for segment in &contour.segments {
+            let _ = match *segment {
+                Segment::Line((x, y)) => write!(data, "L{} {}", num(x), num(y)),
+                Segment::Cubic(c1, c2, end) => write!(
+                    data,
+                    "C{} {} {} {} {} {}",
+                    num(c1.0), num(c1.1), num(c2.0), num(c2.1), num(end.0), num(end.1)
+                ),
+            };
+        }
+        data.push('Z');
+    }
+    data
+}
+
+/// Build the SVG gradient element matching `colors::get_gradient_color`
+///
+/// Uses `userSpaceOnUse` coordinates so the gradient spans the whole image,
+/// exactly like the raster gradient.
+fn gradient_definition(spec: &QrSpec, width: u32, height: u32) -> String {
+    let (width, height) = (width as f32, height as f32);
+    let stops = format!(
+        "<stop offset=\"0\" stop-color=\"{}\"/><stop offset=\"1\" stop-color=\"{}\"/>",
+        hex(spec.fg_color),
+        hex(spec.gradient_color)
+    );
+
+    match spec.gradient_type {
+        GradientType::Radial => {
+            let (cx, cy) = (width / 2.0, height / 2.0);
+            let radius = (cx * cx + cy * cy).sqrt();
+            format!(
+                "<radialGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" r=\"{}\">{}</radialGradient>\n",
+                GRADIENT_ID,
+                num(cx),
+                num(cy),
+                num(radius),
+                stops
+            )
+        }
+        linear => {
+            let (x2, y2) = match linear {
+                GradientType::Horizontal => (width, 0.0),
+                GradientType::Vertical => (0.0, height),
+                // Diagonal: reaches the end color where x + y = width + height
+                _ => ((width + height) / 2.0, (width + height) / 2.0),
+            };
+            format!(
+                "<linearGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" x1=\"0\" y1=\"0\" x2=\"{}\" y2=\"{}\">{}</linearGradient>\n",
+                GRADIENT_ID,
+                num(x2),
+                num(y2),
+                stops
+            )
+        }
+    }
+}
+
+/// Format an RGB color as `#rrggbb`
+fn hex(color: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// Encode an image as a base64 PNG data URI
+fn png_data_uri(img: &image::DynamicImage) -> Result<String, String> {
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to embed image: {}", e))?;
+    Ok(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr::generator;
+    use crate::types::{EyeStyle, ModuleStyle, SymbolType};
+
+    #[test]
+    fn test_svg_is_deterministic() {
+        let spec = QrSpec {
+            use_gradient: true,
+            gradient_type: GradientType::Radial,
+            module_style: ModuleStyle::Dots,
+            eye_style: EyeStyle::FLOWER,
+            ..Default::default()
+        };
+
+        let first = generate_qr_svg(&spec).unwrap();
+        let second = generate_qr_svg(&spec).unwrap();
+        assert_eq!(first, second);
+        assert!(first.contains("<radialGradient"));
+        assert!(first.contains("<circle"));
+    }
+
+    #[test]
+    fn test_svg_matches_raster_dimensions() {
+        let spec = QrSpec::default();
+        let raster = generator::generate_qr_image(&spec).unwrap();
+        let svg = generate_qr_svg(&spec).unwrap();
+        assert!(svg.contains(&format!("width=\"{0}\" height=\"{0}\"", raster.width())));
+
+        let spec = QrSpec { symbol_type: SymbolType::Rectangular, qr_text: "12345".to_string(), ..spec };
+        let raster = generator::generate_qr_image(&spec).unwrap();
+        let svg = generate_qr_svg(&spec).unwrap();
+        assert!(svg.contains(&format!("width=\"{}\" height=\"{}\"", raster.width(), raster.height())));
+    }
+}
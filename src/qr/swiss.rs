@@ -0,0 +1,142 @@
+//! Swiss QR-bill symbol rules
+//!
+//! The Swiss Implementation Guidelines for the QR-bill fix more than the
+//! payload: the code must use error correction level M and carry the
+//! Swiss cross in its center, 7 mm wide on a 46 mm symbol (quiet zone
+//! excluded). The cross is drawn over the finished code in black and
+//! white, whatever colors the rest of the design uses; level M leaves
+//! enough redundancy to read through it.
+
+use image::{Rgba, RgbaImage};
+
+use crate::payload::Payload;
+use crate::qr::generator::Layout;
+use crate::qr::spec::QrSpec;
+use crate::qr::vector::Shape;
+
+/// Width of the cross as a fraction of the symbol (7 mm on 46 mm)
+pub const CROSS_RATIO: f32 = 7.0 / 46.0;
+
+/// White margin around the black square, as a fraction of the cross (0.5 mm on 7 mm)
+const MARGIN_RATIO: f32 = 0.5 / 7.0;
+
+/// Arm length and width as fractions of the black square (20 and 6 units
+/// of the 32 unit Swiss flag)
+const ARM_LENGTH_RATIO: f32 = 20.0 / 32.0;
+const ARM_WIDTH_RATIO: f32 = 6.0 / 32.0;
+
+const BLACK: [u8; 3] = [0, 0, 0];
+const WHITE: [u8; 3] = [255, 255, 255];
+
+/// Whether the specification encodes a Swiss QR-bill and so carries the cross
+pub fn uses_cross(spec: &QrSpec) -> bool {
+    matches!(spec.payload, Some(Payload::SwissQrBill { .. }))
+}
+
+/// Rectangles of the cross, back to front: white margin, black square,
+/// then the horizontal and vertical arms
+///
+/// # Arguments
+/// * `layout` - Pixel geometry of the rendered code
+/// * `(columns, rows)` - Width and height of the QR matrix in modules
+///
+/// # Returns
+/// `(x, y, width, height, color)` for each rectangle in pixels
+fn cross_rects(layout: &Layout, (columns, rows): (usize, usize)) -> [(f32, f32, f32, f32, [u8; 3]); 4] {
+    let (x, y, size) = layout.logo_rect(columns, rows, CROSS_RATIO);
+    let margin = size * MARGIN_RATIO;
+    let square = size - 2.0 * margin;
+    let length = square * ARM_LENGTH_RATIO;
+    let width = square * ARM_WIDTH_RATIO;
+    let center_x = x + size / 2.0;
+    let center_y = y + size / 2.0;
+    [
+        (x, y, size, size, WHITE),
+        (x + margin, y + margin, square, square, BLACK),
+        (center_x - length / 2.0, center_y - width / 2.0, length, width, WHITE),
+        (center_x - width / 2.0, center_y - length / 2.0, width, length, WHITE),
+    ]
+}
+
+/// Paint the cross onto a rendered code, snapping edges to whole pixels
+///
+/// # Arguments
+/// * `image` - Rendered QR code
+/// * `layout` - Pixel geometry the code was rendered with
+/// * `matrix_size` - Width and height of the QR matrix in modules
+pub fn draw_cross(image: &mut RgbaImage, layout: &Layout, matrix_size: (usize, usize)) {
+    for (x, y, width, height, [r, g, b]) in cross_rects(layout, matrix_size) {
+        let (left, top) = (x.round() as u32, y.round() as u32);
+        let (right, bottom) = ((x + width).round() as u32, (y + height).round() as u32);
+        for py in top..bottom.min(image.height()) {
+            for px in left..right.min(image.width()) {
+                image.put_pixel(px, py, Rgba([r, g, b, 255]));
+            }
+        }
+    }
+}
+
+/// The cross as vector shapes with their fixed colors, back to front
+pub fn cross_shapes(layout: &Layout, matrix_size: (usize, usize)) -> Vec<(Shape, [u8; 3])> {
+    cross_rects(layout, matrix_size)
+        .into_iter()
+        .map(|(x, y, width, height, color)| (Shape::Rect { x, y, width, height }, color))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload::{PayloadKind, PostalAddress};
+    use crate::qr::{generator, svg, verify};
+    use crate::types::ErrorCorrectionLevel;
+
+    fn bill_spec() -> QrSpec {
+        let mut payload = Payload::new(PayloadKind::SwissQrBill);
+        if let Payload::SwissQrBill { iban, creditor, amount, .. } = &mut payload {
+            *iban = "CH56 0483 5012 3456 7800 9".to_string();
+            *creditor = PostalAddress {
+                name: "Robert Schneider AG".to_string(),
+                postal_code: "2501".to_string(),
+                town: "Biel".to_string(),
+                country: "CH".to_string(),
+                ..PostalAddress::default()
+            };
+            *amount = "199.95".to_string();
+        }
+        let mut spec = QrSpec { ec_level: ErrorCorrectionLevel::High, fg_color: [20, 60, 160], ..QrSpec::default() };
+        spec.set_payload(payload).unwrap();
+        spec
+    }
+
+    #[test]
+    fn test_cross_proportions() {
+        let layout = Layout { module_size: 10.0, offset_x: 40.0, offset_y: 40.0, width: 540, height: 540 };
+        let [margin, square, horizontal, vertical] = cross_rects(&layout, (46, 46));
+        // 7 of 46 modules, centered
+        assert_eq!((margin.0, margin.1, margin.2), (235.0, 235.0, 70.0));
+        assert_eq!(square.4, BLACK);
+        assert!((square.2 - 60.0).abs() < 1e-3);
+        assert!((horizontal.2 - 37.5).abs() < 1e-3 && (horizontal.3 - 11.25).abs() < 1e-3);
+        assert_eq!((vertical.2, vertical.3), (horizontal.3, horizontal.2));
+        assert!((vertical.0 + vertical.2 / 2.0 - 270.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_qr_bill_carries_the_cross_and_scans() {
+        let spec = bill_spec();
+        assert_eq!(spec.ec_level, ErrorCorrectionLevel::Medium);
+
+        let image = generator::generate_qr_image(&spec).unwrap();
+        let (cx, cy) = (image.width() / 2, image.height() / 2);
+        // White arms at the center, the black square beside them
+        assert_eq!(image.get_pixel(cx, cy).0, [255, 255, 255, 255]);
+        let offset = image.width() / 40;
+        assert_eq!(image.get_pixel(cx - offset, cy - offset).0, [0, 0, 0, 255]);
+        assert!(verify::verify_spec(&spec).unwrap().passed());
+        assert!(svg::generate_qr_svg(&spec).unwrap().contains("<g fill=\"#000000\">"));
+
+        let other_level = QrSpec { ec_level: ErrorCorrectionLevel::High, ..spec.clone() };
+        assert!(generator::generate_qr_image(&other_level).unwrap_err().contains("Medium"));
+    }
+}
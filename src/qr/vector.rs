@@ -0,0 +1,258 @@
+//! Resolution-independent shape geometry
+//!
+//! Converts the QR matrix into a list of vector shapes in image pixel
+//! coordinates, mirroring the raster renderer in `drawing`. Shared by the
+//! SVG and PDF exporters so every vector format produces identical geometry.
+
+use crate::qr::spec::QrSpec;
+use crate::types::{EyePosition, ModuleStyle, PatternStyle};
+use crate::qr::{drawing, encoding, eyes, generator, swiss};
+use crate::qr::path::Path;
+use crate::qr::matrix::{ModuleKind, Neighbors};
+
+/// A filled vector shape in image pixel coordinates (y grows downwards)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Axis-aligned rectangle
+    Rect { x: f32, y: f32, width: f32, height: f32 },
+    /// Axis-aligned rectangle with circular corners
+    RoundedRect { x: f32, y: f32, width: f32, height: f32, radius: f32 },
+    /// Circle given by center and radius
+    Circle { cx: f32, cy: f32, r: f32 },
+    /// Axis-aligned rectangle with individually rounded corners
+    /// (top-left, top-right, bottom-right, bottom-left)
+    RoundedCorners { x: f32, y: f32, width: f32, height: f32, radii: [f32; 4] },
+    /// Arbitrary outline (filled with the nonzero winding rule)
+    Path(Path),
+}
+
+/// Square placement of an image (logo) in pixel coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageRect {
+    /// Left edge in pixels
+    pub x: f32,
+    /// Top edge in pixels
+    pub y: f32,
+    /// Width and height in pixels
+    pub size: f32,
+}
+
+/// One finder pattern (eye) with its resolved colors
+pub struct VectorEye {
+    /// Outer ring
+    pub frame: Shape,
+    /// Center
+    pub pupil: Shape,
+    /// Frame color, or `None` for the module color or gradient
+    pub frame_color: Option<[u8; 3]>,
+    /// Pupil color, or `None` for the module color or gradient
+    pub pupil_color: Option<[u8; 3]>,
+}
+
+/// Complete vector description of a QR code design
+pub struct VectorQr {
+    /// Width of the whole image (including border) in pixels
+    pub width: u32,
+    /// Height of the whole image (including border) in pixels
+    pub height: u32,
+    /// Shapes for data, timing and alignment modules (drawn with the
+    /// foreground color or gradient)
+    pub data_shapes: Vec<Shape>,
+    /// Finder patterns (eyes), in the order of `EyePosition::ALL`
+    pub eyes: Vec<VectorEye>,
+    /// Logo placement, if a logo is loaded
+    pub logo_rect: Option<ImageRect>,
+    /// Swiss cross with its fixed colors, drawn over everything else
+    /// (empty unless the content is a Swiss QR-bill)
+    pub swiss_cross: Vec<(Shape, [u8; 3])>,
+}
+
+/// Build the vector geometry for the current QR code design
+///
+/// Uses the same `Layout` and eye positions as `generate_qr_image`, so
+/// vector output lines up with the raster output. Module positions are
+/// fractional in `SizingMode::Exact`.
+///
+/// # Arguments
+/// * `spec` - Rendering specification containing all QR code settings
+///
+/// # Returns
+/// * `Ok(VectorQr)` - Shapes and placement information
+/// * `Err(String)` - Error message if the QR code cannot be created or
+///   the logo is too small to place
+pub fn build_vector_qr(spec: &QrSpec) -> Result<VectorQr, String> {
+    // === Step 1: Generate QR Code Matrix ===
+    let matrix = encoding::encode(spec)?.matrix;
+    let layout = generator::Layout::new(spec, matrix.width(), matrix.height());
+    let size = layout.module_size;
+
+    // === Step 2: Convert Dark Data Modules to Shapes ===
+    let mut data_shapes = Vec::new();
+    let whole_alignment = spec.alignment_style != PatternStyle::MatchModules;
+
+    for y in 0..matrix.height() {
+        for x in 0..matrix.width() {
+            if !matrix.is_dark(x as isize, y as isize) {
+                continue;
+            }
+
+            let px = layout.offset_x + x as f32 * size;
+            let py = layout.offset_y + y as f32 * size;
+            match matrix.kind(x, y) {
+                ModuleKind::Finder => {}
+                ModuleKind::Alignment if whole_alignment => {}
+                ModuleKind::Timing if spec.timing_style != PatternStyle::MatchModules => {
+                    data_shapes.push(timing_module_shape(spec.timing_style, px, py, size));
+                }
+                _ => {
+                    let neighbors = matrix.neighbors(x, y);
+                    data_shapes.push(data_module_shape(spec, neighbors, px, py, size));
+                }
+            }
+        }
+    }
+
+    if whole_alignment {
+        for &(ax, ay) in matrix.alignment_positions() {
+            let px = layout.offset_x + ax as f32 * size;
+            let py = layout.offset_y + ay as f32 * size;
+            data_shapes.push(Shape::Path(eyes::alignment_path(spec.alignment_style, px, py, size)));
+        }
+    }
+
+    // === Step 3: Eye Frames and Pupils ===
+    let eyes = matrix
+        .eye_positions()
+        .iter()
+        .zip(EyePosition::ALL)
+        .map(|(&(ex, ey), position)| {
+            let design = spec.eye_design(position);
+            let px = layout.offset_x + ex as f32 * size;
+            let py = layout.offset_y + ey as f32 * size;
+            let turns = design.quarter_turns;
+            VectorEye {
+                frame: Shape::Path(eyes::frame_path(design.style.frame, turns, px, py, size)),
+                pupil: Shape::Path(eyes::pupil_path(design.style.pupil, turns, px, py, size)),
+                frame_color: design.frame_color,
+                pupil_color: design.pupil_color,
+            }
+        })
+        .collect();
+
+    // === Step 4: Logo Placement ===
+    let logo_rect = match &spec.logo_image {
+        Some(_) => {
+            let (x, y, logo_size) = layout.logo_rect(matrix.width(), matrix.height(), spec.logo_size);
+            if logo_size < 1.0 {
+                return Err("Logo size too small to render".to_string());
+            }
+            Some(ImageRect { x, y, size: logo_size })
+        }
+        None => None,
+    };
+
+    // === Step 5: Swiss Cross ===
+    let swiss_cross = if swiss::uses_cross(spec) {
+        swiss::cross_shapes(&layout, (matrix.width(), matrix.height()))
+    } else {
+        Vec::new()
+    };
+
+    Ok(VectorQr { width: layout.width, height: layout.height, data_shapes, eyes, logo_rect, swiss_cross })
+}
+
+/// Shape for one data module (mirrors `drawing::draw_data_module`)
+fn data_module_shape(spec: &QrSpec, neighbors: Neighbors, px: f32, py: f32, size: f32) -> Shape {
+    match spec.module_style {
+        ModuleStyle::Square => square(px, py, size),
+        ModuleStyle::Circle => circle(px, py, size, size / 2.0),
+        ModuleStyle::RoundedSquare => rounded_square(px, py, size, rounded_radius(spec, size)),
+        ModuleStyle::Dots => circle(px, py, size, size * 0.35),
+        ModuleStyle::Liquid => Shape::RoundedCorners {
+            x: px,
+            y: py,
+            width: size,
+            height: size,
+            radii: drawing::liquid_radii(neighbors, size),
+        },
+        ModuleStyle::VerticalLines | ModuleStyle::HorizontalLines => {
+            let vertical = spec.module_style == ModuleStyle::VerticalLines;
+            let ([left, top, right, bottom], radii) =
+                drawing::line_segment_geometry(neighbors, vertical, size);
+            Shape::RoundedCorners {
+                x: px + left,
+                y: py + top,
+                width: right - left,
+                height: bottom - top,
+                radii,
+            }
+        }
+    }
+}
+
+/// Shape for one timing pattern module (mirrors `drawing::draw_timing_module`)
+fn timing_module_shape(style: PatternStyle, px: f32, py: f32, size: f32) -> Shape {
+    match style {
+        PatternStyle::Solid | PatternStyle::MatchModules => square(px, py, size),
+        PatternStyle::Rounded => rounded_square(px, py, size, (size / 4.0).floor()),
+        PatternStyle::Circle => circle(px, py, size, size / 2.0),
+    }
+}
+
+/// Corner radius in pixels for rounded modules (mirrors `drawing::draw_rounded_square`)
+fn rounded_radius(spec: &QrSpec, size: f32) -> f32 {
+    if spec.use_rounded_corners {
+        (size * spec.corner_radius).floor()
+    } else {
+        (size * 0.2).floor() // Default 20% rounding
+    }
+}
+
+/// Format a coordinate with at most three decimals and no trailing zeros
+///
+/// Keeps vector output compact and identical across runs.
+pub fn format_number(value: f32) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+fn square(x: f32, y: f32, size: f32) -> Shape {
+    Shape::Rect { x, y, width: size, height: size }
+}
+
+fn rounded_square(x: f32, y: f32, size: f32, radius: f32) -> Shape {
+    Shape::RoundedRect { x, y, width: size, height: size, radius: radius.min(size / 2.0) }
+}
+
+fn circle(x: f32, y: f32, size: f32, radius: f32) -> Shape {
+    Shape::Circle { cx: x + size / 2.0, cy: y + size / 2.0, r: radius }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(12.0), "12");
+        assert_eq!(format_number(3.5000002), "3.5");
+        assert_eq!(format_number(0.125), "0.125");
+        assert_eq!(format_number(-0.0001), "0");
+    }
+
+    #[test]
+    fn test_eyes_are_whole_shapes() {
+        let qr = build_vector_qr(&QrSpec::default()).unwrap();
+        assert_eq!(qr.eyes.len(), 3);
+
+        // The top-left frame covers the 7x7 eye just inside the border
+        let Shape::Path(frame) = &qr.eyes[0].frame else { panic!("frame is not a path") };
+        let spec = QrSpec::default();
+        let width = encoding::encode(&spec).unwrap().matrix.width();
+        let layout = generator::Layout::new(&spec, width, width);
+        let (min, max) = frame.bounds().unwrap();
+        assert_eq!(min, (layout.offset_x, layout.offset_y));
+        assert_eq!(max, (layout.offset_x + 7.0 * layout.module_size, layout.offset_y + 7.0 * layout.module_size));
+    }
+}
@@ -0,0 +1,426 @@
+//! Decode verification
+//!
+//! Runs a pure-Rust QR decoder (`rqrr`) over rendered output and compares
+//! the decoded payload with the encoded text. Heavily styled designs (dots,
+//! diamond eyes, low opacity, large logos) can stop scanning; this catches
+//! that before the artwork is printed.
+//!
+//! `rqrr` only reads standard QR codes, so Micro QR and rMQR designs are
+//! reported as skipped rather than failed. It also rejects the Structured
+//! Append header and FNC1 (GS1), so those symbols are checked one level lower: the
+//! modules read from the image are compared with the encoded symbol, and
+//! each Reed-Solomon block must be able to correct the codewords that differ.
+
+use image::{imageops, GrayImage, Luma, RgbaImage};
+use rqrr::BitGrid;
+
+use crate::qr::spec::QrSpec;
+use crate::qr::encoding::{self, EncodedQr};
+use crate::qr::{eci, generator, gs1, occlusion, structured};
+use crate::types::{CharacterSet, ErrorCorrectionLevel, SymbolType};
+
+/// Largest downscale divisor tried when the full-size image does not decode
+const MAX_DOWNSCALE: u32 = 8;
+
+/// Smallest image side (pixels) worth decoding
+const MIN_DECODE_SIZE: u32 = 64;
+
+/// Result of decoding a rendered QR code
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verification {
+    /// The code decoded to exactly the expected text
+    Passed,
+    /// A code was decoded, but its content differs from the expected text
+    Mismatch {
+        /// Content returned by the decoder
+        decoded: String,
+    },
+    /// No code could be found or decoded in the image
+    Unreadable {
+        /// Decoder error or reason
+        reason: String,
+    },
+    /// The symbol type cannot be checked with the built-in decoder
+    Skipped {
+        /// Why the check did not run
+        reason: String,
+    },
+}
+
+impl Verification {
+    /// Whether the design round-trips
+    pub fn passed(&self) -> bool {
+        matches!(self, Verification::Passed)
+    }
+
+    /// Whether the check did not run (neither passed nor failed)
+    pub fn skipped(&self) -> bool {
+        matches!(self, Verification::Skipped { .. })
+    }
+
+    /// Short human-readable description for status messages
+    pub fn summary(&self) -> String {
+        match self {
+            Verification::Passed => "Scan check passed".to_string(),
+            Verification::Mismatch { decoded } => {
+                let preview: String = decoded.chars().take(40).collect();
+                format!("Scan check failed: decoded different content ({:?})", preview)
+            }
+            Verification::Unreadable { reason } => format!("Scan check failed: {}", reason),
+            Verification::Skipped { reason } => format!("Scan check skipped: {}", reason),
+        }
+    }
+}
+
+/// Content a rendered code must decode to
+#[derive(Debug, Clone)]
+pub enum Expected {
+    /// Text, decoded and error corrected by `rqrr`
+    Text(String),
+    /// Text stored in an ECI character set; `rqrr` skips the designator,
+    /// so the payload is decoded with this set
+    Transcoded {
+        /// Text as it survives the character set (unrepresentable characters as `?`)
+        text: String,
+        /// Character set of the payload
+        charset: CharacterSet,
+    },
+    /// Symbol whose header `rqrr` cannot interpret (Structured Append, FNC1),
+    /// compared module by module before error correction
+    Symbol(Box<EncodedQr>),
+}
+
+impl Expected {
+    /// What the rendering of a specification must decode to
+    ///
+    /// # Returns
+    /// * `Ok(Expected)` - The text (in its character set), or the encoded
+    ///   symbol for part of a sequence or a GS1 element string
+    /// * `Err(String)` - Error message if the content cannot be encoded
+    pub fn for_spec(spec: &QrSpec) -> Result<Self, String> {
+        if spec.sequence_position.is_some() || gs1::uses_fnc1(spec) {
+            return Ok(Expected::Symbol(Box::new(encoding::encode(spec)?)));
+        }
+        Ok(match spec.character_set {
+            CharacterSet::Auto => Expected::Text(spec.qr_text.clone()),
+            charset => Expected::Transcoded {
+                text: eci::decode(&eci::transcode(&spec.qr_text, charset), charset),
+                charset,
+            },
+        })
+    }
+}
+
+/// Decode a rendered image and compare it with the expected content
+///
+/// Transparent pixels are composited over white, matching how the code
+/// appears on a page or screen. The image is decoded at full size first;
+/// if that fails it is retried at smaller, smoothed sizes, since camera
+/// scanners see styled modules (e.g. dots) blurred into solid shapes. A
+/// design passes if any attempt decodes to the expected text.
+///
+/// # Arguments
+/// * `image` - Rendered QR code
+/// * `expected` - Content that was encoded
+///
+/// # Returns
+/// Verification outcome of the best attempt (never panics on undecodable input)
+pub fn verify_image(image: &RgbaImage, expected: &Expected) -> Verification {
+    let gray = to_gray_over_white(image);
+    let mut outcome = decode_gray(&gray, expected);
+
+    let side = gray.width().min(gray.height());
+    for divisor in 2..=MAX_DOWNSCALE {
+        if outcome.passed() || side / divisor < MIN_DECODE_SIZE {
+            break;
+        }
+        let small = imageops::resize(
+            &gray,
+            gray.width() / divisor,
+            gray.height() / divisor,
+            imageops::FilterType::Gaussian,
+        );
+        let attempt = decode_gray(&small, expected);
+
+        // Keep the most informative failure: a wrong payload beats "not found"
+        if attempt.passed() || !matches!(attempt, Verification::Unreadable { .. }) {
+            outcome = attempt;
+        }
+    }
+
+    outcome
+}
+
+/// Run the decoder once on a greyscale image at its current size
+///
+/// # Arguments
+/// * `image` - Greyscale image (dark modules on a light background)
+/// * `expected` - Content that was encoded
+pub fn decode_gray(image: &GrayImage, expected: &Expected) -> Verification {
+    // === Step 1: Prepare for Detection ===
+    let (width, height) = image.dimensions();
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        width as usize,
+        height as usize,
+        |x, y| image.get_pixel(x as u32, y as u32)[0],
+    );
+
+    // === Step 2: Locate and Decode ===
+    let grids = prepared.detect_grids();
+    if grids.is_empty() {
+        return Verification::Unreadable {
+            reason: "no QR code detected".to_string(),
+        };
+    }
+
+    let mut last_error = String::new();
+    for grid in grids {
+        let (text, charset) = match expected {
+            Expected::Text(text) => (text, CharacterSet::Auto),
+            Expected::Transcoded { text, charset } => (text, *charset),
+            Expected::Symbol(encoded) => match grid.get_raw_data() {
+                Ok((meta, _)) => return compare_modules(&meta, &grid.grid, encoded),
+                Err(e) => {
+                    last_error = e.to_string();
+                    continue;
+                }
+            },
+        };
+        let mut bytes = Vec::new();
+        match grid.decode_to(&mut bytes) {
+            Ok(_) => {
+                let content = decoded_text(bytes, charset);
+                if content == *text {
+                    return Verification::Passed;
+                }
+                return Verification::Mismatch { decoded: content };
+            }
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+
+    Verification::Unreadable {
+        reason: format!("decoding failed ({})", last_error),
+    }
+}
+
+/// Compare the modules of a detected grid with an encoded symbol
+///
+/// Passes if every Reed-Solomon block can correct the codewords that have
+/// at least one differing module.
+fn compare_modules(meta: &rqrr::MetaData, grid: &impl BitGrid, encoded: &EncodedQr) -> Verification {
+    let info = &encoded.info;
+    // Level numbering of the format information
+    let format_level = match info.ec_level {
+        ErrorCorrectionLevel::Medium => 0,
+        ErrorCorrectionLevel::Low => 1,
+        ErrorCorrectionLevel::High => 2,
+        ErrorCorrectionLevel::Quartile => 3,
+    };
+    if meta.version.0 != info.version as usize || meta.ecc_level != format_level || meta.mask != info.mask as u16 {
+        return Verification::Unreadable {
+            reason: format!("found a different symbol (version {})", meta.version.0),
+        };
+    }
+
+    let (owners, mut blocks) = occlusion::block_structure(info.version, info.ec_level);
+    let mut damaged = vec![false; owners.len()];
+    let modules = occlusion::data_module_order(info.version, info.width as i16);
+    for (bit, (x, y)) in modules.enumerate() {
+        let codeword = bit / 8;
+        if codeword < owners.len() && grid.bit(y as usize, x as usize) != encoded.matrix.is_dark(x as isize, y as isize) {
+            damaged[codeword] = true;
+        }
+    }
+    for (codeword, &block) in owners.iter().enumerate() {
+        if damaged[codeword] {
+            blocks[block].obscured += 1;
+        }
+    }
+
+    match blocks.iter().enumerate().find(|(_, b)| b.obscured > b.correctable) {
+        None => Verification::Passed,
+        Some((i, block)) => Verification::Unreadable {
+            reason: format!(
+                "too many damaged codewords (block {}: {} of {} correctable)",
+                i + 1, block.obscured, block.correctable
+            ),
+        },
+    }
+}
+
+/// Decoded payload as text
+///
+/// Payloads in an ECI character set are decoded with it. Otherwise
+/// Kanji-mode segments decode to Shift JIS bytes, so content that is not
+/// valid UTF-8 is read as Shift JIS (as phone scanners do).
+fn decoded_text(bytes: Vec<u8>, charset: CharacterSet) -> String {
+    if charset != CharacterSet::Auto {
+        return eci::decode(&bytes, charset);
+    }
+    match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => encoding_rs::SHIFT_JIS.decode(e.as_bytes()).0.into_owned(),
+    }
+}
+
+/// Convert a rendered image to greyscale as seen on a white page
+///
+/// Uses Rec. 601 luma after compositing transparent pixels over white.
+pub fn to_gray_over_white(image: &RgbaImage) -> GrayImage {
+    GrayImage::from_fn(image.width(), image.height(), |x, y| {
+        let pixel = image.get_pixel(x, y).0;
+        let alpha = pixel[3] as f32 / 255.0;
+        let blend = |c: u8| c as f32 * alpha + 255.0 * (1.0 - alpha);
+        let luma = 0.299 * blend(pixel[0]) + 0.587 * blend(pixel[1]) + 0.114 * blend(pixel[2]);
+        Luma([luma.round().clamp(0.0, 255.0) as u8])
+    })
+}
+
+/// Render a specification and verify that it decodes to its own text
+///
+/// Vector output shares its geometry with the raster renderer, so this
+/// check also applies to SVG and PDF exports. A Structured Append sequence
+/// passes if every one of its codes passes.
+///
+/// # Returns
+/// * `Ok(Verification)` - Outcome of the decode check (the first failing
+///   code of a sequence)
+/// * `Err(String)` - Error message if the code could not be rendered
+pub fn verify_spec(spec: &QrSpec) -> Result<Verification, String> {
+    if spec.structured_append.is_some() {
+        let parts = structured::split(spec)?;
+        for (index, part) in parts.iter().enumerate() {
+            match verify_spec(part)? {
+                Verification::Passed => {}
+                Verification::Unreadable { reason } => {
+                    return Ok(Verification::Unreadable {
+                        reason: format!("code {} of {}: {}", index + 1, parts.len(), reason),
+                    });
+                }
+                failed => return Ok(failed),
+            }
+        }
+        return Ok(Verification::Passed);
+    }
+
+    let image = generator::generate_qr_image(spec)?;
+    if let Some(skipped) = unsupported_symbol(spec) {
+        return Ok(skipped);
+    }
+    Ok(verify_image(&image, &Expected::for_spec(spec)?))
+}
+
+/// `Verification::Skipped` for symbol types the decoder cannot read
+pub fn unsupported_symbol(spec: &QrSpec) -> Option<Verification> {
+    (spec.symbol_type != SymbolType::Qr).then(|| Verification::Skipped {
+        reason: format!("{} cannot be decoded by the built-in scanner", spec.symbol_type.name()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContentMode, EncodingMode, EyeStyle, ModuleStyle};
+
+    #[test]
+    fn test_default_design_passes() {
+        let spec = QrSpec::default();
+        assert_eq!(verify_spec(&spec), Ok(Verification::Passed));
+    }
+
+    #[test]
+    fn test_dots_pass_when_blurred() {
+        let spec = QrSpec {
+            qr_text: "https://example.com/styled".to_string(),
+            module_style: ModuleStyle::Dots,
+            ..Default::default()
+        };
+        assert!(verify_spec(&spec).unwrap().passed());
+    }
+
+    #[test]
+    fn test_kanji_mode_passes() {
+        let spec = QrSpec {
+            qr_text: "漢字テスト".to_string(),
+            encoding_mode: EncodingMode::Kanji,
+            ..Default::default()
+        };
+        assert!(verify_spec(&spec).unwrap().passed());
+    }
+
+    #[test]
+    fn test_connected_styles_pass() {
+        for module_style in [ModuleStyle::Liquid, ModuleStyle::VerticalLines, ModuleStyle::HorizontalLines] {
+            let spec = QrSpec { module_style, ..Default::default() };
+            assert!(verify_spec(&spec).unwrap().passed(), "{:?} did not scan", module_style);
+        }
+    }
+
+    #[test]
+    fn test_broken_finder_fails() {
+        // Diamond eyes remove the 1:1:3:1:1 finder ratio scanners look for
+        let spec = QrSpec { eye_style: EyeStyle::DIAMOND, ..Default::default() };
+        assert!(!verify_spec(&spec).unwrap().passed());
+    }
+
+    #[test]
+    fn test_micro_and_rectangular_are_skipped() {
+        for symbol_type in [SymbolType::Micro, SymbolType::Rectangular] {
+            let spec = QrSpec { qr_text: "12345".to_string(), symbol_type, ..Default::default() };
+            let verification = verify_spec(&spec).unwrap();
+            assert!(verification.skipped() && !verification.passed());
+        }
+    }
+
+    #[test]
+    fn test_structured_append_parts_pass() {
+        let spec = QrSpec {
+            qr_text: "Structured Append splits long content. ".repeat(20),
+            structured_append: Some(3),
+            module_style: ModuleStyle::Dots,
+            ..Default::default()
+        };
+        assert_eq!(verify_spec(&spec), Ok(Verification::Passed));
+
+        // The codeword check still notices a symbol that does not scan
+        let broken = QrSpec { qr_opacity: 0.0, ..spec };
+        assert!(!verify_spec(&broken).unwrap().passed());
+    }
+
+    #[test]
+    fn test_gs1_element_string_passes() {
+        let spec = QrSpec {
+            qr_text: "(01)09501101530003(17)251231(10)LOT42".to_string(),
+            content_mode: ContentMode::Gs1,
+            ..Default::default()
+        };
+        assert!(matches!(Expected::for_spec(&spec), Ok(Expected::Symbol(_))));
+        assert_eq!(verify_spec(&spec), Ok(Verification::Passed));
+    }
+
+    #[test]
+    fn test_latin1_payload_passes() {
+        let spec = QrSpec {
+            qr_text: "Crème brûlée €4".to_string(),
+            character_set: CharacterSet::Iso8859_1,
+            ..Default::default()
+        };
+        // The euro sign is not in ISO-8859-1 and is stored as '?'
+        assert!(matches!(Expected::for_spec(&spec), Ok(Expected::Transcoded { ref text, .. }) if text == "Crème brûlée ?4"));
+        assert_eq!(verify_spec(&spec), Ok(Verification::Passed));
+    }
+
+    #[test]
+    fn test_blank_image_is_unreadable() {
+        let blank = RgbaImage::from_pixel(200, 200, image::Rgba([255, 255, 255, 255]));
+        let expected = Expected::Text("x".to_string());
+        assert!(matches!(verify_image(&blank, &expected), Verification::Unreadable { .. }));
+    }
+
+    #[test]
+    fn test_invisible_code_fails() {
+        let spec = QrSpec { qr_opacity: 0.0, ..Default::default() };
+        assert!(!verify_spec(&spec).unwrap().passed());
+    }
+}
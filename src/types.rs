@@ -9,6 +9,18 @@
 
 use serde::{Serialize, Deserialize};
 use qrcode::EcLevel;
+use std::str::FromStr;
+
+/// Normalize a user-supplied enum name for case- and separator-insensitive parsing
+///
+/// `"Rounded-Square"`, `"rounded_square"` and `"roundedsquare"` all normalize
+/// to the same string.
+fn normalize_name(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
 
 /// QR code error correction level
 ///
@@ -31,7 +43,7 @@ impl ErrorCorrectionLevel {
     ///
     /// # Returns
     /// The corresponding EcLevel for QR code generation
-    pub fn to_ec_level(&self) -> EcLevel {
+    pub fn to_ec_level(self) -> EcLevel {
         match self {
             ErrorCorrectionLevel::Low => EcLevel::L,
             ErrorCorrectionLevel::Medium => EcLevel::M,
@@ -39,6 +51,311 @@ impl ErrorCorrectionLevel {
             ErrorCorrectionLevel::High => EcLevel::H,
         }
     }
+
+    /// Next stronger level, or `None` for High
+    pub fn stronger(self) -> Option<Self> {
+        match self {
+            ErrorCorrectionLevel::Low => Some(ErrorCorrectionLevel::Medium),
+            ErrorCorrectionLevel::Medium => Some(ErrorCorrectionLevel::Quartile),
+            ErrorCorrectionLevel::Quartile => Some(ErrorCorrectionLevel::High),
+            ErrorCorrectionLevel::High => None,
+        }
+    }
+}
+
+impl FromStr for ErrorCorrectionLevel {
+    type Err = String;
+
+    /// Parse from a level name or its single-letter code (`l`, `m`, `q`, `h`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "low" | "l" => Ok(ErrorCorrectionLevel::Low),
+            "medium" | "m" => Ok(ErrorCorrectionLevel::Medium),
+            "quartile" | "q" => Ok(ErrorCorrectionLevel::Quartile),
+            "high" | "h" => Ok(ErrorCorrectionLevel::High),
+            _ => Err(format!("Unknown error correction level '{}' (expected low, medium, quartile or high)", s)),
+        }
+    }
+}
+
+/// Kind of 2D symbol to generate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SymbolType {
+    /// Standard QR code (21x21 to 177x177 modules, three finder patterns)
+    #[default]
+    Qr,
+    /// Micro QR M1-M4 (11x11 to 17x17 modules, one finder pattern)
+    Micro,
+    /// Rectangular Micro QR (rMQR): 7-17 modules tall, 27-139 modules wide
+    Rectangular,
+}
+
+impl SymbolType {
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            SymbolType::Qr => "QR Code",
+            SymbolType::Micro => "Micro QR",
+            SymbolType::Rectangular => "rMQR",
+        }
+    }
+}
+
+impl FromStr for SymbolType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "qr" | "standard" => Ok(SymbolType::Qr),
+            "micro" | "microqr" | "mqr" => Ok(SymbolType::Micro),
+            "rmqr" | "rectangular" | "rect" => Ok(SymbolType::Rectangular),
+            _ => Err(format!("Unknown symbol type '{}' (expected qr, micro or rmqr)", s)),
+        }
+    }
+}
+
+/// QR version (symbol size) selection
+///
+/// Versions 1-40 have 21x21 to 177x177 modules. Forcing a version keeps
+/// the printed module size the same across codes with different content.
+/// Micro QR uses versions 1-4 (M1-M4); rMQR numbers its 32 sizes from
+/// R7x43 (1) to R17x139 (32) in the order of `rmqr::SIZES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QrVersion {
+    /// Smallest version that fits the content
+    #[default]
+    Auto,
+    /// Smallest version that fits, but at least this one
+    Minimum(u8),
+    /// Exactly this version (fails if the content does not fit)
+    Exact(u8),
+}
+
+impl FromStr for QrVersion {
+    type Err = String;
+
+    /// Parse `auto`, an exact version (`7`) or a minimum version (`7+`)
+    ///
+    /// Micro QR and rMQR versions can also be given by name (`M2`,
+    /// `R11x43`, `R13x59+`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(QrVersion::Auto);
+        }
+
+        let (number, minimum) = match s.strip_suffix('+') {
+            Some(number) => (number, true),
+            None => (s, false),
+        };
+        let version: u8 = parse_version_name(number)
+            .or_else(|| number.parse().ok().filter(|v| (1..=40).contains(v)))
+            .ok_or_else(|| format!(
+                "Invalid QR version '{}' (expected auto, 1-40, M1-M4, an rMQR size like R11x43, or a + suffix for a minimum)",
+                s
+            ))?;
+
+        Ok(if minimum { QrVersion::Minimum(version) } else { QrVersion::Exact(version) })
+    }
+}
+
+/// Version number of a Micro QR (`M1`-`M4`) or rMQR (`R11x43`) name
+fn parse_version_name(name: &str) -> Option<u8> {
+    let name = name.to_ascii_uppercase();
+    if let Some(number) = name.strip_prefix('M') {
+        return number.parse().ok().filter(|v| (1..=4).contains(v));
+    }
+    let (height, width) = name.strip_prefix('R')?.split_once('X')?;
+    crate::qr::rmqr::size_index(height.parse().ok()?, width.parse().ok()?)
+}
+
+/// Segment mode used to encode the content
+///
+/// Restricted modes pack more characters into each module; they fail when
+/// the content contains characters outside their set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EncodingMode {
+    /// Mix of modes chosen for the shortest encoding
+    #[default]
+    Auto,
+    /// Digits 0-9 only (3.3 bits per character)
+    Numeric,
+    /// Digits, uppercase A-Z and ` $%*+-./:` (5.5 bits per character)
+    Alphanumeric,
+    /// Any bytes; text is stored as UTF-8 (8 bits per byte)
+    Byte,
+    /// Shift JIS double-byte characters (13 bits per character)
+    Kanji,
+}
+
+impl EncodingMode {
+    /// Approximate bits needed per character (per byte for `Auto` and `Byte`)
+    pub fn bits_per_char(self) -> f32 {
+        match self {
+            EncodingMode::Numeric => 10.0 / 3.0,
+            EncodingMode::Alphanumeric => 5.5,
+            EncodingMode::Auto | EncodingMode::Byte => 8.0,
+            EncodingMode::Kanji => 13.0,
+        }
+    }
+}
+
+impl FromStr for EncodingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "auto" => Ok(EncodingMode::Auto),
+            "numeric" => Ok(EncodingMode::Numeric),
+            "alphanumeric" | "alnum" => Ok(EncodingMode::Alphanumeric),
+            "byte" | "binary" => Ok(EncodingMode::Byte),
+            "kanji" => Ok(EncodingMode::Kanji),
+            _ => Err(format!("Unknown encoding mode '{}' (expected auto, numeric, alphanumeric, byte or kanji)", s)),
+        }
+    }
+}
+
+/// Character set the content is stored in
+///
+/// Anything other than `Auto` writes an ECI (Extended Channel
+/// Interpretation) designator in front of the content, so scanners that
+/// would otherwise assume Latin-1 or Shift JIS decode it correctly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CharacterSet {
+    /// UTF-8 without a designator (what phone scanners expect)
+    #[default]
+    Auto,
+    /// UTF-8 (ECI 26)
+    Utf8,
+    /// ISO-8859-1, Western European (ECI 3)
+    Iso8859_1,
+    /// ISO-8859-2, Central European (ECI 4)
+    Iso8859_2,
+    /// ISO-8859-5, Cyrillic (ECI 7)
+    Iso8859_5,
+    /// ISO-8859-7, Greek (ECI 9)
+    Iso8859_7,
+    /// ISO-8859-15, Western European with the euro sign (ECI 17)
+    Iso8859_15,
+    /// Windows-1250, Central European (ECI 21)
+    Windows1250,
+    /// Windows-1251, Cyrillic (ECI 22)
+    Windows1251,
+    /// Windows-1252, Western European (ECI 23)
+    Windows1252,
+    /// Shift JIS, Japanese (ECI 20)
+    ShiftJis,
+    /// Big5, Traditional Chinese (ECI 28)
+    Big5,
+    /// GB 18030, Simplified Chinese (ECI 29)
+    Gb18030,
+    /// EUC-KR, Korean (ECI 30)
+    EucKr,
+}
+
+impl CharacterSet {
+    /// Every character set, in menu order
+    pub const ALL: [CharacterSet; 14] = [
+        CharacterSet::Auto,
+        CharacterSet::Utf8,
+        CharacterSet::Iso8859_1,
+        CharacterSet::Iso8859_2,
+        CharacterSet::Iso8859_5,
+        CharacterSet::Iso8859_7,
+        CharacterSet::Iso8859_15,
+        CharacterSet::Windows1250,
+        CharacterSet::Windows1251,
+        CharacterSet::Windows1252,
+        CharacterSet::ShiftJis,
+        CharacterSet::Big5,
+        CharacterSet::Gb18030,
+        CharacterSet::EucKr,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            CharacterSet::Auto => "Auto (UTF-8, no ECI)",
+            CharacterSet::Utf8 => "UTF-8",
+            CharacterSet::Iso8859_1 => "ISO-8859-1",
+            CharacterSet::Iso8859_2 => "ISO-8859-2",
+            CharacterSet::Iso8859_5 => "ISO-8859-5",
+            CharacterSet::Iso8859_7 => "ISO-8859-7",
+            CharacterSet::Iso8859_15 => "ISO-8859-15",
+            CharacterSet::Windows1250 => "Windows-1250",
+            CharacterSet::Windows1251 => "Windows-1251",
+            CharacterSet::Windows1252 => "Windows-1252",
+            CharacterSet::ShiftJis => "Shift JIS",
+            CharacterSet::Big5 => "Big5",
+            CharacterSet::Gb18030 => "GB 18030",
+            CharacterSet::EucKr => "EUC-KR",
+        }
+    }
+
+    /// ECI designator written in front of the content (`None` for `Auto`)
+    pub fn eci_designator(self) -> Option<u32> {
+        match self {
+            CharacterSet::Auto => None,
+            CharacterSet::Utf8 => Some(26),
+            CharacterSet::Iso8859_1 => Some(3),
+            CharacterSet::Iso8859_2 => Some(4),
+            CharacterSet::Iso8859_5 => Some(7),
+            CharacterSet::Iso8859_7 => Some(9),
+            CharacterSet::Iso8859_15 => Some(17),
+            CharacterSet::Windows1250 => Some(21),
+            CharacterSet::Windows1251 => Some(22),
+            CharacterSet::Windows1252 => Some(23),
+            CharacterSet::ShiftJis => Some(20),
+            CharacterSet::Big5 => Some(28),
+            CharacterSet::Gb18030 => Some(29),
+            CharacterSet::EucKr => Some(30),
+        }
+    }
+}
+
+impl FromStr for CharacterSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = normalize_name(s);
+        CharacterSet::ALL
+            .into_iter()
+            .find(|set| normalize_name(set.name()) == name)
+            .or(match name.as_str() {
+                "auto" | "none" => Some(CharacterSet::Auto),
+                "latin1" => Some(CharacterSet::Iso8859_1),
+                "sjis" => Some(CharacterSet::ShiftJis),
+                "gbk" | "gb2312" => Some(CharacterSet::Gb18030),
+                _ => None,
+            })
+            .ok_or_else(|| format!(
+                "Unknown character set '{}' (expected auto, utf-8, iso-8859-1, -2, -5, -7, -15, windows-1250, -1251, -1252, shift-jis, big5, gb18030 or euc-kr)",
+                s
+            ))
+    }
+}
+
+/// How the content text is interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ContentMode {
+    /// Encoded as written
+    #[default]
+    Text,
+    /// GS1 data: an element string such as `(01)09501101530003(10)LOT42`
+    /// (encoded with FNC1 in the first position) or a GS1 Digital Link URI
+    Gs1,
+}
+
+impl FromStr for ContentMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "text" | "plain" => Ok(ContentMode::Text),
+            "gs1" => Ok(ContentMode::Gs1),
+            _ => Err(format!("Unknown content mode '{}' (expected text or gs1)", s)),
+        }
+    }
 }
 
 /// Visual style for QR code data modules
@@ -55,6 +372,153 @@ pub enum ModuleStyle {
     RoundedSquare,
     /// Small dot-style modules - minimalist, artistic look
     Dots,
+    /// Modules merged with their neighbors, rounded only on exposed corners
+    Liquid,
+    /// Vertical runs of modules drawn as capsules
+    VerticalLines,
+    /// Horizontal runs of modules drawn as capsules
+    HorizontalLines,
+}
+
+impl FromStr for ModuleStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "square" => Ok(ModuleStyle::Square),
+            "circle" => Ok(ModuleStyle::Circle),
+            "roundedsquare" | "rounded" => Ok(ModuleStyle::RoundedSquare),
+            "dots" | "dot" => Ok(ModuleStyle::Dots),
+            "liquid" => Ok(ModuleStyle::Liquid),
+            "verticallines" | "vertical" => Ok(ModuleStyle::VerticalLines),
+            "horizontallines" | "horizontal" => Ok(ModuleStyle::HorizontalLines),
+            _ => Err(format!(
+                "Unknown module style '{}' (expected square, circle, rounded-square, dots, liquid, vertical-lines or horizontal-lines)",
+                s
+            )),
+        }
+    }
+}
+
+/// Style for alignment and timing patterns
+///
+/// These function patterns help scanners correct for distortion, so they
+/// default to plain squares regardless of the data module style.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PatternStyle {
+    /// Plain squares - most reliable for scanning
+    #[default]
+    Solid,
+    /// Slightly rounded corners
+    Rounded,
+    /// Circles (alignment patterns become a ring around a dot)
+    Circle,
+    /// Drawn like data modules with the selected module style
+    MatchModules,
+}
+
+impl FromStr for PatternStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "solid" | "square" => Ok(PatternStyle::Solid),
+            "rounded" => Ok(PatternStyle::Rounded),
+            "circle" => Ok(PatternStyle::Circle),
+            "matchmodules" | "modules" => Ok(PatternStyle::MatchModules),
+            _ => Err(format!("Unknown pattern style '{}' (expected solid, rounded, circle or modules)", s)),
+        }
+    }
+}
+
+/// How the requested output size is turned into module and image sizes
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SizingMode {
+    /// Whole-pixel modules; the image shrinks to a multiple of the module size
+    #[default]
+    FitModules,
+    /// Whole-pixel modules, centered on a canvas of exactly the requested size
+    PadToSize,
+    /// Fractional modules filling exactly the requested size (anti-aliased edges)
+    Exact,
+}
+
+impl FromStr for SizingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "fitmodules" | "fit" => Ok(SizingMode::FitModules),
+            "padtosize" | "pad" => Ok(SizingMode::PadToSize),
+            "exact" => Ok(SizingMode::Exact),
+            _ => Err(format!("Unknown sizing mode '{}' (expected fit, pad or exact)", s)),
+        }
+    }
+}
+
+/// Anti-aliasing quality for raster output
+///
+/// Curved module and eye shapes are rendered by estimating how much of each
+/// edge pixel they cover from a grid of sub-pixel samples.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Antialiasing {
+    /// Hard edges: one sample per pixel (fastest, jagged curves)
+    Off,
+    /// 4x4 samples per edge pixel
+    #[default]
+    Standard,
+    /// 8x8 samples per edge pixel (smoothest, slowest)
+    High,
+}
+
+impl Antialiasing {
+    /// Number of samples along each axis of a pixel
+    pub fn samples_per_axis(self) -> u32 {
+        match self {
+            Antialiasing::Off => 1,
+            Antialiasing::Standard => 4,
+            Antialiasing::High => 8,
+        }
+    }
+}
+
+impl FromStr for Antialiasing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "off" | "none" => Ok(Antialiasing::Off),
+            "standard" | "on" => Ok(Antialiasing::Standard),
+            "high" => Ok(Antialiasing::High),
+            _ => Err(format!("Unknown anti-aliasing quality '{}' (expected off, standard or high)", s)),
+        }
+    }
+}
+
+/// How to react when a logo covers more codewords than the error
+/// correction can safely recover
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LogoFit {
+    /// Keep the settings as they are (only report the coverage)
+    #[default]
+    Off,
+    /// Raise the error correction level until the logo fits
+    RaiseErrorCorrection,
+    /// Shrink the logo until it fits the chosen error correction level
+    ShrinkLogo,
+}
+
+impl FromStr for LogoFit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "off" | "none" => Ok(LogoFit::Off),
+            "raiseec" | "raiseerrorcorrection" | "ec" => Ok(LogoFit::RaiseErrorCorrection),
+            "shrinklogo" | "shrink" => Ok(LogoFit::ShrinkLogo),
+            _ => Err(format!("Unknown logo fit '{}' (expected off, raise-ec or shrink-logo)", s)),
+        }
+    }
 }
 
 /// Tab selection for UI navigation
@@ -87,24 +551,265 @@ pub enum GradientType {
     Radial,
 }
 
+impl FromStr for GradientType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "horizontal" => Ok(GradientType::Horizontal),
+            "vertical" => Ok(GradientType::Vertical),
+            "diagonal" => Ok(GradientType::Diagonal),
+            "radial" => Ok(GradientType::Radial),
+            _ => Err(format!("Unknown gradient type '{}' (expected horizontal, vertical, diagonal or radial)", s)),
+        }
+    }
+}
+
+/// Shape of the outer ring of an eye (finder pattern)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum EyeFrameStyle {
+    /// Square ring - traditional QR appearance
+    #[default]
+    Square,
+    /// Square ring with rounded corners
+    Rounded,
+    /// Circular ring - smooth, modern look (hard to scan)
+    Circle,
+    /// Two opposite corners fully rounded, like a leaf (hard to scan)
+    Leaf,
+    /// Ring of separate dots, one per module
+    Dotted,
+    /// Diamond-shaped ring - geometric, distinctive (hard to scan)
+    Diamond,
+}
+
+impl FromStr for EyeFrameStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "square" | "standard" => Ok(EyeFrameStyle::Square),
+            "rounded" | "roundedsquare" => Ok(EyeFrameStyle::Rounded),
+            "circle" => Ok(EyeFrameStyle::Circle),
+            "leaf" => Ok(EyeFrameStyle::Leaf),
+            "dotted" | "dots" => Ok(EyeFrameStyle::Dotted),
+            "diamond" => Ok(EyeFrameStyle::Diamond),
+            _ => Err(format!("Unknown eye frame '{}' (expected square, rounded, circle, leaf, dotted or diamond)", s)),
+        }
+    }
+}
+
+/// Shape of the 3x3 center of an eye (finder pattern)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum EyePupilStyle {
+    /// Square pupil - traditional QR appearance
+    #[default]
+    Square,
+    /// Square pupil with rounded corners
+    Rounded,
+    /// Circular pupil
+    Circle,
+    /// Diamond-shaped pupil
+    Diamond,
+    /// Five-pointed star
+    Star,
+}
+
+impl FromStr for EyePupilStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "square" | "standard" => Ok(EyePupilStyle::Square),
+            "rounded" | "roundedsquare" => Ok(EyePupilStyle::Rounded),
+            "circle" => Ok(EyePupilStyle::Circle),
+            "diamond" => Ok(EyePupilStyle::Diamond),
+            "star" => Ok(EyePupilStyle::Star),
+            _ => Err(format!("Unknown eye pupil '{}' (expected square, rounded, circle, diamond or star)", s)),
+        }
+    }
+}
+
 /// Eye (finder pattern) visual style
 ///
 /// The three corner squares that help scanners locate the QR code.
-/// Custom styles can make QR codes more visually distinctive.
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum EyeStyle {
+/// Each eye is drawn as an outer frame and an inner pupil, which can be
+/// styled independently. The associated constants are the named styles
+/// offered before the split; presets that stored one of those names are
+/// still read.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "EyeStyleRepr")]
+pub struct EyeStyle {
+    /// Outer ring shape
+    pub frame: EyeFrameStyle,
+    /// Center shape
+    pub pupil: EyePupilStyle,
+}
+
+impl EyeStyle {
     /// Standard square eyes - traditional QR appearance
-    Standard,
+    pub const STANDARD: Self = Self::new(EyeFrameStyle::Square, EyePupilStyle::Square);
     /// Circular eyes - smooth, modern look
-    Circle,
+    pub const CIRCLE: Self = Self::new(EyeFrameStyle::Circle, EyePupilStyle::Circle);
     /// Rounded square eyes - softened corners
-    RoundedSquare,
+    pub const ROUNDED_SQUARE: Self = Self::new(EyeFrameStyle::Rounded, EyePupilStyle::Rounded);
     /// Flower-shaped eyes - decorative, artistic
-    Flower,
+    pub const FLOWER: Self = Self::new(EyeFrameStyle::Dotted, EyePupilStyle::Circle);
     /// Diamond-shaped eyes - geometric, distinctive
+    pub const DIAMOND: Self = Self::new(EyeFrameStyle::Diamond, EyePupilStyle::Diamond);
+
+    /// Named styles with their display names
+    pub const PRESETS: [(&'static str, Self); 5] = [
+        ("Standard", Self::STANDARD),
+        ("Circle", Self::CIRCLE),
+        ("Rounded", Self::ROUNDED_SQUARE),
+        ("Flower", Self::FLOWER),
+        ("Diamond", Self::DIAMOND),
+    ];
+
+    /// Combine a frame and a pupil shape
+    pub const fn new(frame: EyeFrameStyle, pupil: EyePupilStyle) -> Self {
+        Self { frame, pupil }
+    }
+
+    /// Display name of the matching named style, if any
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS.iter().find(|(_, style)| style == self).map(|(name, _)| *name)
+    }
+}
+
+impl FromStr for EyeStyle {
+    type Err = String;
+
+    /// Parse one of the named styles
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "standard" | "square" => Ok(EyeStyle::STANDARD),
+            "circle" => Ok(EyeStyle::CIRCLE),
+            "roundedsquare" | "rounded" => Ok(EyeStyle::ROUNDED_SQUARE),
+            "flower" => Ok(EyeStyle::FLOWER),
+            "diamond" => Ok(EyeStyle::DIAMOND),
+            _ => Err(format!("Unknown eye style '{}' (expected standard, circle, rounded-square, flower or diamond)", s)),
+        }
+    }
+}
+
+/// One of the three finder patterns (eyes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EyePosition {
+    /// Top-left corner
+    TopLeft,
+    /// Top-right corner
+    TopRight,
+    /// Bottom-left corner
+    BottomLeft,
+}
+
+impl EyePosition {
+    /// All eyes, in the order of `QrMatrix::eye_positions`
+    pub const ALL: [EyePosition; 3] = [EyePosition::TopLeft, EyePosition::TopRight, EyePosition::BottomLeft];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            EyePosition::TopLeft => "Top-Left",
+            EyePosition::TopRight => "Top-Right",
+            EyePosition::BottomLeft => "Bottom-Left",
+        }
+    }
+
+    /// Rotation (degrees) that turns a shape drawn for the top-left eye
+    /// towards the center of the code
+    pub fn inward_rotation(self) -> u16 {
+        match self {
+            EyePosition::TopLeft => 0,
+            EyePosition::TopRight => 90,
+            EyePosition::BottomLeft => 270,
+        }
+    }
+}
+
+impl FromStr for EyePosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_name(s).as_str() {
+            "topleft" | "tl" => Ok(EyePosition::TopLeft),
+            "topright" | "tr" => Ok(EyePosition::TopRight),
+            "bottomleft" | "bl" => Ok(EyePosition::BottomLeft),
+            _ => Err(format!("Unknown eye '{}' (expected top-left, top-right or bottom-left)", s)),
+        }
+    }
+}
+
+/// Styling for one eye that replaces the shared eye settings
+///
+/// Unset fields fall back to the shared eye style and colors.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EyeOverride {
+    /// Which eye this applies to
+    pub position: EyePosition,
+
+    /// Frame and pupil shapes
+    #[serde(default)]
+    pub style: Option<EyeStyle>,
+
+    /// Frame color (RGB 0-255)
+    #[serde(default)]
+    pub frame_color: Option<[u8; 3]>,
+
+    /// Pupil color (RGB 0-255); defaults to the override's frame color
+    #[serde(default)]
+    pub pupil_color: Option<[u8; 3]>,
+
+    /// Clockwise rotation in degrees (0, 90, 180 or 270); other values,
+    /// e.g. from hand-edited presets, are rounded to the nearest quarter turn
+    #[serde(default)]
+    pub rotation: u16,
+}
+
+impl EyeOverride {
+    /// Override for an eye that changes nothing yet
+    pub fn new(position: EyePosition) -> Self {
+        Self { position, style: None, frame_color: None, pupil_color: None, rotation: 0 }
+    }
+}
+
+/// Serialized forms of `EyeStyle`: the frame/pupil pair, or a single
+/// style name as written by older presets
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EyeStyleRepr {
+    Split {
+        frame: EyeFrameStyle,
+        pupil: EyePupilStyle,
+    },
+    Named(NamedEyeStyle),
+}
+
+/// Style names stored by presets from before the frame/pupil split
+#[derive(Deserialize)]
+enum NamedEyeStyle {
+    Standard,
+    Circle,
+    RoundedSquare,
+    Flower,
     Diamond,
 }
 
+impl From<EyeStyleRepr> for EyeStyle {
+    fn from(repr: EyeStyleRepr) -> Self {
+        match repr {
+            EyeStyleRepr::Split { frame, pupil } => EyeStyle::new(frame, pupil),
+            EyeStyleRepr::Named(NamedEyeStyle::Standard) => EyeStyle::STANDARD,
+            EyeStyleRepr::Named(NamedEyeStyle::Circle) => EyeStyle::CIRCLE,
+            EyeStyleRepr::Named(NamedEyeStyle::RoundedSquare) => EyeStyle::ROUNDED_SQUARE,
+            EyeStyleRepr::Named(NamedEyeStyle::Flower) => EyeStyle::FLOWER,
+            EyeStyleRepr::Named(NamedEyeStyle::Diamond) => EyeStyle::DIAMOND,
+        }
+    }
+}
+
 /// Predefined color scheme for quick styling
 ///
 /// Provides professionally designed color combinations for instant use.
@@ -0,0 +1,259 @@
+//! Batch generation dialog
+//!
+//! A floating window for generating one QR code per row of a CSV or JSONL
+//! file using the current style settings. The run happens on a worker
+//! thread, so the window stays responsive, shows progress and can cancel.
+
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+
+use crate::app::QrCodeApp;
+use qrtistry::batch::{self, BatchReport};
+
+/// State of the batch generation dialog (not serialized)
+pub struct BatchDialog {
+    /// Whether the dialog window is shown
+    pub open: bool,
+
+    /// Selected CSV/JSONL input file
+    pub input_path: Option<PathBuf>,
+
+    /// Selected output directory
+    pub output_dir: Option<PathBuf>,
+
+    /// Content template with `{field}` placeholders
+    pub text_template: String,
+
+    /// Output filename template with `{field}` placeholders
+    pub filename_template: String,
+
+    /// Decode every generated code to check that it scans
+    pub verify: bool,
+
+    /// Result summary of the last run
+    pub last_result: Option<String>,
+
+    /// Run in progress, if any
+    worker: Option<BatchWorker>,
+}
+
+/// Batch run on a worker thread
+struct BatchWorker {
+    /// Rows done and total, updated after every row
+    progress: Arc<Mutex<(usize, usize)>>,
+
+    /// Set to stop after the current row
+    cancel: Arc<AtomicBool>,
+
+    /// Receives the outcome when the run ends
+    receiver: Receiver<Result<BatchReport, String>>,
+}
+
+impl Default for BatchDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            input_path: None,
+            output_dir: None,
+            text_template: String::new(),
+            filename_template: batch::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            verify: true,
+            last_result: None,
+            worker: None,
+        }
+    }
+}
+
+/// Render the batch generation window if it is open
+///
+/// # Arguments
+/// * `app` - Application state (its style settings are applied to every row)
+/// * `ctx` - egui context for the floating window
+pub fn render_batch_dialog(app: &mut QrCodeApp, ctx: &egui::Context) {
+    // A run keeps going while the window is closed
+    poll_worker(app);
+
+    if !app.batch_dialog.open {
+        return;
+    }
+
+    let mut open = true;
+    let mut run_clicked = false;
+
+    egui::Window::new("📦 Batch Generation")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let dialog = &mut app.batch_dialog;
+
+            ui.label("Generates one QR code per row using the current settings.");
+            ui.label("💡 Load a preset first to apply it to every row.");
+            ui.add_space(10.0);
+
+            // === Input File ===
+            ui.horizontal(|ui| {
+                if ui.button("📂 Select Input").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Batch Data", &["csv", "jsonl", "ndjson"])
+                        .pick_file()
+                    {
+                        dialog.input_path = Some(path);
+                    }
+                }
+                match &dialog.input_path {
+                    Some(path) => ui.label(format!("📎 {}", path.display())),
+                    None => ui.label("No CSV/JSONL file selected"),
+                };
+            });
+
+            // === Output Directory ===
+            ui.horizontal(|ui| {
+                if ui.button("📁 Output Folder").clicked() {
+                    if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                        dialog.output_dir = Some(path);
+                    }
+                }
+                match &dialog.output_dir {
+                    Some(path) => ui.label(format!("📎 {}", path.display())),
+                    None => ui.label("No output folder selected"),
+                };
+            });
+
+            ui.add_space(10.0);
+
+            // === Templates ===
+            ui.label("Content template (leave empty to use the current text or form):");
+            ui.add(
+                egui::TextEdit::singleline(&mut dialog.text_template)
+                    .hint_text("https://example.com/item/{sku}")
+                    .desired_width(f32::INFINITY)
+            );
+
+            ui.label("Filename template:");
+            ui.add(
+                egui::TextEdit::singleline(&mut dialog.filename_template)
+                    .desired_width(f32::INFINITY)
+            );
+            ui.label("💡 Use {column} for row fields and {row} for the row number");
+
+            ui.add_space(5.0);
+            ui.checkbox(&mut dialog.verify, "Verify each code scans");
+
+            ui.add_space(10.0);
+
+            match &dialog.worker {
+                Some(worker) => {
+                    let (done, total) = *worker.progress.lock().unwrap_or_else(|e| e.into_inner());
+                    let fraction = if total == 0 { 0.0 } else { done as f32 / total as f32 };
+                    ui.add(egui::ProgressBar::new(fraction).text(format!("{} of {} rows", done, total)));
+                    let cancelling = worker.cancel.load(Ordering::Relaxed);
+                    if ui.add_enabled(!cancelling, egui::Button::new("⏹ Cancel")).clicked() {
+                        worker.cancel.store(true, Ordering::Relaxed);
+                    }
+                }
+                None => {
+                    let ready = dialog.input_path.is_some() && dialog.output_dir.is_some();
+                    if ui.add_enabled(ready, egui::Button::new("▶ Generate All")).clicked() {
+                        run_clicked = true;
+                    }
+                }
+            }
+
+            if let Some(result) = &dialog.last_result {
+                ui.add_space(5.0);
+                ui.label(result);
+            }
+        });
+
+    app.batch_dialog.open = open;
+
+    if run_clicked {
+        start_batch(app, ctx);
+    }
+}
+
+/// Start the batch job described by the dialog state on a worker thread
+///
+/// The current settings are copied, so editing the design during the run
+/// does not affect it.
+fn start_batch(app: &mut QrCodeApp, ctx: &egui::Context) {
+    let (Some(input), Some(output_dir)) = (
+        app.batch_dialog.input_path.clone(),
+        app.batch_dialog.output_dir.clone(),
+    ) else {
+        return;
+    };
+
+    let text_template = app.batch_dialog.text_template.trim();
+    let job = batch::BatchJob {
+        input,
+        output_dir,
+        text_template: (!text_template.is_empty()).then(|| text_template.to_string()),
+        filename_template: app.batch_dialog.filename_template.clone(),
+        verify: app.batch_dialog.verify,
+    };
+
+    let progress = Arc::new(Mutex::new((0, 0)));
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+    let mut spec = app.spec.clone();
+    let ctx = ctx.clone();
+    let worker = BatchWorker { progress: progress.clone(), cancel: cancel.clone(), receiver };
+
+    std::thread::spawn(move || {
+        let result = batch::run_batch_with_progress(&mut spec, &job, |done, total| {
+            *progress.lock().unwrap_or_else(|e| e.into_inner()) = (done, total);
+            ctx.request_repaint();
+            !cancel.load(Ordering::Relaxed)
+        });
+        // The dialog is gone only if the application is closing
+        let _ = sender.send(result);
+        ctx.request_repaint();
+    });
+
+    app.batch_dialog.worker = Some(worker);
+    app.batch_dialog.last_result = None;
+    app.status_message = "📦 Batch generation running...".to_string();
+}
+
+/// Collect the outcome of a finished batch run
+fn poll_worker(app: &mut QrCodeApp) {
+    let Some(worker) = &app.batch_dialog.worker else {
+        return;
+    };
+    let result = match worker.receiver.try_recv() {
+        Ok(result) => result,
+        Err(TryRecvError::Empty) => return,
+        Err(TryRecvError::Disconnected) => Err("Batch worker stopped unexpectedly".to_string()),
+    };
+    app.batch_dialog.worker = None;
+
+    let message = match result {
+        Ok(report) if report.cancelled => format!(
+            "⏹ Batch cancelled after {} rows, {} failed (see {})",
+            report.rows.len(),
+            report.failed(),
+            report.summary_path.display()
+        ),
+        Ok(report) if report.failed() == 0 => format!(
+            "✅ Generated {} QR codes (summary: {})",
+            report.succeeded(),
+            report.summary_path.display()
+        ),
+        Ok(report) => format!(
+            "⚠️ Generated {} of {} QR codes, {} failed (see {})",
+            report.succeeded(),
+            report.rows.len(),
+            report.failed(),
+            report.summary_path.display()
+        ),
+        Err(e) => format!("❌ Batch failed: {}", e),
+    };
+
+    app.batch_dialog.last_result = Some(message.clone());
+    app.status_message = message;
+}
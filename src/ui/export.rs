@@ -0,0 +1,147 @@
+//! Export dialog
+//!
+//! A floating window for choosing the output format and its options
+//! (JPEG quality, WebP compression, TIFF depth, icon sizes, transparent
+//! background) before picking where to save.
+
+use eframe::egui;
+
+use crate::app::QrCodeApp;
+use crate::io;
+use qrtistry::export::{ExportOptions, OutputFormat};
+use image::ImageFormat;
+
+/// Icon sizes offered for ICO files (favicons up to high-DPI desktop icons)
+const ICO_SIZE_CHOICES: [u32; 7] = [16, 24, 32, 48, 64, 128, 256];
+
+/// State of the export dialog (not serialized; the options live in the spec)
+pub struct ExportDialog {
+    /// Whether the dialog window is shown
+    pub open: bool,
+
+    /// Selected output format
+    pub format: OutputFormat,
+}
+
+impl Default for ExportDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            format: OutputFormat::Raster(ImageFormat::Png),
+        }
+    }
+}
+
+/// Render the export window if it is open
+///
+/// # Arguments
+/// * `app` - Application state (its export options are edited in place)
+/// * `ctx` - egui context for the floating window
+pub fn render_export_dialog(app: &mut QrCodeApp, ctx: &egui::Context) {
+    if !app.export_dialog.open {
+        return;
+    }
+
+    let mut open = true;
+    let mut save_clicked = false;
+    let has_bg_image = app.spec.bg_image.is_some();
+
+    egui::Window::new("💾 Save QR Code")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let format = &mut app.export_dialog.format;
+            let options = &mut app.spec.export_options;
+
+            // === Format ===
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Format:");
+                for choice in OutputFormat::CHOICES {
+                    ui.selectable_value(format, choice, choice.extensions()[0].to_uppercase())
+                        .on_hover_text(choice.name());
+                }
+            });
+            ui.add_space(10.0);
+
+            // === Format Options ===
+            ui.group(|ui| {
+                render_format_options(ui, *format, options);
+
+                if format.supports_transparency() {
+                    ui.add_enabled(
+                        !has_bg_image,
+                        egui::Checkbox::new(&mut options.transparent_background, "Transparent Background"),
+                    );
+                    if has_bg_image {
+                        ui.label("💡 Remove the background image to save with transparency");
+                    }
+                }
+            });
+            ui.add_space(10.0);
+
+            if ui.button("💾 Save As...").clicked() {
+                save_clicked = true;
+            }
+        });
+
+    app.export_dialog.open = open;
+
+    if save_clicked && io::save_qr_code(app, app.export_dialog.format) {
+        app.export_dialog.open = false;
+    }
+}
+
+/// Options specific to one output format
+fn render_format_options(ui: &mut egui::Ui, format: OutputFormat, options: &mut ExportOptions) {
+    match format {
+        OutputFormat::Raster(ImageFormat::Png) => {
+            ui.label("Lossless image for screens and the web");
+        }
+        OutputFormat::Raster(ImageFormat::Jpeg) => {
+            ui.add(egui::Slider::new(&mut options.jpeg_quality, 1..=100).text("Quality"));
+            ui.label("💡 Lower quality blurs module edges; keep 80 or above for reliable scanning");
+        }
+        OutputFormat::Raster(ImageFormat::WebP) => {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut options.webp_lossless, true, "Lossless");
+                ui.selectable_value(&mut options.webp_lossless, false, "Lossy");
+            });
+            if !options.webp_lossless {
+                ui.add(egui::Slider::new(&mut options.webp_quality, 1..=100).text("Quality"));
+            }
+        }
+        OutputFormat::Raster(ImageFormat::Bmp) => {
+            ui.label("Uncompressed bitmap on the background color");
+        }
+        OutputFormat::Raster(ImageFormat::Tiff) => {
+            ui.checkbox(&mut options.tiff_16_bit, "16 bits per channel");
+        }
+        OutputFormat::Raster(ImageFormat::Ico) => {
+            ui.label("Icon sizes:");
+            ui.horizontal_wrapped(|ui| {
+                for size in ICO_SIZE_CHOICES {
+                    let mut selected = options.ico_sizes.contains(&size);
+                    if ui.checkbox(&mut selected, format!("{0}x{0}", size)).changed() {
+                        options.ico_sizes.retain(|&s| s != size);
+                        if selected {
+                            options.ico_sizes.push(size);
+                            options.ico_sizes.sort_unstable();
+                        }
+                    }
+                }
+            });
+            if options.ico_sizes.is_empty() {
+                ui.colored_label(egui::Color32::YELLOW, "⚠️ Select at least one icon size");
+            }
+            ui.label("💡 Small icons blur the modules; use them as favicons, not for scanning");
+        }
+        OutputFormat::Svg => {
+            ui.label("Vector artwork that scales to any size");
+        }
+        OutputFormat::Pdf => {
+            ui.label("Print-ready page (see Print Layout in the Advanced tab)");
+        }
+        OutputFormat::Raster(_) => {}
+    }
+}
@@ -4,6 +4,8 @@
 
 use eframe::egui;
 
+use qrtistry::qr::scannability::{Degradation, ScannabilityReport};
+
 /// Render an RGB color picker widget
 ///
 /// Creates an interactive color picker that modifies the provided color array.
@@ -35,3 +37,69 @@ pub fn color_picker(ui: &mut egui::Ui, color: &mut [u8; 3]) {
         color[2] = (color_f32[2] * 255.0) as u8;
     }
 }
+
+/// Color for a scannability score or margin (0-100)
+///
+/// Green for good results, yellow for borderline and red for poor ones.
+pub fn score_color(score: u32) -> egui::Color32 {
+    match score {
+        60.. => egui::Color32::from_rgb(46, 160, 67),
+        40..=59 => egui::Color32::YELLOW,
+        _ => egui::Color32::from_rgb(220, 60, 60),
+    }
+}
+
+/// Show the measured scannability score of the last preview
+///
+/// # Arguments
+/// * `ui` - egui UI context to render into
+/// * `report` - Report of the last generated preview, if any
+pub fn scannability_score(ui: &mut egui::Ui, report: Option<&ScannabilityReport>) {
+    match report {
+        Some(report) if !report.is_complete() => {
+            ui.label("⏳ Measuring scannability...");
+        }
+        Some(report) if report.baseline.passed() => {
+            ui.colored_label(score_color(report.score()), format!("📊 {}", report.summary()));
+        }
+        Some(report) if report.baseline.skipped() => {
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠️ {}", report.baseline.summary()));
+        }
+        Some(report) => {
+            ui.colored_label(score_color(0), format!("❌ {}", report.baseline.summary()));
+        }
+        None => {
+            ui.label("💡 Generate the preview to measure scannability");
+        }
+    }
+}
+
+/// Show the measured margin of one degradation from the last preview
+///
+/// Used next to settings that mainly affect that degradation, e.g. opacity
+/// and reduced contrast.
+///
+/// # Arguments
+/// * `ui` - egui UI context to render into
+/// * `report` - Report of the last generated preview, if any
+/// * `degradation` - Degradation to show
+pub fn scannability_margin(
+    ui: &mut egui::Ui,
+    report: Option<&ScannabilityReport>,
+    degradation: Degradation,
+) {
+    let result = report
+        .filter(|r| r.baseline.passed())
+        .and_then(|r| r.results.iter().find(|d| d.degradation == degradation));
+
+    match result {
+        Some(result) => {
+            let margin = (result.margin() * 100.0).round() as u32;
+            ui.colored_label(
+                score_color(margin),
+                format!("📊 {}: {}", result.degradation.name(), result.summary()),
+            );
+        }
+        None => scannability_score(ui, report),
+    }
+}
@@ -5,7 +5,12 @@
 pub mod tabs;
 pub mod preview;
 pub mod helpers;
+pub mod batch;
+pub mod export;
+pub mod payload;
 
 // Re-export main functions for convenience
 pub use preview::render_preview;
 pub use tabs::render_controls;
+pub use batch::render_batch_dialog;
+pub use export::render_export_dialog;
@@ -0,0 +1,249 @@
+//! Payload editors
+//!
+//! Form-based editors for the structured content types (links, WiFi, contacts,
+//! calendar events, locations, messages, calls, payments and authenticators).
+//! Each form edits the
+//! fields of a `Payload`; the Basic tab turns them into `qr_text`.
+
+use eframe::egui;
+
+use qrtistry::payload::{
+    self, Contact, OtpAlgorithm, OtpType, Payload, PostalAddress, QueryParameter, SwissCurrency, VCardVersion,
+    WifiSecurity,
+};
+
+/// Render the form for a payload
+///
+/// # Arguments
+/// * `ui` - egui UI context
+/// * `payload` - Payload whose fields are edited in place
+pub fn render_payload_editor(ui: &mut egui::Ui, payload: &mut Payload) {
+    egui::Grid::new("payload_fields")
+        .num_columns(2)
+        .spacing([8.0, 6.0])
+        .show(ui, |ui| match payload {
+            Payload::Url { url, source, medium, campaign, term, content, params } => {
+                text_row(ui, "URL:", url, "https://example.com/offer");
+                text_row(ui, "Source:", source, "utm_source, e.g. flyer or {store}");
+                text_row(ui, "Medium:", medium, "utm_medium, e.g. print");
+                text_row(ui, "Campaign:", campaign, "utm_campaign, e.g. spring_sale");
+                text_row(ui, "Term:", term, "utm_term (optional)");
+                text_row(ui, "Content:", content, "utm_content (optional)");
+                query_rows(ui, params);
+            }
+            Payload::Wifi { ssid, password, security, hidden } => {
+                text_row(ui, "Network (SSID):", ssid, "");
+                ui.label("Security:");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(security, WifiSecurity::Wpa, "WPA/WPA2/WPA3");
+                    ui.selectable_value(security, WifiSecurity::Wep, "WEP");
+                    ui.selectable_value(security, WifiSecurity::Open, "Open");
+                });
+                ui.end_row();
+                if *security != WifiSecurity::Open {
+                    text_row(ui, "Password:", password, "");
+                }
+                ui.label("");
+                ui.checkbox(hidden, "Hidden network");
+                ui.end_row();
+            }
+            Payload::VCard { version, contact } => {
+                ui.label("Format:");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(version, VCardVersion::V3, "vCard 3.0");
+                    ui.selectable_value(version, VCardVersion::V4, "vCard 4.0");
+                });
+                ui.end_row();
+                contact_rows(ui, contact, true);
+            }
+            Payload::MeCard { contact } => contact_rows(ui, contact, false),
+            Payload::Event { summary, location, description, start, end, all_day } => {
+                text_row(ui, "Title:", summary, "");
+                ui.label("");
+                ui.checkbox(all_day, "All-day event");
+                ui.end_row();
+                let hint = if *all_day { "2026-11-05" } else { "2026-11-05 14:00" };
+                text_row(ui, "Starts:", start, hint);
+                text_row(ui, "Ends:", end, hint);
+                text_row(ui, "Location:", location, "");
+                multiline_row(ui, "Description:", description);
+            }
+            Payload::Geo { latitude, longitude } => {
+                ui.label("Latitude:");
+                ui.add(egui::DragValue::new(latitude).range(-90.0..=90.0).speed(0.0001).fixed_decimals(6).suffix("°"));
+                ui.end_row();
+                ui.label("Longitude:");
+                ui.add(egui::DragValue::new(longitude).range(-180.0..=180.0).speed(0.0001).fixed_decimals(6).suffix("°"));
+                ui.end_row();
+            }
+            Payload::Sms { number, message } => {
+                text_row(ui, "Phone number:", number, "+1 555 0100");
+                multiline_row(ui, "Message:", message);
+            }
+            Payload::Email { to, subject, body } => {
+                text_row(ui, "To:", to, "name@example.com");
+                text_row(ui, "Subject:", subject, "");
+                multiline_row(ui, "Body:", body);
+            }
+            Payload::Phone { number } => text_row(ui, "Phone number:", number, "+1 555 0100"),
+            Payload::Epc { name, iban, bic, amount, purpose, reference, text, information } => {
+                text_row(ui, "Beneficiary:", name, "");
+                text_row(ui, "IBAN:", iban, "DE89 3704 0044 0532 0130 00");
+                text_row(ui, "BIC:", bic, "optional within the EEA");
+                text_row(ui, "Amount (EUR):", amount, "12.50");
+                text_row(ui, "Purpose code:", purpose, "optional, e.g. GDDS");
+                text_row(ui, "Reference:", reference, "RF18 5390 0754 7034");
+                text_row(ui, "Remittance text:", text, "instead of a reference");
+                text_row(ui, "Note to payer:", information, "");
+            }
+            Payload::SwissQrBill { iban, creditor, amount, currency, debtor, reference, message } => {
+                text_row(ui, "IBAN / QR-IBAN:", iban, "CH44 3199 9123 0008 8901 2");
+                ui.label("Amount:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(amount).hint_text("blank lets the payer enter it"));
+                    ui.selectable_value(currency, SwissCurrency::Chf, "CHF");
+                    ui.selectable_value(currency, SwissCurrency::Eur, "EUR");
+                });
+                ui.end_row();
+                text_row(ui, "Reference:", reference, "QR reference (27 digits) or RF...");
+                multiline_row(ui, "Message:", message);
+                ui.strong("Payable to");
+                ui.end_row();
+                address_rows(ui, creditor);
+                ui.strong("Payable by");
+                ui.label("optional");
+                ui.end_row();
+                address_rows(ui, debtor);
+            }
+            Payload::Upi { vpa, name, amount, note, reference } => {
+                text_row(ui, "UPI ID:", vpa, "shop@upi");
+                text_row(ui, "Payee name:", name, "");
+                text_row(ui, "Amount (INR):", amount, "250.00");
+                text_row(ui, "Note:", note, "");
+                text_row(ui, "Reference:", reference, "optional");
+            }
+            Payload::Bitcoin { address, amount, label, message } => {
+                text_row(ui, "Address:", address, "bc1q...");
+                text_row(ui, "Amount (BTC):", amount, "0.0015");
+                text_row(ui, "Label:", label, "");
+                text_row(ui, "Message:", message, "");
+            }
+            Payload::Otp { otp_type, issuer, account, secret, algorithm, digits, period, counter } => {
+                ui.label("Type:");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(otp_type, OtpType::Totp, "Time-based (TOTP)");
+                    ui.selectable_value(otp_type, OtpType::Hotp, "Counter-based (HOTP)");
+                });
+                ui.end_row();
+                text_row(ui, "Issuer:", issuer, "Acme");
+                text_row(ui, "Account:", account, "name@example.com");
+                ui.label("Secret (Base32):");
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(secret).font(egui::TextStyle::Monospace).desired_width(260.0));
+                    // The system random source only fails on broken platforms;
+                    // the empty secret is then reported below the form
+                    if ui.button("🎲 Generate").on_hover_text("Create a random 160-bit secret on this computer").clicked() {
+                        if let Ok(generated) = payload::generate_otp_secret() {
+                            *secret = generated;
+                        }
+                    }
+                });
+                ui.end_row();
+                ui.label("");
+                ui.label("🔒 The secret is not saved in presets");
+                ui.end_row();
+                ui.label("Algorithm:");
+                ui.horizontal(|ui| {
+                    for option in OtpAlgorithm::ALL {
+                        ui.selectable_value(algorithm, option, option.name());
+                    }
+                });
+                ui.end_row();
+                ui.label("Digits:");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(digits, 6, "6");
+                    ui.selectable_value(digits, 8, "8");
+                });
+                ui.end_row();
+                match otp_type {
+                    OtpType::Totp => {
+                        ui.label("Period:");
+                        ui.add(egui::DragValue::new(period).range(1..=600).suffix(" s"));
+                    }
+                    OtpType::Hotp => {
+                        ui.label("Counter:");
+                        ui.add(egui::DragValue::new(counter));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+}
+
+/// Extra query parameters as editable name/value pairs
+fn query_rows(ui: &mut egui::Ui, params: &mut Vec<QueryParameter>) {
+    let mut removed = None;
+    for (index, param) in params.iter_mut().enumerate() {
+        ui.label("Parameter:");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut param.name).hint_text("name").desired_width(100.0));
+            ui.label("=");
+            ui.add(egui::TextEdit::singleline(&mut param.value).hint_text("value").desired_width(160.0));
+            if ui.small_button("🗑").on_hover_text("Remove parameter").clicked() {
+                removed = Some(index);
+            }
+        });
+        ui.end_row();
+    }
+    if let Some(index) = removed {
+        params.remove(index);
+    }
+    ui.label("");
+    if ui.button("➕ Add parameter").clicked() {
+        params.push(QueryParameter::default());
+    }
+    ui.end_row();
+}
+
+/// Structured address of a Swiss QR-bill party
+fn address_rows(ui: &mut egui::Ui, address: &mut PostalAddress) {
+    text_row(ui, "Name:", &mut address.name, "");
+    text_row(ui, "Street:", &mut address.street, "");
+    text_row(ui, "Building number:", &mut address.building_number, "");
+    text_row(ui, "Postal code:", &mut address.postal_code, "");
+    text_row(ui, "Town:", &mut address.town, "");
+    text_row(ui, "Country:", &mut address.country, "CH");
+}
+
+/// Contact fields shared by vCard and MeCard (job title is vCard only)
+fn contact_rows(ui: &mut egui::Ui, contact: &mut Contact, with_title: bool) {
+    text_row(ui, "First name:", &mut contact.first_name, "");
+    text_row(ui, "Last name:", &mut contact.last_name, "");
+    text_row(ui, "Organization:", &mut contact.organization, "");
+    if with_title {
+        text_row(ui, "Job title:", &mut contact.title, "");
+    }
+    text_row(ui, "Phone:", &mut contact.phone, "+1 555 0100");
+    text_row(ui, "Email:", &mut contact.email, "name@example.com");
+    text_row(ui, "Website:", &mut contact.website, "https://example.com");
+    text_row(ui, "Street:", &mut contact.street, "");
+    text_row(ui, "City:", &mut contact.city, "");
+    text_row(ui, "Region:", &mut contact.region, "");
+    text_row(ui, "Postal code:", &mut contact.postal_code, "");
+    text_row(ui, "Country:", &mut contact.country, "");
+    multiline_row(ui, "Note:", &mut contact.note);
+}
+
+/// Labelled single-line text field with an optional example as hint
+fn text_row(ui: &mut egui::Ui, label: &str, value: &mut String, hint: &str) {
+    ui.label(label);
+    ui.add(egui::TextEdit::singleline(value).hint_text(hint).desired_width(f32::INFINITY));
+    ui.end_row();
+}
+
+/// Labelled multi-line text field
+fn multiline_row(ui: &mut egui::Ui, label: &str, value: &mut String) {
+    ui.label(label);
+    ui.add(egui::TextEdit::multiline(value).desired_rows(3).desired_width(f32::INFINITY));
+    ui.end_row();
+}
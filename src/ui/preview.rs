@@ -1,15 +1,128 @@
 //! Preview panel rendering
 //!
 //! Displays the generated QR code in the central panel with appropriate sizing.
+//! The scannability ladder of each preview is measured on a background
+//! thread (`ScannabilityJob`) so the window stays responsive.
 
 use eframe::egui;
+use image::RgbaImage;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 
 use crate::app::QrCodeApp;
+use crate::ui::helpers;
+use qrtistry::qr::scannability::{self, ScannabilityReport};
+use qrtistry::qr::QrSpec;
+
+/// Number of measured previews remembered before the cache is cleared
+const MAX_CACHED_REPORTS: usize = 64;
+
+/// Scannability measurement of the previews (not serialized)
+///
+/// The decode check runs inline; the degradations (dozens of decodes) run
+/// on a worker thread. Reports are cached per rendered image and
+/// specification, so going back to an earlier design is instant.
+#[derive(Default)]
+pub struct ScannabilityJob {
+    /// Key of the preview shown now
+    current: Option<u64>,
+
+    /// Receives the report of the running measurement
+    receiver: Option<Receiver<(u64, Option<ScannabilityReport>)>>,
+
+    /// Finished reports by preview key
+    cache: HashMap<u64, ScannabilityReport>,
+}
+
+impl ScannabilityJob {
+    /// Check a new preview and measure its degradations in the background
+    ///
+    /// Replaces any measurement still running for an older preview.
+    ///
+    /// # Arguments
+    /// * `image` - Rendered preview
+    /// * `spec` - Specification the preview was rendered from
+    /// * `ctx` - egui context, repainted when the measurement finishes
+    ///
+    /// # Returns
+    /// The cached report if this preview was measured before, otherwise its
+    /// baseline check (`None` if the QR matrix cannot be created). The
+    /// complete report arrives through `poll`.
+    pub fn start(&mut self, image: &RgbaImage, spec: &QrSpec, ctx: &egui::Context) -> Option<ScannabilityReport> {
+        let key = preview_key(image, spec);
+        self.current = Some(key);
+        self.receiver = None;
+        if let Some(report) = self.cache.get(&key) {
+            return Some(report.clone());
+        }
+
+        // Nothing to degrade if the code does not scan in the first place
+        let baseline = scannability::check_baseline(image, spec).ok()?;
+        if baseline.is_complete() {
+            return Some(baseline);
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let (image, spec, ctx) = (image.clone(), spec.clone(), ctx.clone());
+        std::thread::spawn(move || {
+            let report = scannability::analyze_image(&image, &spec).ok();
+            // The receiver is gone if a newer preview replaced this job
+            if sender.send((key, report)).is_ok() {
+                ctx.request_repaint();
+            }
+        });
+        self.receiver = Some(receiver);
+        Some(baseline)
+    }
+
+    /// Forget the current preview (e.g. after a failed render)
+    pub fn cancel(&mut self) {
+        self.current = None;
+        self.receiver = None;
+    }
+
+    /// Collect a finished measurement without blocking
+    ///
+    /// # Returns
+    /// The complete report of the current preview once it is measured
+    pub fn poll(&mut self) -> Option<ScannabilityReport> {
+        let message = match self.receiver.as_ref()?.try_recv() {
+            Ok(message) => message,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => {
+                self.receiver = None;
+                return None;
+            }
+        };
+        self.receiver = None;
+
+        let (key, report) = message;
+        let report = report?;
+        if self.cache.len() >= MAX_CACHED_REPORTS {
+            self.cache.clear();
+        }
+        self.cache.insert(key, report.clone());
+        (self.current == Some(key)).then_some(report)
+    }
+}
+
+/// Cache key of a preview: its pixels plus the settings that decide what
+/// it should decode to
+fn preview_key(image: &RgbaImage, spec: &QrSpec) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    image.dimensions().hash(&mut hasher);
+    image.as_raw().hash(&mut hasher);
+    serde_json::to_string(spec).unwrap_or_default().hash(&mut hasher);
+    hasher.finish()
+}
 
 /// Render the QR code preview panel
 ///
 /// Displays the QR code texture in the center of the UI with white background.
 /// Automatically scales to use available space while maintaining aspect ratio.
+/// A badge below the code shows whether it decodes back to the entered text
+/// and its scannability score.
 ///
 /// # Arguments
 /// * `app` - Application state containing preview texture
@@ -24,7 +137,12 @@ pub fn render_preview(app: &mut QrCodeApp, ui: &mut egui::Ui, ctx: &egui::Contex
         let available = ui.available_size();
         
         // Use 90% of available space, minimum 300px, maximum 800px
-        let size = (available.x.min(available.y) * 0.9).max(300.0).min(800.0);
+        let size = (available.x.min(available.y) * 0.9).clamp(300.0, 800.0);
+
+        // The longer side gets the full size (rMQR codes are wide and short)
+        let [width, height] = texture.size();
+        let scale = size / width.max(height) as f32;
+        let display = egui::vec2(width as f32 * scale, height as f32 * scale);
         
         // Center the preview
         ui.vertical_centered(|ui| {
@@ -33,13 +151,13 @@ pub fn render_preview(app: &mut QrCodeApp, ui: &mut egui::Ui, ctx: &egui::Contex
             // Draw white background for QR code
             let rect_pos = ui.cursor().min;
             ui.painter().rect_filled(
-                egui::Rect::from_min_size(rect_pos, egui::vec2(size, size)),
+                egui::Rect::from_min_size(rect_pos, display),
                 0.0, // No rounding
                 egui::Color32::WHITE,
             );
             
             // Display QR code texture
-            ui.image((texture.id(), egui::vec2(size, size)));
+            ui.image((texture.id(), display));
             
             ui.add_space(10.0);
             
@@ -49,6 +167,36 @@ pub fn render_preview(app: &mut QrCodeApp, ui: &mut egui::Ui, ctx: &egui::Contex
                 texture.size()[0], 
                 texture.size()[1]
             ));
+            
+            // Scan check badge
+            if let Some(report) = &app.scannability {
+                ui.add_space(5.0);
+                if report.baseline.skipped() {
+                    ui.colored_label(egui::Color32::YELLOW, format!("⚠️ {}", report.baseline.summary()));
+                } else if report.baseline.passed() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(46, 160, 67),
+                        format!("✅ {}", report.baseline.summary())
+                    );
+                    if report.is_complete() {
+                        ui.colored_label(
+                            helpers::score_color(report.score()),
+                            format!("📊 {}", report.summary())
+                        );
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Measuring scannability...");
+                        });
+                    }
+                } else {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(220, 60, 60),
+                        format!("❌ {}", report.baseline.summary())
+                    );
+                    ui.label("💡 Try standard eyes, higher error correction, a smaller logo or more opacity");
+                }
+            }
         });
     } else {
         // No preview available yet
@@ -66,3 +214,31 @@ pub fn render_preview(app: &mut QrCodeApp, ui: &mut egui::Ui, ctx: &egui::Contex
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scannability_job_measures_in_background_and_caches() {
+        let ctx = egui::Context::default();
+        let spec = QrSpec::default();
+        let image = qrtistry::qr::generate_qr_image(&spec).unwrap();
+        let mut job = ScannabilityJob::default();
+
+        // The decode check is immediate, the degradations follow
+        let baseline = job.start(&image, &spec, &ctx).unwrap();
+        assert!(baseline.baseline.passed() && !baseline.is_complete());
+        let report = loop {
+            if let Some(report) = job.poll() {
+                break report;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert!(report.is_complete());
+
+        // The same preview again comes from the cache
+        assert_eq!(job.start(&image, &spec, &ctx), Some(report));
+        assert!(job.receiver.is_none());
+    }
+}
@@ -3,14 +3,18 @@
 //! Organizes all settings into four logical tabs:
 //! - Basic: Content, dimensions, error correction
 //! - Style: Colors, gradients, module/eye styles
-//! - Advanced: Opacity controls
+//! - Advanced: Opacity controls, PDF print layout
 //! - Images: Logo and background image integration
 
 use eframe::egui;
 
 use crate::app::QrCodeApp;
-use crate::types::*;
-use crate::ui::helpers;
+use qrtistry::types::*;
+use qrtistry::payload::{Payload, PayloadKind};
+use qrtistry::qr::pdf::{LengthUnit, PageSize};
+use qrtistry::qr::{self, occlusion, spec};
+use qrtistry::qr::scannability::Degradation;
+use crate::ui::{self, helpers};
 
 /// Main control panel renderer with tab selection
 ///
@@ -51,26 +55,98 @@ pub fn render_controls(app: &mut QrCodeApp, ui: &mut egui::Ui, _ctx: &egui::Cont
 /// Render the Basic settings tab
 ///
 /// Contains essential QR code settings:
-/// - Text content input
-/// - Size and border dimensions  
+/// - Content input: plain text, GS1 data or a form for WiFi, contacts,
+///   events and other structured payloads
+/// - Size, border and sizing mode
 /// - Error correction level
+/// - Version, mask, encoding mode and character set, with the resulting capacity
+/// - Splitting long content across linked codes (Structured Append)
 fn render_basic_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
     // === QR Code Content Section ===
     ui.group(|ui| {
         ui.label("📝 QR Code Content:");
-        ui.add(
-            egui::TextEdit::multiline(&mut app.qr_text)
-                .desired_width(f32::INFINITY)
-                .desired_rows(8)
-        );
-        ui.label(format!("Characters: {}", app.qr_text.len()));
+
+        // Content type: free text, GS1 data or a structured payload
+        let current = match (&app.spec.payload, app.spec.content_mode) {
+            (Some(payload), _) => payload.kind().name(),
+            (None, ContentMode::Text) => "Text",
+            (None, ContentMode::Gs1) => "GS1",
+        };
+        ui.horizontal(|ui| {
+            ui.label("Type:");
+            egui::ComboBox::from_id_salt("content_type")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (mode, name) in [(ContentMode::Text, "Text"), (ContentMode::Gs1, "GS1")] {
+                        if ui.selectable_label(current == name, name).clicked() {
+                            app.spec.payload = None;
+                            app.spec.content_mode = mode;
+                        }
+                    }
+                    ui.separator();
+                    for kind in PayloadKind::ALL {
+                        if ui.selectable_label(current == kind.name(), kind.name()).clicked() {
+                            let payload = match &app.spec.payload {
+                                Some(payload) => payload.converted(kind),
+                                None => Payload::new(kind),
+                            };
+                            app.spec.payload = Some(payload);
+                            app.spec.content_mode = ContentMode::Text;
+                        }
+                    }
+                });
+        });
+        ui.add_space(5.0);
+
+        match &mut app.spec.payload {
+            // The form is the source of truth; the text follows it
+            Some(payload) => {
+                ui::payload::render_payload_editor(ui, payload);
+                if let Some(level) = payload.required_ec_level() {
+                    app.spec.ec_level = level;
+                }
+                match payload.to_text() {
+                    Ok(text) => app.spec.qr_text = text,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::from_rgb(220, 60, 60), format!("❌ {}", e));
+                    }
+                }
+                ui.collapsing("Encoded text", |ui| {
+                    ui.monospace(&app.spec.qr_text);
+                });
+            }
+            None => {
+                ui.add(
+                    egui::TextEdit::multiline(&mut app.spec.qr_text)
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(8)
+                );
+            }
+        }
+        ui.label(format!("Characters: {}", app.spec.qr_text.len()));
         
-        if app.qr_text.len() > 500 {
+        if app.spec.qr_text.len() > 500 && app.spec.structured_append.is_none() {
             ui.colored_label(
                 egui::Color32::YELLOW,
-                "⚠️ Long text may require high error correction"
+                "⚠️ Long text makes a dense code; consider splitting it across linked codes"
             );
         }
+
+        // Parsed Application Identifiers, or what is wrong with them
+        if app.spec.content_mode == ContentMode::Gs1 {
+            ui.add_space(5.0);
+            match qr::gs1::parse(&app.spec.qr_text) {
+                Ok(elements) => {
+                    for element in elements {
+                        ui.label(format!("✅ {}  {}", element.human_readable(), element.title));
+                    }
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(220, 60, 60), format!("❌ {}", e));
+                }
+            }
+            ui.label("💡 Enter (01)09501101530003(17)251231(10)LOT42 or a GS1 Digital Link URI");
+        }
     });
 
     ui.add_space(10.0);
@@ -83,7 +159,7 @@ fn render_basic_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         // Output size slider
         ui.horizontal(|ui| {
             ui.label("Size:");
-            ui.add(egui::Slider::new(&mut app.size, 128..=2048).suffix(" px"));
+            ui.add(egui::Slider::new(&mut app.spec.size, spec::SIZE_RANGE).suffix(" px"));
         });
         
         ui.add_space(8.0);
@@ -91,11 +167,42 @@ fn render_basic_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         // Border (quiet zone) slider
         ui.horizontal(|ui| {
             ui.label("Border:");
-            ui.add(egui::Slider::new(&mut app.border, 0..=10).suffix(" modules"));
+            ui.add(egui::Slider::new(&mut app.spec.border, spec::BORDER_RANGE).suffix(" modules"));
         });
         
         ui.add_space(5.0);
         ui.label("💡 Tip: Use 2-4 modules for reliable scanning");
+
+        ui.add_space(8.0);
+
+        // Sizing mode: how the requested size is met
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Sizing:");
+            ui.selectable_value(&mut app.spec.sizing_mode, SizingMode::FitModules, "Fit Modules");
+            ui.selectable_value(&mut app.spec.sizing_mode, SizingMode::PadToSize, "Pad to Size");
+            ui.selectable_value(&mut app.spec.sizing_mode, SizingMode::Exact, "Exact");
+        });
+
+        let explanation = match app.spec.sizing_mode {
+            SizingMode::FitModules => "Whole-pixel modules; the image may be a little smaller",
+            SizingMode::PadToSize => "Whole-pixel modules centered on the exact size",
+            SizingMode::Exact => "Fractional modules fill the exact size (smoothed edges)",
+        };
+        ui.label(explanation);
+
+        // Codes of a sequence share one version, so the first gives the size
+        let (size, per_code) = match app.spec.structured_append {
+            Some(_) => (qr::structured::split(&app.spec).and_then(|parts| qr::generator::output_size(&parts[0])), " per code"),
+            None => (qr::generator::output_size(&app.spec), ""),
+        };
+        match size {
+            Ok((width, height)) => {
+                ui.label(format!("📐 Output: {} x {} px{}", width, height, per_code));
+            }
+            Err(e) => {
+                ui.colored_label(egui::Color32::from_rgb(220, 60, 60), format!("❌ {}", e));
+            }
+        }
     });
 
     ui.add_space(10.0);
@@ -105,18 +212,24 @@ fn render_basic_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         ui.label("🛡️ Error Correction:");
         ui.add_space(5.0);
         
-        // Error correction level buttons
-        ui.horizontal_wrapped(|ui| {
-            ui.selectable_value(&mut app.ec_level, ErrorCorrectionLevel::Low, "Low");
-            ui.selectable_value(&mut app.ec_level, ErrorCorrectionLevel::Medium, "Medium");
-            ui.selectable_value(&mut app.ec_level, ErrorCorrectionLevel::Quartile, "Quartile");
-            ui.selectable_value(&mut app.ec_level, ErrorCorrectionLevel::High, "High");
+        // Error correction level buttons (fixed when the payload format mandates a level)
+        let required = app.spec.payload.as_ref().and_then(|payload| payload.required_ec_level());
+        ui.add_enabled_ui(required.is_none(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.selectable_value(&mut app.spec.ec_level, ErrorCorrectionLevel::Low, "Low");
+                ui.selectable_value(&mut app.spec.ec_level, ErrorCorrectionLevel::Medium, "Medium");
+                ui.selectable_value(&mut app.spec.ec_level, ErrorCorrectionLevel::Quartile, "Quartile");
+                ui.selectable_value(&mut app.spec.ec_level, ErrorCorrectionLevel::High, "High");
+            });
         });
+        if let (Some(level), Some(payload)) = (required, &app.spec.payload) {
+            ui.label(format!("💡 {} codes always use {:?} and carry the Swiss cross", payload.kind().name(), level));
+        }
         
         ui.add_space(5.0);
         
         // Explanation
-        let explanation = match app.ec_level {
+        let explanation = match app.spec.ec_level {
             ErrorCorrectionLevel::Low => "Low (7%): Smallest QR code, minimal decoration",
             ErrorCorrectionLevel::Medium => "Medium (15%): Balanced, recommended for most uses",
             ErrorCorrectionLevel::Quartile => "Quartile (25%): Good for styled QR codes",
@@ -124,6 +237,166 @@ fn render_basic_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         };
         ui.label(explanation);
     });
+
+    ui.add_space(10.0);
+
+    // === Encoding Section ===
+    ui.group(|ui| {
+        ui.label("🔢 Encoding:");
+        ui.add_space(5.0);
+
+        // Symbol type; versions and masks are numbered per type, so reset them
+        ui.horizontal(|ui| {
+            ui.label("Symbol:");
+            let before = app.spec.symbol_type;
+            for symbol_type in [SymbolType::Qr, SymbolType::Micro, SymbolType::Rectangular] {
+                ui.selectable_value(&mut app.spec.symbol_type, symbol_type, symbol_type.name());
+            }
+            if app.spec.symbol_type != before {
+                app.spec.version = QrVersion::Auto;
+                app.spec.mask_pattern = None;
+            }
+        });
+        let (max_version, max_mask) = match app.spec.symbol_type {
+            SymbolType::Qr => (40, 7),
+            SymbolType::Micro => (4, 3),
+            SymbolType::Rectangular => (qr::rmqr::SIZES.len() as u8, 0),
+        };
+
+        // Version: automatic, or a number used as minimum or exact size
+        let mut number = match app.spec.version {
+            QrVersion::Auto => None,
+            QrVersion::Minimum(v) | QrVersion::Exact(v) => Some(v),
+        };
+        ui.horizontal(|ui| {
+            ui.label("Version:");
+            let current = number.unwrap_or(1);
+            ui.selectable_value(&mut app.spec.version, QrVersion::Auto, "Auto");
+            ui.selectable_value(&mut app.spec.version, QrVersion::Minimum(current), "At Least");
+            ui.selectable_value(&mut app.spec.version, QrVersion::Exact(current), "Exactly");
+            if let Some(v) = &mut number {
+                if ui.add(egui::DragValue::new(v).range(1..=max_version)).changed() {
+                    app.spec.version = match app.spec.version {
+                        QrVersion::Exact(_) => QrVersion::Exact(*v),
+                        _ => QrVersion::Minimum(*v),
+                    };
+                }
+                if app.spec.symbol_type == SymbolType::Rectangular {
+                    ui.label(qr::rmqr::size_name((*v).clamp(1, max_version)));
+                }
+            }
+        });
+
+        // Mask pattern: automatic or locked
+        ui.horizontal(|ui| {
+            ui.label("Mask:");
+            egui::ComboBox::from_id_salt("mask_pattern")
+                .selected_text(match app.spec.mask_pattern {
+                    Some(mask) => mask.to_string(),
+                    None => "Auto".to_string(),
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.spec.mask_pattern, None, "Auto");
+                    for mask in 0..=max_mask {
+                        ui.selectable_value(&mut app.spec.mask_pattern, Some(mask), mask.to_string());
+                    }
+                });
+        });
+
+        // Segment mode
+        ui.horizontal(|ui| {
+            ui.label("Mode:");
+            egui::ComboBox::from_id_salt("encoding_mode")
+                .selected_text(format!("{:?}", app.spec.encoding_mode))
+                .show_ui(ui, |ui| {
+                    let mode = &mut app.spec.encoding_mode;
+                    ui.selectable_value(mode, EncodingMode::Auto, "Auto");
+                    ui.selectable_value(mode, EncodingMode::Numeric, "Numeric");
+                    ui.selectable_value(mode, EncodingMode::Alphanumeric, "Alphanumeric");
+                    ui.selectable_value(mode, EncodingMode::Byte, "Byte");
+                    ui.selectable_value(mode, EncodingMode::Kanji, "Kanji");
+                });
+        });
+
+        // Character set, announced to scanners with an ECI designator
+        ui.horizontal(|ui| {
+            ui.label("Character Set:");
+            egui::ComboBox::from_id_salt("character_set")
+                .selected_text(app.spec.character_set.name())
+                .show_ui(ui, |ui| {
+                    for charset in CharacterSet::ALL {
+                        ui.selectable_value(&mut app.spec.character_set, charset, charset.name());
+                    }
+                });
+        });
+        if let Some(warning) = qr::eci::warning(&app.spec.qr_text, app.spec.character_set) {
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠️ {}", warning));
+        }
+
+        ui.add_space(5.0);
+        if app.spec.structured_append.is_none() {
+            match qr::encoding::encode(&app.spec) {
+                Ok(encoded) => {
+                    ui.label(format!("📊 {}", encoded.info.summary()));
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(220, 60, 60), format!("❌ {}", e));
+                }
+            }
+        }
+        ui.label("💡 Fix the version to keep the module size the same across a product line");
+        if app.spec.symbol_type != SymbolType::Qr {
+            ui.label(format!(
+                "⚠️ {} needs a compatible scanner and is not covered by the built-in scan check",
+                app.spec.symbol_type.name()
+            ));
+        }
+        if app.spec.symbol_type == SymbolType::Rectangular {
+            ui.label("💡 rMQR supports Medium and High error correction only");
+        }
+        if app.spec.character_set != CharacterSet::Auto {
+            ui.label("💡 Pick a character set only for scanners that ignore UTF-8; phone apps read Auto best");
+        }
+    });
+
+    ui.add_space(10.0);
+
+    // === Structured Append Section ===
+    ui.group(|ui| {
+        ui.label("🔗 Linked Codes:");
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            let mut enabled = app.spec.structured_append.is_some();
+            if ui.checkbox(&mut enabled, "Split across").changed() {
+                app.spec.structured_append = enabled.then_some(2);
+            }
+            if let Some(count) = &mut app.spec.structured_append {
+                ui.add(egui::DragValue::new(count).range(2..=qr::structured::MAX_SYMBOLS));
+            }
+            ui.label("codes (Structured Append)");
+        });
+
+        if app.spec.structured_append.is_none() {
+            ui.label("💡 Split long content into up to 16 linked codes that scanners join in order");
+            return;
+        }
+
+        ui.add_space(5.0);
+        // Every code of the sequence has the same version, so one summary covers all
+        let first = qr::structured::split(&app.spec)
+            .and_then(|parts| qr::encoding::encode(&parts[0]).map(|encoded| (parts.len(), encoded)));
+        match first {
+            Ok((count, encoded)) => {
+                ui.label(format!("📊 Code 1 of {}: {}", count, encoded.info.summary()));
+            }
+            Err(e) => {
+                ui.colored_label(egui::Color32::from_rgb(220, 60, 60), format!("❌ {}", e));
+            }
+        }
+        ui.label("💡 Saving writes one numbered file per code plus a combined sheet");
+        ui.label("⚠️ Scanners without Structured Append support read each code on its own");
+    });
 }
 
 // ============================================================================
@@ -146,20 +419,20 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         // Foreground color picker
         ui.horizontal(|ui| {
             ui.label("Foreground:");
-            helpers::color_picker(ui, &mut app.fg_color);
+            helpers::color_picker(ui, &mut app.spec.fg_color);
             ui.label(format!(
                 "RGB({}, {}, {})",
-                app.fg_color[0], app.fg_color[1], app.fg_color[2]
+                app.spec.fg_color[0], app.spec.fg_color[1], app.spec.fg_color[2]
             ));
         });
 
         // Background color picker
         ui.horizontal(|ui| {
             ui.label("Background:");
-            helpers::color_picker(ui, &mut app.bg_color);
+            helpers::color_picker(ui, &mut app.spec.bg_color);
             ui.label(format!(
                 "RGB({}, {}, {})",
-                app.bg_color[0], app.bg_color[1], app.bg_color[2]
+                app.spec.bg_color[0], app.spec.bg_color[1], app.spec.bg_color[2]
             ));
         });
 
@@ -169,8 +442,8 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         ui.horizontal_wrapped(|ui| {
             for preset in COLOR_PRESETS {
                 if ui.button(preset.name).clicked() {
-                    app.fg_color = preset.fg;
-                    app.bg_color = preset.bg;
+                    app.spec.fg_color = preset.fg;
+                    app.spec.bg_color = preset.bg;
                     app.status_message = format!("Applied {} preset", preset.name);
                 }
             }
@@ -183,28 +456,28 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
     ui.group(|ui| {
         ui.label("🌈 Gradient Options:");
         
-        ui.checkbox(&mut app.use_gradient, "Enable Gradient");
+        ui.checkbox(&mut app.spec.use_gradient, "Enable Gradient");
         
-        if app.use_gradient {
+        if app.spec.use_gradient {
             ui.add_space(5.0);
             
             // Gradient type selector
             ui.horizontal(|ui| {
                 ui.label("Type:");
                 egui::ComboBox::from_id_salt("gradient_type")
-                    .selected_text(format!("{:?}", app.gradient_type))
+                    .selected_text(format!("{:?}", app.spec.gradient_type))
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut app.gradient_type, GradientType::Horizontal, "Horizontal");
-                        ui.selectable_value(&mut app.gradient_type, GradientType::Vertical, "Vertical");
-                        ui.selectable_value(&mut app.gradient_type, GradientType::Radial, "Radial");
-                        ui.selectable_value(&mut app.gradient_type, GradientType::Diagonal, "Diagonal");
+                        ui.selectable_value(&mut app.spec.gradient_type, GradientType::Horizontal, "Horizontal");
+                        ui.selectable_value(&mut app.spec.gradient_type, GradientType::Vertical, "Vertical");
+                        ui.selectable_value(&mut app.spec.gradient_type, GradientType::Radial, "Radial");
+                        ui.selectable_value(&mut app.spec.gradient_type, GradientType::Diagonal, "Diagonal");
                     });
             });
 
             // Gradient end color picker
             ui.horizontal(|ui| {
                 ui.label("End Color:");
-                helpers::color_picker(ui, &mut app.gradient_color);
+                helpers::color_picker(ui, &mut app.spec.gradient_color);
             });
             
             ui.add_space(3.0);
@@ -222,24 +495,27 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.label("Shape:");
             egui::ComboBox::from_id_salt("module_style")
-                .selected_text(format!("{:?}", app.module_style))
+                .selected_text(format!("{:?}", app.spec.module_style))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut app.module_style, ModuleStyle::Square, "Square");
-                    ui.selectable_value(&mut app.module_style, ModuleStyle::Circle, "Circle");
-                    ui.selectable_value(&mut app.module_style, ModuleStyle::RoundedSquare, "Rounded Square");
-                    ui.selectable_value(&mut app.module_style, ModuleStyle::Dots, "Dots");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::Square, "Square");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::Circle, "Circle");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::RoundedSquare, "Rounded Square");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::Dots, "Dots");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::Liquid, "Liquid");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::VerticalLines, "Vertical Lines");
+                    ui.selectable_value(&mut app.spec.module_style, ModuleStyle::HorizontalLines, "Horizontal Lines");
                 });
         });
 
         ui.add_space(5.0);
         
         // Extra rounding option
-        ui.checkbox(&mut app.use_rounded_corners, "Extra Rounded Corners");
+        ui.checkbox(&mut app.spec.use_rounded_corners, "Extra Rounded Corners");
         
-        if app.use_rounded_corners {
+        if app.spec.use_rounded_corners {
             ui.horizontal(|ui| {
                 ui.label("Corner Radius:");
-                ui.add(egui::Slider::new(&mut app.corner_radius, 0.0..=1.0));
+                ui.add(egui::Slider::new(&mut app.spec.corner_radius, 0.0..=1.0));
             });
         }
         
@@ -253,30 +529,59 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
     ui.group(|ui| {
         ui.label("👁️ Finder Pattern (Eyes):");
         
-        // Eye style selector
+        // Named styles set frame and pupil together
         ui.horizontal(|ui| {
             ui.label("Style:");
             egui::ComboBox::from_id_salt("eye_style")
-                .selected_text(format!("{:?}", app.eye_style))
+                .selected_text(app.spec.eye_style.preset_name().unwrap_or("Custom"))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut app.eye_style, EyeStyle::Standard, "Standard");
-                    ui.selectable_value(&mut app.eye_style, EyeStyle::Circle, "Circle");
-                    ui.selectable_value(&mut app.eye_style, EyeStyle::RoundedSquare, "Rounded");
-                    ui.selectable_value(&mut app.eye_style, EyeStyle::Flower, "Flower");
-                    ui.selectable_value(&mut app.eye_style, EyeStyle::Diamond, "Diamond");
+                    for (name, style) in EyeStyle::PRESETS {
+                        ui.selectable_value(&mut app.spec.eye_style, style, name);
+                    }
                 });
         });
 
+        // Frame and pupil shape selectors
+        eye_shape_combos(ui, "eye", &mut app.spec.eye_style);
+
         ui.add_space(5.0);
         
         // Custom eye color option
-        ui.checkbox(&mut app.use_custom_eye_color, "Custom Eye Color");
+        ui.checkbox(&mut app.spec.use_custom_eye_color, "Custom Eye Colors");
         
-        if app.use_custom_eye_color {
+        if app.spec.use_custom_eye_color {
             ui.horizontal(|ui| {
-                ui.label("Eye Color:");
-                helpers::color_picker(ui, &mut app.eye_color);
+                ui.label("Frame Color:");
+                helpers::color_picker(ui, &mut app.spec.eye_color);
             });
+
+            // The pupil follows the frame color unless it has its own
+            let mut separate_pupil = app.spec.eye_pupil_color.is_some();
+            if ui.checkbox(&mut separate_pupil, "Different Pupil Color").changed() {
+                app.spec.eye_pupil_color = separate_pupil.then_some(app.spec.eye_color);
+            }
+            if let Some(pupil_color) = &mut app.spec.eye_pupil_color {
+                ui.horizontal(|ui| {
+                    ui.label("Pupil Color:");
+                    helpers::color_picker(ui, pupil_color);
+                });
+            }
+        }
+        
+        ui.add_space(5.0);
+        ui.separator();
+
+        // === Individual Eyes ===
+        ui.label("Individual Eyes:");
+        for position in EyePosition::ALL {
+            render_eye_override(app, ui, position);
+        }
+
+        // Asymmetric shapes (leaf) are drawn for the top-left corner
+        if ui.button("↪ Point Eyes Inward").clicked() {
+            for position in EyePosition::ALL {
+                app.spec.eye_override_mut(position).rotation = position.inward_rotation();
+            }
         }
         
         ui.add_space(3.0);
@@ -284,6 +589,101 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
     });
 }
 
+/// Frame and pupil shape selectors for an eye style
+///
+/// # Arguments
+/// * `ui` - egui UI context
+/// * `id` - Prefix keeping the combo box ids unique
+/// * `style` - Eye style to edit
+fn eye_shape_combos(ui: &mut egui::Ui, id: &str, style: &mut EyeStyle) {
+    ui.horizontal(|ui| {
+        ui.label("Frame:");
+        egui::ComboBox::from_id_salt(format!("{}_frame", id))
+            .selected_text(format!("{:?}", style.frame))
+            .show_ui(ui, |ui| {
+                let frame = &mut style.frame;
+                ui.selectable_value(frame, EyeFrameStyle::Square, "Square");
+                ui.selectable_value(frame, EyeFrameStyle::Rounded, "Rounded");
+                ui.selectable_value(frame, EyeFrameStyle::Circle, "Circle");
+                ui.selectable_value(frame, EyeFrameStyle::Leaf, "Leaf");
+                ui.selectable_value(frame, EyeFrameStyle::Dotted, "Dotted");
+                ui.selectable_value(frame, EyeFrameStyle::Diamond, "Diamond");
+            });
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Pupil:");
+        egui::ComboBox::from_id_salt(format!("{}_pupil", id))
+            .selected_text(format!("{:?}", style.pupil))
+            .show_ui(ui, |ui| {
+                let pupil = &mut style.pupil;
+                ui.selectable_value(pupil, EyePupilStyle::Square, "Square");
+                ui.selectable_value(pupil, EyePupilStyle::Rounded, "Rounded");
+                ui.selectable_value(pupil, EyePupilStyle::Circle, "Circle");
+                ui.selectable_value(pupil, EyePupilStyle::Diamond, "Diamond");
+                ui.selectable_value(pupil, EyePupilStyle::Star, "Star");
+            });
+    });
+}
+
+/// Override controls for one eye
+///
+/// Unchecking the eye removes its override so it follows the shared settings again.
+fn render_eye_override(app: &mut QrCodeApp, ui: &mut egui::Ui, position: EyePosition) {
+    let mut customized = app.spec.eye_override(position).is_some();
+    if ui.checkbox(&mut customized, format!("Customize {}", position.name())).changed() {
+        if customized {
+            let design = app.spec.eye_design(position);
+            let eye = app.spec.eye_override_mut(position);
+            eye.style = Some(design.style);
+        } else {
+            app.spec.eye_overrides.retain(|o| o.position != position);
+        }
+    }
+    if !customized {
+        return;
+    }
+
+    let shared_style = app.spec.eye_style;
+    let base_color = if app.spec.use_custom_eye_color { app.spec.eye_color } else { app.spec.fg_color };
+    let eye = app.spec.eye_override_mut(position);
+
+    ui.indent(position.name(), |ui| {
+        let style = eye.style.get_or_insert(shared_style);
+        eye_shape_combos(ui, position.name(), style);
+
+        // Colors fall back to the shared eye colors when unset
+        let mut own_color = eye.frame_color.is_some();
+        if ui.checkbox(&mut own_color, "Own Color").changed() {
+            eye.frame_color = own_color.then_some(base_color);
+        }
+        if let Some(color) = &mut eye.frame_color {
+            ui.horizontal(|ui| {
+                ui.label("Color:");
+                helpers::color_picker(ui, color);
+            });
+        }
+
+        let mut own_pupil = eye.pupil_color.is_some();
+        if ui.checkbox(&mut own_pupil, "Own Pupil Color").changed() {
+            eye.pupil_color = own_pupil.then_some(eye.frame_color.unwrap_or(base_color));
+        }
+        if let Some(color) = &mut eye.pupil_color {
+            ui.horizontal(|ui| {
+                ui.label("Pupil Color:");
+                helpers::color_picker(ui, color);
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Rotation:");
+            for degrees in [0, 90, 180, 270] {
+                ui.selectable_value(&mut eye.rotation, degrees, format!("{}°", degrees));
+            }
+        });
+    });
+}
+
 // ============================================================================
 // Advanced Tab
 // ============================================================================
@@ -292,6 +692,10 @@ fn render_style_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
 ///
 /// Contains fine-tuning options:
 /// - Overall QR code opacity
+/// - Anti-aliasing quality
+/// - Alignment and timing pattern styles
+/// - Scannability report of the last preview
+/// - Physical page layout for PDF export
 fn render_advanced_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
     ui.group(|ui| {
         ui.label("🔍 Opacity Controls:");
@@ -299,20 +703,115 @@ fn render_advanced_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         // QR opacity slider
         ui.horizontal(|ui| {
             ui.label("QR Opacity:");
-            ui.add(egui::Slider::new(&mut app.qr_opacity, 0.0..=1.0));
+            ui.add(egui::Slider::new(&mut app.spec.qr_opacity, 0.0..=1.0));
         });
         
         ui.add_space(5.0);
         ui.label("Use lower opacity for watermark effects or subtle integration with backgrounds");
         
-        if app.qr_opacity < 0.5 {
-            ui.colored_label(
-                egui::Color32::YELLOW,
-                "⚠️ Low opacity may reduce scannability"
-            );
-        }
+        // Opacity mostly costs contrast: show how much the last preview had to spare
+        helpers::scannability_margin(ui, app.scannability.as_ref(), Degradation::Contrast);
     });
     
+    ui.add_space(10.0);
+
+    // === Rendering Quality Section ===
+    ui.group(|ui| {
+        ui.label("✨ Rendering Quality:");
+
+        ui.horizontal(|ui| {
+            ui.label("Anti-aliasing:");
+            ui.selectable_value(&mut app.spec.antialiasing, Antialiasing::Off, "Off");
+            ui.selectable_value(&mut app.spec.antialiasing, Antialiasing::Standard, "Standard");
+            ui.selectable_value(&mut app.spec.antialiasing, Antialiasing::High, "High");
+        });
+
+        let explanation = match app.spec.antialiasing {
+            Antialiasing::Off => "Hard pixel edges (fastest; curves look jagged at small sizes)",
+            Antialiasing::Standard => "Smooth edges on circles, dots and rounded shapes",
+            Antialiasing::High => "Smoothest edges (slower on large images)",
+        };
+        ui.label(explanation);
+        ui.label("💡 Applies to PNG/JPEG output; SVG and PDF are always smooth");
+    });
+
+    ui.add_space(10.0);
+
+    // === Function Pattern Section ===
+    ui.group(|ui| {
+        ui.label("🧭 Alignment & Timing Patterns:");
+        pattern_style_selector(ui, "Alignment:", &mut app.spec.alignment_style);
+        pattern_style_selector(ui, "Timing:", &mut app.spec.timing_style);
+
+        ui.add_space(5.0);
+        ui.label("💡 Scanners use these patterns to correct for distortion; Solid is the safest");
+    });
+
+    ui.add_space(10.0);
+
+    // === Scannability Report Section ===
+    ui.group(|ui| {
+        ui.label("📊 Scannability Report:");
+        helpers::scannability_score(ui, app.scannability.as_ref());
+
+        if let Some(report) = app.scannability.as_ref().filter(|r| r.baseline.passed()) {
+            ui.add_space(5.0);
+            egui::Grid::new("scannability_grid").striped(true).show(ui, |ui| {
+                for result in &report.results {
+                    ui.label(result.degradation.name());
+                    ui.colored_label(
+                        helpers::score_color((result.margin() * 100.0).round() as u32),
+                        format!("{}/{}", result.levels_passed, result.levels_total),
+                    );
+                    ui.label(result.summary());
+                    ui.end_row();
+                }
+            });
+        }
+
+        ui.add_space(5.0);
+        ui.label("Each degradation is applied with increasing severity until the code stops decoding");
+    });
+
+    ui.add_space(10.0);
+
+    // === PDF Print Layout Section ===
+    ui.group(|ui| {
+        ui.label("📄 Print Layout (PDF):");
+        let layout = &mut app.spec.pdf_options;
+
+        ui.horizontal(|ui| {
+            ui.label("Units:");
+            ui.selectable_value(&mut layout.unit, LengthUnit::Millimeters, "mm");
+            ui.selectable_value(&mut layout.unit, LengthUnit::Inches, "in");
+        });
+
+        // Page size presets
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Page:");
+            for page in PageSize::ALL {
+                if ui.button(page.label()).clicked() {
+                    layout.set_page_size(page);
+                }
+            }
+        });
+
+        let unit = layout.unit;
+        length_input(ui, "Page Width:", &mut layout.page_width_mm, unit);
+        length_input(ui, "Page Height:", &mut layout.page_height_mm, unit);
+        length_input(ui, "QR Code Size:", &mut layout.qr_size_mm, unit);
+        length_input(ui, "Bleed:", &mut layout.bleed_mm, unit);
+
+        ui.checkbox(&mut layout.crop_marks, "Crop Marks");
+        ui.checkbox(&mut layout.fill_page_background, "Fill Page with Background Color");
+
+        ui.add_space(5.0);
+        if let Err(e) = layout.validate() {
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠️ {}", e));
+        }
+        ui.label("💡 Save as PDF to export vector artwork for printing");
+    });
+
     ui.add_space(10.0);
     
     // Placeholder for future advanced settings
@@ -321,11 +820,40 @@ fn render_advanced_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
         ui.label("This tab is reserved for advanced features.");
         ui.label("More options may be added in future versions:");
         ui.label("• Fine-tune module spacing");
-        ui.label("• Custom timing patterns");
         ui.label("• Advanced color blending modes");
     });
 }
 
+/// Selectable values for an alignment or timing pattern style
+fn pattern_style_selector(ui: &mut egui::Ui, label: &str, style: &mut PatternStyle) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.selectable_value(style, PatternStyle::Solid, "Solid");
+        ui.selectable_value(style, PatternStyle::Rounded, "Rounded");
+        ui.selectable_value(style, PatternStyle::Circle, "Circle");
+        ui.selectable_value(style, PatternStyle::MatchModules, "Match Modules");
+    });
+}
+
+/// Numeric input for a length stored in millimeters, shown in `unit`
+fn length_input(ui: &mut egui::Ui, label: &str, value_mm: &mut f32, unit: LengthUnit) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let mut shown = unit.mm_to_unit(*value_mm);
+        let speed = if unit == LengthUnit::Inches { 0.01 } else { 0.5 };
+        let response = ui.add(
+            egui::DragValue::new(&mut shown)
+                .range(0.0..=f32::MAX)
+                .speed(speed)
+                .max_decimals(3)
+                .suffix(format!(" {}", unit.suffix()))
+        );
+        if response.changed() {
+            *value_mm = unit.unit_to_mm(shown);
+        }
+    });
+}
+
 // ============================================================================
 // Images Tab
 // ============================================================================
@@ -333,7 +861,7 @@ fn render_advanced_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
 /// Render the Images tab
 ///
 /// Handles image integration:
-/// - Logo overlay (center of QR code)
+/// - Logo overlay (center of QR code) and its error correction budget
 /// - Background image blending
 fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
     // === Logo Section ===
@@ -349,7 +877,7 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
                 {
                     match image::open(&path) {
                         Ok(img) => {
-                            app.logo_image = Some(img);
+                            app.spec.logo_image = Some(img);
                             app.logo_path = Some(path.clone());
                             app.status_message = format!("Logo loaded: {}", path.display());
                         }
@@ -360,12 +888,10 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
                 }
             }
 
-            if app.logo_path.is_some() {
-                if ui.button("❌ Clear").clicked() {
-                    app.logo_path = None;
-                    app.logo_image = None;
-                    app.status_message = "Logo cleared".to_string();
-                }
+            if app.logo_path.is_some() && ui.button("❌ Clear").clicked() {
+                app.logo_path = None;
+                app.spec.logo_image = None;
+                app.status_message = "Logo cleared".to_string();
             }
         });
 
@@ -377,7 +903,7 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
             // Logo size slider
             ui.horizontal(|ui| {
                 ui.label("Logo Size:");
-                ui.add(egui::Slider::new(&mut app.logo_size, 0.05..=0.35)
+                ui.add(egui::Slider::new(&mut app.spec.logo_size, 0.05..=0.35)
                     .suffix("%")
                     .custom_formatter(|n, _| format!("{:.0}%", n * 100.0))
                 );
@@ -385,22 +911,43 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
             
             ui.add_space(5.0);
             
-            // Warning about scannability
-            if app.logo_size > 0.25 {
-                ui.colored_label(
-                    egui::Color32::YELLOW,
-                    "⚠️ Large logos may reduce scannability"
-                );
-            } else {
-                ui.label("💡 Keep logo under 30% for best scannability");
+            // Codewords hidden by the logo versus the error correction budget
+            match occlusion::analyze(&app.spec) {
+                Ok(Some(report)) => {
+                    let color = if report.fits(app.spec.logo_safety_margin) {
+                        egui::Color32::from_rgb(46, 160, 67)
+                    } else if report.budget_used() <= 1.0 {
+                        egui::Color32::YELLOW
+                    } else {
+                        egui::Color32::from_rgb(220, 60, 60)
+                    };
+                    ui.colored_label(color, format!("🛡️ {}", report.summary()));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(220, 60, 60), format!("❌ {}", e));
+                }
             }
-            
-            if app.ec_level != ErrorCorrectionLevel::High {
-                ui.colored_label(
-                    egui::Color32::LIGHT_BLUE,
-                    "💡 Tip: Use High error correction with logos"
+
+            // Automatic fitting when the budget is exceeded
+            ui.horizontal(|ui| {
+                ui.label("Auto-Fit:");
+                ui.selectable_value(&mut app.spec.logo_fit, LogoFit::Off, "Off");
+                ui.selectable_value(&mut app.spec.logo_fit, LogoFit::RaiseErrorCorrection, "Raise EC");
+                ui.selectable_value(&mut app.spec.logo_fit, LogoFit::ShrinkLogo, "Shrink Logo");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Safety Margin:");
+                ui.add(egui::Slider::new(&mut app.spec.logo_safety_margin, 0.0..=0.9)
+                    .custom_formatter(|n, _| format!("{:.0}%", n * 100.0))
                 );
-            }
+            });
+            ui.label("💡 Auto-fit is applied when the preview is generated");
+
+            ui.add_space(5.0);
+
+            // Measured scannability of the last preview
+            helpers::scannability_score(ui, app.scannability.as_ref());
         } else {
             ui.add_space(5.0);
             ui.label("No logo selected. A logo will be centered on the QR code.");
@@ -422,7 +969,7 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
                 {
                     match image::open(&path) {
                         Ok(img) => {
-                            app.bg_image = Some(img);
+                            app.spec.bg_image = Some(img);
                             app.bg_image_path = Some(path.clone());
                             app.status_message = format!("Background loaded: {}", path.display());
                         }
@@ -433,12 +980,10 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
                 }
             }
 
-            if app.bg_image_path.is_some() {
-                if ui.button("❌ Clear").clicked() {
-                    app.bg_image_path = None;
-                    app.bg_image = None;
-                    app.status_message = "Background cleared".to_string();
-                }
+            if app.bg_image_path.is_some() && ui.button("❌ Clear").clicked() {
+                app.bg_image_path = None;
+                app.spec.bg_image = None;
+                app.status_message = "Background cleared".to_string();
             }
         });
 
@@ -450,7 +995,7 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
             // Background opacity slider
             ui.horizontal(|ui| {
                 ui.label("Opacity:");
-                ui.add(egui::Slider::new(&mut app.bg_image_opacity, 0.0..=1.0)
+                ui.add(egui::Slider::new(&mut app.spec.bg_image_opacity, 0.0..=1.0)
                     .custom_formatter(|n, _| format!("{:.0}%", n * 100.0))
                 );
             });
@@ -458,12 +1003,7 @@ fn render_images_tab(app: &mut QrCodeApp, ui: &mut egui::Ui) {
             ui.add_space(5.0);
             ui.label("Background image will be resized and blended behind the QR code");
             
-            if app.bg_image_opacity > 0.7 {
-                ui.colored_label(
-                    egui::Color32::YELLOW,
-                    "⚠️ High background opacity may reduce contrast"
-                );
-            }
+            helpers::scannability_margin(ui, app.scannability.as_ref(), Degradation::Contrast);
         } else {
             ui.add_space(5.0);
             ui.label("No background image selected.");